@@ -1,26 +1,34 @@
 use crate::api::handlers;
+use crate::application::bundle_import::ImportResult;
 use crate::application::state::AppState;
 use tauri::State;
 
-/// Imports an ebook file at the given path into the library.
+/// Imports an ebook file, or the ebooks in a ZIP bundle, into the library.
+///
+/// A `.zip` or `.cbz` bundle is unpacked and its EPUB, PDF and MOBI entries
+/// imported, as by `import_paths`; a bundle that is really an EPUB is
+/// imported as one. New books are queued for full-text indexing.
 ///
 /// # Arguments
 ///
-/// * `path` - Absolute path to the ebook file (`.epub` or `.pdf`).
+/// * `path` - Absolute path to the ebook file (`.epub`, `.pdf`, `.mobi`,
+///   `.azw3` or `.azw`) or bundle (`.zip`, `.cbz`).
 ///
 /// # Returns
 ///
-/// The imported book as a [`BookDto`](crate::domain::dto::book_dto::BookDto).
+/// One result per imported file, with a `status` (`imported` with `path`
+/// and the new `book`, `duplicate` or `failed` with a `reason`): a single
+/// `imported` result for an ebook, one per entry for a bundle.
 ///
 /// # Errors
 ///
-/// Returns an error string if the file cannot be parsed, already exists
-/// (duplicate checksum), or has an unsupported format.
+/// Returns an error string if an ebook file cannot be parsed, already
+/// exists (duplicate checksum), or has an unsupported format.
 #[tauri::command]
 pub async fn import_book(
     path: String,
     state: State<'_, AppState>,
-) -> Result<crate::domain::dto::book_dto::BookDto, String> {
+) -> Result<Vec<ImportResult>, String> {
     handlers::book_handler::import_book(path, &state)
         .await
         .map_err(|e| e.to_string())
@@ -31,13 +39,15 @@ pub async fn import_book(
 /// # Arguments
 ///
 /// * `path` - Absolute path to the EPUB file.
+/// * `book_id` - Optional database ID of the book. When given, images are
+///   linked through the `book://` protocol instead of being inlined.
 ///
 /// # Returns
 ///
-/// A self-contained HTML string with inline base64 images.
+/// The concatenated body HTML of every spine item.
 #[tauri::command]
-pub async fn read_epub(path: String) -> Result<String, String> {
-    handlers::book_handler::read_epub(path)
+pub async fn read_epub(path: String, book_id: Option<i32>) -> Result<String, String> {
+    handlers::book_handler::read_epub(path, book_id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -47,7 +57,13 @@ pub async fn read_epub(path: String) -> Result<String, String> {
 /// # Arguments
 ///
 /// * `path` - Absolute path to the ebook file.
-/// * `file_type` - Either `"epub"` (returns HTML) or `"pdf"` (returns rendered page).
+/// * `file_type` - `"epub"` or `"mobi"` (returns HTML) or `"pdf"` (returns rendered page).
+/// * `chapter` - Optional EPUB spine index; when set only that chapter is
+///   returned (as `EpubChapters`) instead of the whole book.
+/// * `prefetch` - Optional number of neighbouring chapters to include on each
+///   side of `chapter`.
+/// * `book_id` - Optional database ID of the book. When given, EPUB images
+///   are linked through the `book://` protocol instead of being inlined.
 ///
 /// # Errors
 ///
@@ -56,16 +72,132 @@ pub async fn read_epub(path: String) -> Result<String, String> {
 pub async fn read_book(
     path: String,
     file_type: String,
+    chapter: Option<usize>,
+    prefetch: Option<usize>,
+    book_id: Option<i32>,
 ) -> Result<crate::application::book::BookContent, String> {
     match file_type.as_str() {
-        "epub" | "pdf" => {}
+        "epub" | "pdf" | "mobi" => {}
         _ => return Err(format!("Unsupported file type: {}", file_type)),
     }
-    handlers::book_handler::read_book(path, file_type)
+    handlers::book_handler::read_book(path, file_type, chapter, prefetch, book_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Lists the spine (reading order) of an EPUB file.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+///
+/// # Returns
+///
+/// One [`EpubSpineItem`](crate::infrastructure::file_handlers::epub_handler::EpubSpineItem)
+/// per spine entry with its index, idref, href and byte length.
+#[tauri::command]
+pub async fn get_epub_spine(
+    path: String,
+) -> Result<Vec<crate::infrastructure::file_handlers::epub_handler::EpubSpineItem>, String> {
+    handlers::book_handler::get_epub_spine(path)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Renders a single EPUB chapter by spine index.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+/// * `index` - 0-based spine index of the chapter.
+/// * `prefetch` - Optional number of neighbouring chapters to render on each
+///   side of `index`.
+/// * `book_id` - Optional database ID of the book. When given, images are
+///   linked through the `book://` protocol instead of being inlined.
+///
+/// # Returns
+///
+/// The rendered [`EpubChapter`](crate::infrastructure::file_handlers::epub_handler::EpubChapter)s
+/// in spine order, each with sanitized body HTML.
+#[tauri::command]
+pub async fn read_epub_chapter(
+    path: String,
+    index: usize,
+    prefetch: Option<usize>,
+    book_id: Option<i32>,
+) -> Result<Vec<crate::infrastructure::file_handlers::epub_handler::EpubChapter>, String> {
+    handlers::book_handler::read_epub_chapter(path, index, prefetch, book_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Returns the table of contents of a book.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+///
+/// # Returns
+///
+/// A nested tree of [`TocEntry`](crate::infrastructure::file_handlers::TocEntry)
+/// nodes with their label, EPUB href/fragment/spine index or PDF page, depth,
+/// and, for PDFs, a `position` usable as `reading_progress.current_position`.
+#[tauri::command]
+pub async fn get_table_of_contents(
+    book_id: i32,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::infrastructure::file_handlers::TocEntry>, String> {
+    handlers::book_handler::get_table_of_contents(book_id, &state)
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Searches the full text of a book.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `query` - Text to search for.
+/// * `case_insensitive` - Optional; match regardless of case. Defaults to `false`.
+/// * `whole_word` - Optional; only match whole words. Defaults to `false`.
+/// * `regex` - Optional; treat `query` as a regular expression. Defaults to `false`.
+/// * `max_hits` - Optional; maximum number of hits to return. Defaults to
+///   [`MAX_SEARCH_HITS`](crate::utils::search::MAX_SEARCH_HITS).
+///
+/// # Returns
+///
+/// [`SearchResults`](crate::infrastructure::file_handlers::SearchResults)
+/// holding the first hits, each with the EPUB spine index or PDF page,
+/// character offset, snippet and (for PDFs) the bounding box of the match,
+/// and a `truncated` flag set when the book has more hits.
+///
+/// # Errors
+///
+/// Returns an error string if the book does not exist, the regex is invalid
+/// or the file cannot be read.
+#[tauri::command]
+pub async fn search_in_book(
+    book_id: i32,
+    query: String,
+    case_insensitive: Option<bool>,
+    whole_word: Option<bool>,
+    regex: Option<bool>,
+    max_hits: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<crate::infrastructure::file_handlers::SearchResults, String> {
+    handlers::book_handler::search_in_book(
+        book_id,
+        query,
+        case_insensitive,
+        whole_word,
+        regex,
+        max_hits,
+        &state,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
 /// Returns the total number of pages in a PDF file.
 ///
 /// # Arguments
@@ -96,21 +228,74 @@ pub async fn read_pdf_page(path: String, page_number: u32) -> Result<crate::infr
         .map_err(|e| e.to_string())
 }
 
-/// Returns all books in the library.
+/// Returns the books in the library, optionally filtered.
+///
+/// # Arguments
+///
+/// * `favorites_only` - When `true`, only favorite books are returned.
+/// * `min_rating` - Optional minimum star rating (`0`–`5`); unrated books
+///   are excluded when set.
 ///
 /// # Returns
 ///
 /// A vector of [`BookDto`](crate::domain::dto::book_dto::BookDto) for every
-/// book, each resolved with author and publisher names.
+/// matching book, ordered by title and resolved with author and publisher
+/// names and reading progress.
 #[tauri::command]
 pub async fn list_books(
+    favorites_only: Option<bool>,
+    min_rating: Option<f32>,
     state: State<'_, AppState>,
 ) -> Result<Vec<crate::domain::dto::book_dto::BookDto>, String> {
-    handlers::book_handler::list_books(&state)
+    handlers::book_handler::list_books(favorites_only, min_rating, &state)
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Returns one page of the library, sorted and filtered, with a total count.
+///
+/// # Arguments
+///
+/// * `offset` - Number of matching books to skip (default `0`).
+/// * `limit` - Page size (default `50`, at most `500`).
+/// * `sort_by` - `"title"` (default), `"author"`, `"added_at"` or
+///   `"last_read_at"`.
+/// * `descending` - Reverse the sort order.
+/// * `file_type` - Only include books of this type (`"epub"`, `"pdf"`, `"mobi"`).
+/// * `favorites_only` - Only include favorite books.
+/// * `min_rating` - Only include books rated at least this many stars.
+///
+/// # Returns
+///
+/// A [`BookPageDto`](crate::domain::dto::book_dto::BookPageDto) with the
+/// page's books (including all author names and reading progress) and the
+/// total number of matching books.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn list_books_page(
+    offset: Option<i64>,
+    limit: Option<i64>,
+    sort_by: Option<crate::domain::repository::BookSort>,
+    descending: Option<bool>,
+    file_type: Option<String>,
+    favorites_only: Option<bool>,
+    min_rating: Option<f32>,
+    state: State<'_, AppState>,
+) -> Result<crate::domain::dto::book_dto::BookPageDto, String> {
+    handlers::book_handler::list_books_page(
+        offset,
+        limit,
+        sort_by,
+        descending,
+        file_type,
+        favorites_only,
+        min_rating,
+        &state,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
 /// Returns details for a single book by ID.
 ///
 /// # Arguments
@@ -132,19 +317,25 @@ pub async fn get_book_details(
 
 /// Returns the cover image bytes for a book.
 ///
+/// Covers are served from the thumbnail cache written at import and
+/// regenerated when missing or older than the ebook file.
+///
 /// # Arguments
 ///
 /// * `book_id` - The book's database ID.
+/// * `size` - `"list"`, `"grid"` or `"detail"`; defaults to `"detail"`.
 ///
 /// # Returns
 ///
-/// `Some(bytes)` with the raw cover image, or `None` if unavailable.
+/// `Some(bytes)` with the JPEG thumbnail, or `None` if unavailable. Covers
+/// that cannot be decoded are returned as extracted from the file.
 #[tauri::command]
 pub async fn get_cover_img(
     book_id: i32,
+    size: Option<crate::infrastructure::cover_cache::CoverSize>,
     state: State<'_, AppState>,
 ) -> Result<Option<Vec<u8>>, String> {
-    handlers::book_handler::get_cover_img(book_id, &state)
+    handlers::book_handler::get_cover_img(book_id, size, &state)
         .await
         .map_err(|e| e.to_string())
 }
@@ -160,3 +351,54 @@ pub async fn remove_book(book_id: i32, state: State<'_, AppState>) -> Result<(),
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Marks or unmarks a book as a favorite.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `is_favorite` - The new favorite flag.
+#[tauri::command]
+pub async fn set_book_favorite(
+    book_id: i32,
+    is_favorite: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::book_handler::set_book_favorite(book_id, is_favorite, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Sets or clears a book's star rating.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `rating` - Stars from `0` to `5` in half-star steps, or `null` to clear.
+#[tauri::command]
+pub async fn set_book_rating(
+    book_id: i32,
+    rating: Option<f32>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::book_handler::set_book_rating(book_id, rating, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Sets or clears a book's free-text review.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `review` - The review text; `null` or blank text clears it.
+#[tauri::command]
+pub async fn set_book_review(
+    book_id: i32,
+    review: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::book_handler::set_book_review(book_id, review, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,236 @@
+use crate::api::handlers;
+use crate::application::state::AppState;
+use tauri::State;
+
+/// Creates a new, empty collection.
+///
+/// # Arguments
+///
+/// * `name` - Display name; must not be blank.
+/// * `description` - Optional description.
+/// * `accent_color` - Optional accent color or style class.
+/// * `cover_image` - Optional cover image URL or path.
+///
+/// # Returns
+///
+/// The created [`CollectionDto`](crate::domain::dto::collection_dto::CollectionDto).
+#[tauri::command]
+pub async fn create_collection(
+    name: String,
+    description: Option<String>,
+    accent_color: Option<String>,
+    cover_image: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::domain::dto::collection_dto::CollectionDto, String> {
+    handlers::collection_handler::create_collection(
+        name,
+        description,
+        accent_color,
+        cover_image,
+        &state,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Renames a collection.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `name` - The new name; must not be blank.
+#[tauri::command]
+pub async fn rename_collection(
+    collection_id: i32,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::collection_handler::rename_collection(collection_id, name, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Deletes a collection. Its books stay in the library.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+#[tauri::command]
+pub async fn delete_collection(
+    collection_id: i32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::collection_handler::delete_collection(collection_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Returns all collections.
+///
+/// # Returns
+///
+/// A [`CollectionDto`](crate::domain::dto::collection_dto::CollectionDto) per
+/// collection, ordered by name, with its book IDs in collection order.
+#[tauri::command]
+pub async fn list_collections(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::domain::dto::collection_dto::CollectionDto>, String> {
+    handlers::collection_handler::list_collections(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Appends a book to the end of a collection.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `book_id` - The book's database ID.
+#[tauri::command]
+pub async fn add_book_to_collection(
+    collection_id: i32,
+    book_id: i32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::collection_handler::add_book_to_collection(collection_id, book_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Removes a book from a collection.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `book_id` - The book's database ID.
+#[tauri::command]
+pub async fn remove_book_from_collection(
+    collection_id: i32,
+    book_id: i32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::collection_handler::remove_book_from_collection(collection_id, book_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Reorders the books of a collection.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `book_ids` - Book IDs in their new order. Books not listed keep their
+///   relative order after the listed ones.
+///
+/// # Returns
+///
+/// The collection with its book IDs in the new order.
+#[tauri::command]
+pub async fn reorder_collection_books(
+    collection_id: i32,
+    book_ids: Vec<i32>,
+    state: State<'_, AppState>,
+) -> Result<crate::domain::dto::collection_dto::CollectionDto, String> {
+    handlers::collection_handler::reorder_collection_books(collection_id, book_ids, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Returns the books of a collection, in collection order.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+///
+/// # Returns
+///
+/// A [`BookDto`](crate::domain::dto::book_dto::BookDto) per book.
+#[tauri::command]
+pub async fn get_collection_books(
+    collection_id: i32,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::domain::dto::book_dto::BookDto>, String> {
+    handlers::collection_handler::get_collection_books(collection_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Creates a smart collection whose books are selected by rules.
+///
+/// Rules are JSON of the form
+/// `{"match": "all" | "any", "rules": [{"field", "operator", "value"} | group]}`,
+/// e.g. `{"match": "all", "rules": [{"field": "progress", "operator": "between", "value": [10, 90]}]}`.
+///
+/// # Arguments
+///
+/// * `name` - Display name; must not be blank.
+/// * `rules` - The root rule group.
+/// * `description` - Optional description.
+/// * `accent_color` - Optional accent color or style class.
+/// * `cover_image` - Optional cover image URL or path.
+///
+/// # Returns
+///
+/// The created [`CollectionDto`](crate::domain::dto::collection_dto::CollectionDto)
+/// with the IDs of the books currently matching.
+#[tauri::command]
+pub async fn create_smart_collection(
+    name: String,
+    rules: crate::domain::models::smart_collection::RuleGroup,
+    description: Option<String>,
+    accent_color: Option<String>,
+    cover_image: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::domain::dto::collection_dto::CollectionDto, String> {
+    handlers::collection_handler::create_smart_collection(
+        name,
+        rules,
+        description,
+        accent_color,
+        cover_image,
+        &state,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Replaces the rules of a smart collection.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `rules` - The new root rule group.
+///
+/// # Returns
+///
+/// The updated [`CollectionDto`](crate::domain::dto::collection_dto::CollectionDto).
+#[tauri::command]
+pub async fn update_smart_collection_rules(
+    collection_id: i32,
+    rules: crate::domain::models::smart_collection::RuleGroup,
+    state: State<'_, AppState>,
+) -> Result<crate::domain::dto::collection_dto::CollectionDto, String> {
+    handlers::collection_handler::update_smart_collection_rules(collection_id, rules, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Evaluates smart-collection rules without saving them.
+///
+/// # Arguments
+///
+/// * `rules` - The root rule group.
+///
+/// # Returns
+///
+/// The matching books as [`BookDto`](crate::domain::dto::book_dto::BookDto)s,
+/// ordered by title.
+#[tauri::command]
+pub async fn preview_smart_collection(
+    rules: crate::domain::models::smart_collection::RuleGroup,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::domain::dto::book_dto::BookDto>, String> {
+    handlers::collection_handler::preview_smart_collection(rules, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
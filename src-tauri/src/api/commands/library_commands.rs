@@ -1,8 +1,30 @@
 use crate::api::handlers;
+use crate::application::bundle_import::ImportResult;
+use crate::application::library_health::{FileRepair, LibraryHealthReport};
 use crate::application::state::AppState;
-use tauri::State;
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::models::library_root::LibraryRoot;
+use crate::domain::models::library_settings::LibrarySettings;
+use tauri::{AppHandle, State};
 
-/// Recursively scans a directory for ebooks and imports them into the library.
+/// Starts importing the ebooks in a directory, recursively, as a background
+/// job.
+///
+/// Files are checksummed and parsed a few at a time (`SCAN_CONCURRENCY`,
+/// default 4). `.zip` and `.cbz` bundles are unpacked afterwards, as by
+/// `import_paths`; each of their ebooks is reported under the bundle's path
+/// followed by the entry's. Progress is emitted as `library-scan` events, each carrying
+/// the `job_id` and a `kind`:
+///
+/// * `discovered` - `path` of an ebook file or bundle found in the
+///   directory.
+/// * `imported` - `path`, `book_id` and `title` of a new book.
+/// * `duplicate` - `path` of a file already in the library.
+/// * `failed` - `path` and `reason` of a file that could not be imported.
+/// * `progress` - `counts` (`discovered`, `imported`, `duplicates`,
+///   `failed`) after each processed file.
+/// * `finished` - the final `summary`: `counts`, `failures` and whether the
+///   scan was `cancelled`. Always the last event of a job.
 ///
 /// # Arguments
 ///
@@ -10,13 +32,238 @@ use tauri::State;
 ///
 /// # Returns
 ///
-/// A list of error messages for files that failed to import.
+/// The scan's job ID, for matching events and for `cancel_library_scan`.
+///
+/// # Errors
+///
+/// Returns an error string if the path is not a directory.
 #[tauri::command]
 pub async fn scan_books_directory(
     directory_path: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    handlers::library_handler::scan_directory(directory_path, app, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Imports files, folders and ZIP bundles, e.g. dropped on the window.
+///
+/// Folders are searched recursively. `.zip` and `.cbz` bundles are
+/// unpacked and their EPUB, PDF and MOBI entries imported; a bundle that is
+/// really an EPUB is imported as one. Without a managed library, entries are
+/// extracted into a folder named after the bundle in the app's `imports`
+/// data folder, never next to the bundle. New books are queued for
+/// full-text indexing.
+///
+/// # Arguments
+///
+/// * `paths` - Absolute paths to ebook files, bundles and folders.
+///
+/// # Returns
+///
+/// One result per file or bundle entry, with a `status`:
+///
+/// * `imported` - `path` and the new `book`.
+/// * `duplicate` - `path` of a file already in the library.
+/// * `failed` - `path` and `reason`. A bundle that cannot be read or holds
+///   no ebooks yields a single failure for the bundle.
+///
+/// Entry paths are the bundle's path followed by the entry's path inside
+/// it.
+#[tauri::command]
+pub async fn import_paths(
+    paths: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ImportResult>, String> {
+    Ok(handlers::library_handler::import_paths(paths, &state).await)
+}
+
+/// Cancels a running library scan.
+///
+/// Books imported before the cancellation stay in the library; the job ends
+/// with a `finished` event whose summary is marked `cancelled`.
+///
+/// # Arguments
+///
+/// * `job_id` - The ID returned by `scan_books_directory`.
+///
+/// # Returns
+///
+/// `false` if no scan with that ID is running.
+#[tauri::command]
+pub fn cancel_library_scan(job_id: u64, state: State<'_, AppState>) -> bool {
+    handlers::library_handler::cancel_scan(job_id, &state)
+}
+
+/// Lists the library folders watched for new, renamed and deleted ebooks.
+///
+/// # Returns
+///
+/// The folders (`id`, `path`, `added_at`), ordered by path.
+#[tauri::command]
+pub async fn list_library_roots(state: State<'_, AppState>) -> Result<Vec<LibraryRoot>, String> {
+    handlers::library_handler::list_library_roots(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Adds a library folder and starts watching it.
+///
+/// While the app runs, EPUB, PDF and MOBI files appearing anywhere below the
+/// folder are imported, as are the ebooks in new `.zip` and `.cbz` bundles. A file whose checksum matches a book whose file is
+/// gone is treated as a rename or move and the book's `file_path` is
+/// updated; books whose file is deleted get the `file_status` `"missing"`.
+/// Changes are emitted as `library-watch` events with a `kind`:
+///
+/// * `imported` - `path`, `book_id` and `title` of a new book.
+/// * `relinked` - `path` and `book_id` of a book whose file moved or
+///   reappeared.
+/// * `missing` - `path` and `book_id` of a book whose file is gone.
+/// * `failed` - `path` and `reason` of a file that could not be imported.
+///
+/// Files already in the folder are not imported; call
+/// `scan_books_directory` on it for that.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the folder. Stored canonicalized.
+///
+/// # Returns
+///
+/// The registered folder.
+///
+/// # Errors
+///
+/// Returns an error string if the path is not a directory or is, contains
+/// or lies inside an already registered folder.
+#[tauri::command]
+pub async fn add_library_root(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<LibraryRoot, String> {
+    handlers::library_handler::add_library_root(path, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Stops watching a library folder. Books imported from it are kept.
+///
+/// # Arguments
+///
+/// * `root_id` - The folder's ID from `list_library_roots`.
+///
+/// # Errors
+///
+/// Returns an error string if no folder has that ID.
+#[tauri::command]
+pub async fn remove_library_root(root_id: i32, state: State<'_, AppState>) -> Result<(), String> {
+    handlers::library_handler::remove_library_root(root_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Returns the managed library settings.
+///
+/// # Returns
+///
+/// An object with `library_dir` (`null` while managed mode is off),
+/// `naming_template` and `import_mode` (`"copy"` or `"move"`). Until
+/// settings are saved, the `LIBRARY_DIR`, `LIBRARY_NAMING_TEMPLATE` and
+/// `LIBRARY_IMPORT_MODE` environment variables apply.
+#[tauri::command]
+pub fn get_library_settings() -> LibrarySettings {
+    handlers::library_handler::get_library_settings()
+}
+
+/// Saves the managed library settings; they apply to the next import or
+/// metadata edit.
+///
+/// In managed mode imported files are copied (or moved) into `library_dir`
+/// at the path the naming template gives, e.g. `{author}/{series}/{title}.{ext}`
+/// (placeholders: `author`, `title`, `series`, `series_index`, `year`,
+/// `ext`), and managed files are renamed when their metadata changes. Books
+/// already in the library are not moved.
+///
+/// # Arguments
+///
+/// * `settings` - `library_dir` (`null` or blank turns managed mode off; the
+///   folder is created if needed), `naming_template` (blank for the
+///   default) and `import_mode` (`"copy"` or `"move"`).
+///
+/// # Returns
+///
+/// The settings as saved, with `library_dir` canonicalized.
+///
+/// # Errors
+///
+/// Returns an error string for an unknown import mode or a folder that
+/// cannot be created.
+#[tauri::command]
+pub async fn update_library_settings(
+    settings: LibrarySettings,
+    state: State<'_, AppState>,
+) -> Result<LibrarySettings, String> {
+    handlers::library_handler::update_library_settings(settings, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Checks the file of every book in the library.
+///
+/// Each book is classified and the result is stored in its `file_status`,
+/// which `BookDto` carries so the UI can grey out unavailable books:
+///
+/// * `ok` - the file exists and matches the stored checksum.
+/// * `modified` - the file exists but its checksum changed.
+/// * `moved` - the file is gone, but a file with its checksum exists
+///   elsewhere under a library folder (`found_path`).
+/// * `missing` - the file is gone and was not found.
+///
+/// Every file is checksummed, so this can take a while on large libraries.
+///
+/// # Returns
+///
+/// Counts per status (`ok`, `missing`, `moved`, `modified`) and the
+/// `issues`: every book that is not OK, with `book_id`, `title`,
+/// `file_path`, `status` and `found_path`.
+#[tauri::command]
+pub async fn verify_library(state: State<'_, AppState>) -> Result<LibraryHealthReport, String> {
+    handlers::library_handler::verify_library(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Fixes a book reported by `verify_library`.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `repair` - An object with an `action`:
+///   * `{"action": "relink", "path": "..."}` - points the book at another
+///     file, such as the `found_path` of a moved book. The book is `ok` if
+///     the file matches its checksum, otherwise `modified`.
+///   * `{"action": "rehash"}` - accepts the current file and stores its
+///     checksum.
+///   * `{"action": "remove"}` - deletes the book from the library, leaving
+///     the file alone.
+///
+/// # Returns
+///
+/// The updated book, or `null` after `remove`.
+///
+/// # Errors
+///
+/// Returns an error string if the book does not exist, the file cannot be
+/// read, or another book already uses the relink path.
+#[tauri::command]
+pub async fn repair_book_file(
+    book_id: i32,
+    repair: FileRepair,
     state: State<'_, AppState>,
-) -> Result<Vec<String>, String> {
-    handlers::library_handler::scan_directory(directory_path, &state)
+) -> Result<Option<BookDto>, String> {
+    handlers::library_handler::repair_book_file(book_id, repair, &state)
         .await
         .map_err(|e| e.to_string())
 }
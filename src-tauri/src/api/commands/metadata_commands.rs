@@ -1,5 +1,8 @@
 use crate::api::handlers;
+use crate::application::provider::{MetadataCandidate, MetadataField};
+use crate::application::service::book_service::MetadataPatch;
 use crate::application::state::AppState;
+use crate::domain::dto::book_dto::BookDto;
 use crate::infrastructure::file_handlers::BookMetadata;
 use tauri::State;
 
@@ -35,23 +38,129 @@ pub async fn list_metadata(state: State<'_, AppState>) -> Result<Vec<BookMetadat
         .map_err(|e| e.to_string())
 }
 
-/// Updates metadata fields for a book found by title search.
+/// Applies a metadata edit from the book details form to a book.
+///
+/// The patch is written in one transaction. Authors, the publisher, the
+/// series and tags are looked up by name and created when missing; authors
+/// and publishers no longer credited on any book are deleted.
 ///
 /// # Arguments
 ///
-/// * `book_name` - Substring to search book titles against.
-/// * `title` - Optional new title.
-/// * `published_date` - Optional new publication date.
-/// * `isbn` - Optional new ISBN.
+/// * `book_id` - The book's database ID.
+/// * `patch` - Object with the fields to change: `title`, `authors` (names in
+///   credit order), `publisher`, `published_date`, `isbn` (ISBN-10 or
+///   ISBN-13, stored as ISBN-13), `description`, `series`, `series_index`
+///   and `tags`. Omitted fields are kept. `null`, blank text or an empty
+///   `tags` list clears a field; `null` is ignored for `title` and
+///   `authors`, which cannot be cleared.
+///
+/// # Returns
+///
+/// The updated book as a [`BookDto`].
+///
+/// # Errors
+///
+/// Returns an error string if the book does not exist, the title is blank,
+/// the series index is negative or the ISBN has an invalid check digit.
 #[tauri::command]
 pub async fn update_metadata(
-    book_name: String,
-    title: Option<String>,
-    published_date: Option<String>,
-    isbn: Option<String>,
+    book_id: i32,
+    patch: MetadataPatch,
+    state: State<'_, AppState>,
+) -> Result<BookDto, String> {
+    handlers::metadata_handler::update_metadata(book_id, patch, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Looks a book up in the online metadata provider (Open Library).
+///
+/// Books with an ISBN are looked up by ISBN; otherwise, or when that finds
+/// nothing, the provider is searched by title and first author.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+///
+/// # Returns
+///
+/// Candidate records ordered by descending `confidence` (`0.0`–`1.0`).
+///
+/// # Errors
+///
+/// Returns an error string if the book does not exist or the provider
+/// cannot be reached.
+#[tauri::command]
+pub async fn find_metadata_candidates(
+    book_id: i32,
+    state: State<'_, AppState>,
+) -> Result<Vec<MetadataCandidate>, String> {
+    handlers::metadata_handler::find_metadata_candidates(book_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Copies the chosen fields of a metadata candidate onto a book.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `candidate` - The `metadata` of a candidate from
+///   `find_metadata_candidates`.
+/// * `fields` - Fields to copy: `"title"`, `"authors"`, `"publisher"`,
+///   `"published_date"`, `"isbn"`, `"series"`, `"tags"`, `"description"`,
+///   `"language"` or `"page_count"`. Fields without a value in the candidate
+///   are left unchanged; tags are added to the existing ones.
+///
+/// # Returns
+///
+/// The updated book as a [`BookDto`].
+///
+/// # Errors
+///
+/// Returns an error string if the book does not exist or the candidate's
+/// ISBN is invalid.
+#[tauri::command]
+pub async fn apply_metadata_candidate(
+    book_id: i32,
+    candidate: BookMetadata,
+    fields: Vec<MetadataField>,
+    state: State<'_, AppState>,
+) -> Result<BookDto, String> {
+    handlers::metadata_handler::apply_metadata_candidate(book_id, candidate, fields, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Writes a book's library metadata into its EPUB file.
+///
+/// Opt-in: the file is only changed when this command is called. Title,
+/// contributors with roles, publisher, date, ISBN, tags, series, description
+/// and language are written to the EPUB's package document so other readers
+/// and e-ink devices show the edited values. The file is replaced atomically
+/// and the book's checksum is updated.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `cover_path` - Optional path to a JPEG, PNG, GIF or WebP image to embed
+///   as the new cover.
+///
+/// # Returns
+///
+/// The updated book as a [`BookDto`].
+///
+/// # Errors
+///
+/// Returns an error string if the book does not exist, is not an EPUB, the
+/// cover is not a supported image, or the file cannot be rewritten.
+#[tauri::command]
+pub async fn embed_metadata(
+    book_id: i32,
+    cover_path: Option<String>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    handlers::metadata_handler::update_metadata(book_name, title, published_date, isbn, &state)
+) -> Result<BookDto, String> {
+    handlers::metadata_handler::embed_metadata(book_id, cover_path, &state)
         .await
         .map_err(|e| e.to_string())
 }
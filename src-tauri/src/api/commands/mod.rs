@@ -7,6 +7,10 @@
 pub mod annotation_commands;
 pub mod book_commands;
 pub mod bookmark_commands;
+pub mod collection_commands;
 pub mod library_commands;
 pub mod metadata_commands;
 pub mod reading_progress_commands;
+pub mod search_commands;
+pub mod series_commands;
+pub mod tag_commands;
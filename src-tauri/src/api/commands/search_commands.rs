@@ -0,0 +1,61 @@
+use crate::api::handlers;
+use crate::application::state::AppState;
+use tauri::State;
+
+/// Searches the text of every indexed book in the library.
+///
+/// # Arguments
+///
+/// * `query` - Words to search for; all must occur in the same chapter or
+///   page.
+/// * `limit` - Optional maximum number of matching chapters/pages to
+///   consider. Defaults to 200.
+///
+/// # Returns
+///
+/// [`LibrarySearchResult`](crate::domain::dto::search_dto::LibrarySearchResult)s
+/// ranked by relevance, each with the book and its matching chapters or pages
+/// with `<mark>`-highlighted snippets.
+#[tauri::command]
+pub async fn search_library(
+    query: String,
+    limit: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::domain::dto::search_dto::LibrarySearchResult>, String> {
+    handlers::search_handler::search_library(query, limit, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Clears the full-text index and rebuilds it in the background.
+///
+/// # Returns
+///
+/// The number of books queued for indexing. Progress can be followed with
+/// [`get_index_status`].
+#[tauri::command]
+pub async fn rebuild_library_index(state: State<'_, AppState>) -> Result<usize, String> {
+    handlers::search_handler::rebuild_library_index(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Returns the full-text indexing status of books.
+///
+/// # Arguments
+///
+/// * `book_id` - Optional book ID; when omitted every status is returned.
+///
+/// # Returns
+///
+/// [`BookIndexStatus`](crate::domain::models::search_index::BookIndexStatus)
+/// records with status `"pending"`, `"indexing"`, `"indexed"` or `"failed"`.
+#[tauri::command]
+pub async fn get_index_status(
+    book_id: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::domain::models::search_index::BookIndexStatus>, String> {
+    handlers::search_handler::get_index_status(book_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
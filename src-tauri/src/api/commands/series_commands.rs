@@ -0,0 +1,28 @@
+use crate::api::handlers;
+use crate::application::state::AppState;
+use tauri::State;
+
+/// Returns the books of a series in reading order, with the next book to
+/// read.
+///
+/// The next book is the one after the last finished book of the series
+/// (progress at 100%), or the first book if none is finished yet.
+///
+/// # Arguments
+///
+/// * `series_id` - The series' database ID, as found in `BookDto::series_id`.
+///
+/// # Returns
+///
+/// A [`SeriesBooksDto`](crate::domain::dto::series_dto::SeriesBooksDto) with
+/// the ordered books and `next_unread`, which is `null` once the series is
+/// finished.
+#[tauri::command]
+pub async fn get_series_books(
+    series_id: i32,
+    state: State<'_, AppState>,
+) -> Result<crate::domain::dto::series_dto::SeriesBooksDto, String> {
+    handlers::series_handler::get_series_books(series_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
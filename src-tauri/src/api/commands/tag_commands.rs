@@ -0,0 +1,129 @@
+use crate::api::handlers;
+use crate::application::state::AppState;
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::dto::tag_dto::TagDto;
+use tauri::State;
+
+/// Lists every tag with the number of books carrying it.
+///
+/// # Returns
+///
+/// A list of [`TagDto`] ordered by name, case-insensitively. Tags no longer
+/// used by any book are included with a `book_count` of `0`.
+#[tauri::command]
+pub async fn list_tags(state: State<'_, AppState>) -> Result<Vec<TagDto>, String> {
+    handlers::tag_handler::list_tags(&state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Lists the books carrying a tag, ordered by title.
+///
+/// # Arguments
+///
+/// * `tag_id` - The tag's database ID.
+///
+/// # Returns
+///
+/// A list of [`BookDto`], or an error if the tag does not exist.
+#[tauri::command]
+pub async fn get_tag_books(
+    tag_id: i32,
+    state: State<'_, AppState>,
+) -> Result<Vec<BookDto>, String> {
+    handlers::tag_handler::get_tag_books(tag_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Adds a tag to a book, creating the tag if needed.
+///
+/// Names are matched case-insensitively, so adding "fantasy" to a library
+/// that already has "Fantasy" reuses the existing tag. Adding a tag the book
+/// already has is a no-op.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `name` - The tag name (1–64 characters after trimming).
+///
+/// # Returns
+///
+/// The [`TagDto`] with its updated book count.
+#[tauri::command]
+pub async fn add_tag_to_book(
+    book_id: i32,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<TagDto, String> {
+    handlers::tag_handler::add_tag_to_book(book_id, name, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Removes a tag from a book.
+///
+/// The tag itself is kept, even when no book uses it any more.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `tag_id` - The tag's database ID.
+#[tauri::command]
+pub async fn remove_tag_from_book(
+    book_id: i32,
+    tag_id: i32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    handlers::tag_handler::remove_tag_from_book(book_id, tag_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Renames a tag.
+///
+/// Fails if another tag already has the new name; use `merge_tags` to
+/// combine the two instead.
+///
+/// # Arguments
+///
+/// * `tag_id` - The tag's database ID.
+/// * `name` - The new name (1–64 characters after trimming).
+///
+/// # Returns
+///
+/// The renamed [`TagDto`].
+#[tauri::command]
+pub async fn rename_tag(
+    tag_id: i32,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<TagDto, String> {
+    handlers::tag_handler::rename_tag(tag_id, name, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Merges one tag into another.
+///
+/// Every book of the source tag gets the target tag, then the source tag is
+/// deleted.
+///
+/// # Arguments
+///
+/// * `source_tag_id` - The tag to merge away.
+/// * `target_tag_id` - The tag to keep.
+///
+/// # Returns
+///
+/// The target [`TagDto`] with its updated book count.
+#[tauri::command]
+pub async fn merge_tags(
+    source_tag_id: i32,
+    target_tag_id: i32,
+    state: State<'_, AppState>,
+) -> Result<TagDto, String> {
+    handlers::tag_handler::merge_tags(source_tag_id, target_tag_id, &state)
+        .await
+        .map_err(|e| e.to_string())
+}
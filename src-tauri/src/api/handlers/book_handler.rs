@@ -1,29 +1,72 @@
 use std::path::Path;
 
+use crate::application::bundle_import::ImportResult;
 use crate::application::state::AppState;
-use crate::domain::dto::book_dto::BookDto;
+use crate::domain::dto::book_dto::{BookDto, BookPageDto};
 use crate::domain::error::DomainError;
+use crate::domain::repository::{BookFilter, BookListQuery, BookSort};
+use crate::infrastructure::cover_cache::CoverSize;
 
-/// Imports an ebook file at the given path into the library.
-pub async fn import_book(path: String, state: &AppState) -> Result<BookDto, DomainError> {
-    crate::application::book::import_book(
+/// Imports an ebook file or ZIP bundle at the given path into the library.
+///
+/// New books are queued for full-text indexing in the background.
+pub async fn import_book(path: String, state: &AppState) -> Result<Vec<ImportResult>, DomainError> {
+    let results = crate::application::book::import_book(
         Path::new(&path),
         &state.book_repo,
         &state.author_repo,
         &state.book_author_repo,
         &state.publisher_repo,
+        &state.series_repo,
+        &state.tag_repo,
     )
-    .await
+    .await?;
+
+    crate::api::handlers::library_handler::queue_imported_books(&results, state).await;
+    Ok(results)
 }
 
-/// Returns all books in the library as DTOs.
-pub async fn list_books(state: &AppState) -> Result<Vec<BookDto>, DomainError> {
-    crate::application::book::list_books(
-        &state.book_repo,
-        &state.author_repo,
-        &state.publisher_repo,
-    )
-    .await
+/// Returns the books matching the filter as DTOs.
+pub async fn list_books(
+    favorites_only: Option<bool>,
+    min_rating: Option<f32>,
+    state: &AppState,
+) -> Result<Vec<BookDto>, DomainError> {
+    let filter = BookFilter {
+        favorites_only: favorites_only.unwrap_or(false),
+        min_rating,
+        ..Default::default()
+    };
+    crate::application::book::list_books(filter, &state.book_repo).await
+}
+
+/// Returns one sorted, filtered page of books with the total count.
+#[allow(clippy::too_many_arguments)]
+pub async fn list_books_page(
+    offset: Option<i64>,
+    limit: Option<i64>,
+    sort_by: Option<BookSort>,
+    descending: Option<bool>,
+    file_type: Option<String>,
+    favorites_only: Option<bool>,
+    min_rating: Option<f32>,
+    state: &AppState,
+) -> Result<BookPageDto, DomainError> {
+    let query = BookListQuery {
+        filter: BookFilter {
+            favorites_only: favorites_only.unwrap_or(false),
+            min_rating,
+            file_type,
+            book_ids: None,
+            series_id: None,
+            tag_id: None,
+        },
+        sort: sort_by.unwrap_or_default(),
+        descending: descending.unwrap_or(false),
+        offset: offset.unwrap_or(0),
+        limit,
+    };
+    crate::application::book::list_books_page(query, &state.book_repo).await
 }
 
 /// Returns book details by ID as a DTO.
@@ -31,26 +74,78 @@ pub async fn get_book_details(
     book_id: i32,
     state: &AppState,
 ) -> Result<Option<BookDto>, DomainError> {
-    crate::application::book::get_book(
-        book_id,
-        &state.book_repo,
-        &state.author_repo,
-        &state.publisher_repo,
-    )
-    .await
+    crate::application::book::get_book(book_id, &state.book_repo).await
 }
 
 /// Reads the full HTML content of an EPUB file.
-pub async fn read_epub(path: String) -> Result<String, DomainError> {
-    crate::application::book::read_epub(&path).await
+pub async fn read_epub(path: String, book_id: Option<i32>) -> Result<String, DomainError> {
+    crate::application::book::read_epub(&path, book_id).await
 }
 
-/// Reads content from an ebook file by type (EPUB or PDF).
+/// Reads content from an ebook file by type (EPUB, PDF or MOBI).
 pub async fn read_book(
     path: String,
     file_type: String,
+    chapter: Option<usize>,
+    prefetch: Option<usize>,
+    book_id: Option<i32>,
 ) -> Result<crate::application::book::BookContent, DomainError> {
-    crate::application::book::read_book(&path, &file_type).await
+    crate::application::book::read_book(&path, &file_type, chapter, prefetch, book_id).await
+}
+
+/// Lists the spine items of an EPUB file.
+pub async fn get_epub_spine(
+    path: String,
+) -> Result<Vec<crate::infrastructure::file_handlers::epub_handler::EpubSpineItem>, DomainError> {
+    crate::application::book::get_epub_spine(&path).await
+}
+
+/// Renders an EPUB chapter plus `prefetch` neighbours on each side.
+pub async fn read_epub_chapter(
+    path: String,
+    index: usize,
+    prefetch: Option<usize>,
+    book_id: Option<i32>,
+) -> Result<Vec<crate::infrastructure::file_handlers::epub_handler::EpubChapter>, DomainError> {
+    crate::application::book::read_epub_chapter(&path, index, prefetch.unwrap_or(0), book_id)
+        .await
+}
+
+/// Reads an EPUB manifest resource (bytes and MIME type) for the `book://` protocol.
+pub async fn get_book_resource(
+    book_id: i32,
+    href: String,
+    state: &AppState,
+) -> Result<(Vec<u8>, String), DomainError> {
+    crate::application::book::read_book_resource(book_id, &href, &state.book_repo).await
+}
+
+/// Returns the table of contents of a book as a nested tree.
+pub async fn get_table_of_contents(
+    book_id: i32,
+    state: &AppState,
+) -> Result<Vec<crate::infrastructure::file_handlers::TocEntry>, DomainError> {
+    crate::application::book::get_table_of_contents(book_id, &state.book_repo).await
+}
+
+/// Searches a book's text for `query` with the given matching options.
+pub async fn search_in_book(
+    book_id: i32,
+    query: String,
+    case_insensitive: Option<bool>,
+    whole_word: Option<bool>,
+    regex: Option<bool>,
+    max_hits: Option<usize>,
+    state: &AppState,
+) -> Result<crate::infrastructure::file_handlers::SearchResults, DomainError> {
+    let options = crate::utils::search::SearchOptions {
+        case_insensitive: case_insensitive.unwrap_or(false),
+        whole_word: whole_word.unwrap_or(false),
+        regex: regex.unwrap_or(false),
+    };
+    let max_hits = max_hits.unwrap_or(crate::utils::search::MAX_SEARCH_HITS);
+    crate::application::book::search_in_book(book_id, &query, options, max_hits, &state.book_repo)
+        .await
 }
 
 /// Returns the page count of a PDF file.
@@ -70,12 +165,43 @@ pub async fn read_pdf_page(
         .map_err(|e| DomainError::Parse(e.to_string()))
 }
 
-/// Returns the cover image bytes for a book.
-pub async fn get_cover_img(book_id: i32, state: &AppState) -> Result<Option<Vec<u8>>, DomainError> {
-    crate::application::book::get_cover(book_id, &state.book_repo).await
+/// Returns the cached cover thumbnail of a book, defaulting to the detail size.
+pub async fn get_cover_img(
+    book_id: i32,
+    size: Option<CoverSize>,
+    state: &AppState,
+) -> Result<Option<Vec<u8>>, DomainError> {
+    crate::application::book::get_cover(book_id, size.unwrap_or_default(), &state.book_repo).await
 }
 
 /// Removes a book from the library by ID.
 pub async fn remove_book(book_id: i32, state: &AppState) -> Result<(), DomainError> {
     crate::application::book::remove_book(book_id, &state.book_repo).await
 }
+
+/// Marks or unmarks a book as a favorite.
+pub async fn set_book_favorite(
+    book_id: i32,
+    is_favorite: bool,
+    state: &AppState,
+) -> Result<(), DomainError> {
+    crate::application::book::set_book_favorite(book_id, is_favorite, &state.book_repo).await
+}
+
+/// Sets or clears a book's star rating.
+pub async fn set_book_rating(
+    book_id: i32,
+    rating: Option<f32>,
+    state: &AppState,
+) -> Result<(), DomainError> {
+    crate::application::book::set_book_rating(book_id, rating, &state.book_repo).await
+}
+
+/// Sets or clears a book's review.
+pub async fn set_book_review(
+    book_id: i32,
+    review: Option<String>,
+    state: &AppState,
+) -> Result<(), DomainError> {
+    crate::application::book::set_book_review(book_id, review, &state.book_repo).await
+}
@@ -0,0 +1,152 @@
+use crate::application::state::AppState;
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::dto::collection_dto::CollectionDto;
+use crate::domain::error::DomainError;
+use crate::domain::models::smart_collection::RuleGroup;
+use crate::domain::repository::*;
+
+/// Creates a new collection.
+pub async fn create_collection(
+    name: String,
+    description: Option<String>,
+    accent_color: Option<String>,
+    cover_image: Option<String>,
+    state: &AppState,
+) -> Result<CollectionDto, DomainError> {
+    crate::application::collection::create_collection(
+        NewCollection {
+            name,
+            description,
+            accent_color,
+            cover_image,
+            rules: None,
+        },
+        &state.collection_repo,
+    )
+    .await
+}
+
+/// Renames a collection.
+pub async fn rename_collection(
+    collection_id: i32,
+    name: String,
+    state: &AppState,
+) -> Result<(), DomainError> {
+    crate::application::collection::rename_collection(collection_id, &name, &state.collection_repo)
+        .await
+}
+
+/// Deletes a collection by ID.
+pub async fn delete_collection(collection_id: i32, state: &AppState) -> Result<(), DomainError> {
+    crate::application::collection::delete_collection(collection_id, &state.collection_repo).await
+}
+
+/// Returns all collections with their book IDs.
+pub async fn list_collections(state: &AppState) -> Result<Vec<CollectionDto>, DomainError> {
+    crate::application::collection::list_collections(&state.collection_repo).await
+}
+
+/// Appends a book to a collection.
+pub async fn add_book_to_collection(
+    collection_id: i32,
+    book_id: i32,
+    state: &AppState,
+) -> Result<(), DomainError> {
+    crate::application::collection::add_book_to_collection(
+        collection_id,
+        book_id,
+        &state.collection_repo,
+        &state.book_repo,
+    )
+    .await
+}
+
+/// Removes a book from a collection.
+pub async fn remove_book_from_collection(
+    collection_id: i32,
+    book_id: i32,
+    state: &AppState,
+) -> Result<(), DomainError> {
+    crate::application::collection::remove_book_from_collection(
+        collection_id,
+        book_id,
+        &state.collection_repo,
+    )
+    .await
+}
+
+/// Reorders the books of a collection.
+pub async fn reorder_collection_books(
+    collection_id: i32,
+    book_ids: Vec<i32>,
+    state: &AppState,
+) -> Result<CollectionDto, DomainError> {
+    crate::application::collection::reorder_collection_books(
+        collection_id,
+        book_ids,
+        &state.collection_repo,
+    )
+    .await
+}
+
+/// Returns the books of a collection as DTOs, in collection order.
+pub async fn get_collection_books(
+    collection_id: i32,
+    state: &AppState,
+) -> Result<Vec<BookDto>, DomainError> {
+    crate::application::collection::get_collection_books(
+        collection_id,
+        &state.collection_repo,
+        &state.book_repo,
+    )
+    .await
+}
+
+/// Creates a smart collection from a rule group.
+pub async fn create_smart_collection(
+    name: String,
+    rules: RuleGroup,
+    description: Option<String>,
+    accent_color: Option<String>,
+    cover_image: Option<String>,
+    state: &AppState,
+) -> Result<CollectionDto, DomainError> {
+    crate::application::collection::create_collection(
+        NewCollection {
+            name,
+            description,
+            accent_color,
+            cover_image,
+            rules: Some(rules),
+        },
+        &state.collection_repo,
+    )
+    .await
+}
+
+/// Replaces the rules of a smart collection.
+pub async fn update_smart_collection_rules(
+    collection_id: i32,
+    rules: RuleGroup,
+    state: &AppState,
+) -> Result<CollectionDto, DomainError> {
+    crate::application::collection::update_smart_collection_rules(
+        collection_id,
+        rules,
+        &state.collection_repo,
+    )
+    .await
+}
+
+/// Returns the books matching unsaved smart-collection rules.
+pub async fn preview_smart_collection(
+    rules: RuleGroup,
+    state: &AppState,
+) -> Result<Vec<BookDto>, DomainError> {
+    crate::application::collection::preview_smart_collection(
+        &rules,
+        &state.collection_repo,
+        &state.book_repo,
+    )
+    .await
+}
@@ -1,19 +1,257 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+
+use crate::application::book::LibraryRepos;
+use crate::application::bundle_import::{self, ImportResult};
+use crate::application::library_health::{self, FileRepair, LibraryHealthReport};
+use crate::application::library_scan::{self, ScanEvent, ScanFailure, ScanSummary};
+use crate::application::library_watch::{self, WatchEvent};
 use crate::application::state::AppState;
+use crate::application::{library_root, managed_library, search_index};
+use crate::domain::dto::book_dto::BookDto;
 use crate::domain::error::DomainError;
+use crate::domain::models::library_root::LibraryRoot;
+use crate::domain::models::library_settings::LibrarySettings;
+
+/// Name of the Tauri event carrying library scan progress.
+pub const SCAN_EVENT: &str = "library-scan";
+
+/// Name of the Tauri event carrying changes made by the library watcher.
+pub const WATCH_EVENT: &str = "library-watch";
+
+/// A [`ScanEvent`] tagged with the ID of the scan that sent it.
+#[derive(Serialize, Clone)]
+struct ScanEventPayload {
+    job_id: u64,
+    #[serde(flatten)]
+    event: ScanEvent,
+}
 
-/// Recursively scans a directory for ebooks and imports them.
+/// Starts a background scan of a directory and returns its job ID.
+///
+/// Progress is emitted as [`SCAN_EVENT`] events. When the scan ends,
+/// unindexed books are queued for background full-text indexing.
 pub async fn scan_directory(
     directory_path: String,
+    app: AppHandle,
     state: &AppState,
-) -> Result<Vec<String>, DomainError> {
-    crate::application::book::scan_directory(
-        Path::new(&directory_path),
-        &state.book_repo,
-        &state.author_repo,
-        &state.book_author_repo,
-        &state.publisher_repo,
+) -> Result<u64, DomainError> {
+    let dir_path = PathBuf::from(directory_path);
+    if !dir_path.is_dir() {
+        return Err(DomainError::File(format!(
+            "Not a directory: {}",
+            dir_path.display()
+        )));
+    }
+
+    let (job_id, cancelled) = state.scan_jobs.start();
+    let scan_jobs = state.scan_jobs.clone();
+    let book_repo = state.book_repo.clone();
+    let author_repo = state.author_repo.clone();
+    let book_author_repo = state.book_author_repo.clone();
+    let publisher_repo = state.publisher_repo.clone();
+    let series_repo = state.series_repo.clone();
+    let tag_repo = state.tag_repo.clone();
+    let search_index_repo = state.search_index_repo.clone();
+
+    tokio::spawn(async move {
+        let emit = |event: ScanEvent| {
+            if let Err(e) = app.emit(SCAN_EVENT, ScanEventPayload { job_id, event }) {
+                log::warn!("Failed to emit scan event: {}", e);
+            }
+        };
+        let result = library_scan::scan_directory(
+            &dir_path,
+            library_scan::scan_concurrency(),
+            &cancelled,
+            &emit,
+            &LibraryRepos {
+                book_repo: &book_repo,
+                author_repo: &author_repo,
+                book_author_repo: &book_author_repo,
+                publisher_repo: &publisher_repo,
+                series_repo: &series_repo,
+                tag_repo: &tag_repo,
+            },
+        )
+        .await;
+        scan_jobs.finish(job_id);
+
+        if let Err(e) = result {
+            emit(ScanEvent::Finished {
+                summary: ScanSummary {
+                    failures: vec![ScanFailure {
+                        path: dir_path.to_string_lossy().to_string(),
+                        reason: e.to_string(),
+                    }],
+                    ..Default::default()
+                },
+            });
+        }
+
+        if let Err(e) = crate::application::search_index::spawn_index_unindexed_books(
+            book_repo,
+            search_index_repo,
+        )
+        .await
+        {
+            log::warn!("Failed to queue books for indexing: {}", e);
+        }
+    });
+
+    Ok(job_id)
+}
+
+/// Asks a running library scan to stop.
+pub fn cancel_scan(job_id: u64, state: &AppState) -> bool {
+    state.scan_jobs.cancel(job_id)
+}
+
+/// Starts watching the registered library folders.
+///
+/// Changes are emitted as [`WATCH_EVENT`] events; imported books are queued
+/// for background full-text indexing. Folders that cannot be watched are
+/// logged and skipped.
+pub async fn start_library_watch(app: AppHandle, state: &AppState) -> Result<(), DomainError> {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    state
+        .library_watcher
+        .start(sender)
+        .map_err(|e| DomainError::File(e.to_string()))?;
+    for root in state.library_root_repo.find_all().await? {
+        if let Err(e) = state.library_watcher.watch(Path::new(&root.path)) {
+            log::warn!("Failed to watch {}: {}", root.path, e);
+        }
+    }
+
+    let book_repo = state.book_repo.clone();
+    let author_repo = state.author_repo.clone();
+    let book_author_repo = state.book_author_repo.clone();
+    let publisher_repo = state.publisher_repo.clone();
+    let series_repo = state.series_repo.clone();
+    let tag_repo = state.tag_repo.clone();
+    let search_index_repo = state.search_index_repo.clone();
+
+    tokio::spawn(async move {
+        let emit = |event: WatchEvent| {
+            if let WatchEvent::Imported { book_id, .. } = event {
+                let book_repo = book_repo.clone();
+                let search_index_repo = search_index_repo.clone();
+                tokio::spawn(async move {
+                    if let Err(e) =
+                        search_index::spawn_index_book(book_id, book_repo, search_index_repo).await
+                    {
+                        log::warn!("Failed to queue book {} for indexing: {}", book_id, e);
+                    }
+                });
+            }
+            if let Err(e) = app.emit(WATCH_EVENT, event) {
+                log::warn!("Failed to emit watch event: {}", e);
+            }
+        };
+        library_watch::watch_library(
+            receiver,
+            &emit,
+            &LibraryRepos {
+                book_repo: &book_repo,
+                author_repo: &author_repo,
+                book_author_repo: &book_author_repo,
+                publisher_repo: &publisher_repo,
+                series_repo: &series_repo,
+                tag_repo: &tag_repo,
+            },
+        )
+        .await;
+    });
+
+    Ok(())
+}
+
+/// Returns the registered library folders.
+pub async fn list_library_roots(state: &AppState) -> Result<Vec<LibraryRoot>, DomainError> {
+    library_root::list_library_roots(&state.library_root_repo).await
+}
+
+/// Registers a library folder and starts watching it.
+pub async fn add_library_root(path: String, state: &AppState) -> Result<LibraryRoot, DomainError> {
+    let root = library_root::add_library_root(&path, &state.library_root_repo).await?;
+    if let Err(e) = state.library_watcher.watch(Path::new(&root.path)) {
+        log::warn!("Failed to watch {}: {}", root.path, e);
+    }
+    Ok(root)
+}
+
+/// Unregisters a library folder and stops watching it.
+pub async fn remove_library_root(root_id: i32, state: &AppState) -> Result<(), DomainError> {
+    let root = library_root::remove_library_root(root_id, &state.library_root_repo).await?;
+    if let Err(e) = state.library_watcher.unwatch(Path::new(&root.path)) {
+        log::warn!("Failed to stop watching {}: {}", root.path, e);
+    }
+    Ok(())
+}
+
+/// Returns the managed library settings in effect.
+pub fn get_library_settings() -> LibrarySettings {
+    managed_library::library_settings()
+}
+
+/// Saves and applies new managed library settings.
+pub async fn update_library_settings(
+    settings: LibrarySettings,
+    state: &AppState,
+) -> Result<LibrarySettings, DomainError> {
+    managed_library::update_library_settings(settings, &state.library_settings_repo).await
+}
+
+/// Imports dropped files, folders and ZIP bundles, queueing every new book
+/// for full-text indexing.
+pub async fn import_paths(paths: Vec<String>, state: &AppState) -> Vec<ImportResult> {
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    let results = bundle_import::import_paths(
+        &paths,
+        &LibraryRepos {
+            book_repo: &state.book_repo,
+            author_repo: &state.author_repo,
+            book_author_repo: &state.book_author_repo,
+            publisher_repo: &state.publisher_repo,
+            series_repo: &state.series_repo,
+            tag_repo: &state.tag_repo,
+        },
     )
-    .await
+    .await;
+    queue_imported_books(&results, state).await;
+    results
+}
+
+/// Queues the books of the imported results for full-text indexing.
+pub async fn queue_imported_books(results: &[ImportResult], state: &AppState) {
+    for result in results {
+        if let ImportResult::Imported { book, .. } = result
+            && let Err(e) = search_index::spawn_index_book(
+                book.id,
+                state.book_repo.clone(),
+                state.search_index_repo.clone(),
+            )
+            .await
+        {
+            log::warn!("Failed to queue book {} for indexing: {}", book.id, e);
+        }
+    }
+}
+
+/// Checks every book's file and stores the results.
+pub async fn verify_library(state: &AppState) -> Result<LibraryHealthReport, DomainError> {
+    library_health::verify_library(&state.book_repo, &state.library_root_repo).await
+}
+
+/// Relinks, rehashes or removes a book flagged by the library check.
+pub async fn repair_book_file(
+    book_id: i32,
+    repair: FileRepair,
+    state: &AppState,
+) -> Result<Option<BookDto>, DomainError> {
+    library_health::repair_book_file(book_id, repair, &state.book_repo).await
 }
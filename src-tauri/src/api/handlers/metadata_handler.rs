@@ -1,6 +1,11 @@
+use crate::application::provider::{MetadataCandidate, MetadataField};
+use crate::application::book::LibraryRepos;
+use crate::application::service::book_service::MetadataPatch;
 use crate::application::state::AppState;
+use crate::domain::dto::book_dto::BookDto;
 use crate::domain::error::DomainError;
 use crate::infrastructure::file_handlers::BookMetadata;
+use std::path::Path;
 
 /// Re-parses the ebook file for a book and returns fresh metadata.
 pub async fn fetch_metadata(
@@ -15,19 +20,61 @@ pub async fn list_metadata(state: &AppState) -> Result<Vec<BookMetadata>, Domain
     crate::application::service::book_service::list_metadata(&state.book_repo).await
 }
 
-/// Updates metadata fields for a book found by title search.
+/// Applies a metadata edit to a book.
 pub async fn update_metadata(
-    book_name: String,
-    title: Option<String>,
-    published_date: Option<String>,
-    isbn: Option<String>,
+    book_id: i32,
+    patch: MetadataPatch,
+    state: &AppState,
+) -> Result<BookDto, DomainError> {
+    crate::application::service::book_service::update_metadata(book_id, &patch, &state.book_repo)
+        .await
+}
+
+/// Asks the metadata provider for records matching a book.
+pub async fn find_metadata_candidates(
+    book_id: i32,
+    state: &AppState,
+) -> Result<Vec<MetadataCandidate>, DomainError> {
+    crate::application::service::book_service::find_metadata_candidates(
+        book_id,
+        &state.metadata_provider,
+        &state.book_repo,
+    )
+    .await
+}
+
+/// Copies the chosen fields of a metadata candidate onto a book.
+pub async fn apply_metadata_candidate(
+    book_id: i32,
+    candidate: BookMetadata,
+    fields: Vec<MetadataField>,
+    state: &AppState,
+) -> Result<BookDto, DomainError> {
+    crate::application::service::book_service::apply_metadata_candidate(
+        book_id,
+        &candidate,
+        &fields,
+        &LibraryRepos {
+            book_repo: &state.book_repo,
+            author_repo: &state.author_repo,
+            book_author_repo: &state.book_author_repo,
+            publisher_repo: &state.publisher_repo,
+            series_repo: &state.series_repo,
+            tag_repo: &state.tag_repo,
+        },
+    )
+    .await
+}
+
+/// Writes a book's library metadata into its EPUB file.
+pub async fn embed_metadata(
+    book_id: i32,
+    cover_path: Option<String>,
     state: &AppState,
-) -> Result<(), DomainError> {
-    crate::application::service::book_service::update_metadata(
-        &book_name,
-        title.as_deref(),
-        published_date.as_deref(),
-        isbn.as_deref(),
+) -> Result<BookDto, DomainError> {
+    crate::application::service::book_service::embed_metadata(
+        book_id,
+        cover_path.as_deref().map(Path::new),
         &state.book_repo,
     )
     .await
@@ -6,6 +6,10 @@
 pub mod annotation_handler;
 pub mod book_handler;
 pub mod bookmark_handler;
+pub mod collection_handler;
 pub mod library_handler;
 pub mod metadata_handler;
 pub mod reading_progress_handler;
+pub mod search_handler;
+pub mod series_handler;
+pub mod tag_handler;
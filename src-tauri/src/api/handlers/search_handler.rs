@@ -0,0 +1,36 @@
+use crate::application::state::AppState;
+use crate::domain::dto::search_dto::LibrarySearchResult;
+use crate::domain::error::DomainError;
+use crate::domain::models::search_index::BookIndexStatus;
+
+/// Searches the full-text index of the whole library.
+pub async fn search_library(
+    query: String,
+    limit: Option<i32>,
+    state: &AppState,
+) -> Result<Vec<LibrarySearchResult>, DomainError> {
+    crate::application::search_index::search_library(
+        &query,
+        limit,
+        &state.book_repo,
+        &state.search_index_repo,
+    )
+    .await
+}
+
+/// Clears the full-text index and re-indexes every book in the background.
+pub async fn rebuild_library_index(state: &AppState) -> Result<usize, DomainError> {
+    crate::application::search_index::rebuild_index(
+        state.book_repo.clone(),
+        state.search_index_repo.clone(),
+    )
+    .await
+}
+
+/// Returns the full-text indexing status of one book or of all books.
+pub async fn get_index_status(
+    book_id: Option<i32>,
+    state: &AppState,
+) -> Result<Vec<BookIndexStatus>, DomainError> {
+    crate::application::search_index::get_index_status(book_id, &state.search_index_repo).await
+}
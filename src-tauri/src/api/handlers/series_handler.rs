@@ -0,0 +1,12 @@
+use crate::application::state::AppState;
+use crate::domain::dto::series_dto::SeriesBooksDto;
+use crate::domain::error::DomainError;
+
+/// Returns the books of a series in order with the next book to read.
+pub async fn get_series_books(
+    series_id: i32,
+    state: &AppState,
+) -> Result<SeriesBooksDto, DomainError> {
+    crate::application::series::get_series_books(series_id, &state.series_repo, &state.book_repo)
+        .await
+}
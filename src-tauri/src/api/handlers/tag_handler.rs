@@ -0,0 +1,51 @@
+use crate::application::state::AppState;
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::dto::tag_dto::TagDto;
+use crate::domain::error::DomainError;
+
+/// Returns every tag with its book count.
+pub async fn list_tags(state: &AppState) -> Result<Vec<TagDto>, DomainError> {
+    crate::application::tag::list_tags(&state.tag_repo).await
+}
+
+/// Returns the books carrying a tag.
+pub async fn get_tag_books(tag_id: i32, state: &AppState) -> Result<Vec<BookDto>, DomainError> {
+    crate::application::tag::get_tag_books(tag_id, &state.tag_repo, &state.book_repo).await
+}
+
+/// Tags a book by tag name.
+pub async fn add_tag_to_book(
+    book_id: i32,
+    name: String,
+    state: &AppState,
+) -> Result<TagDto, DomainError> {
+    crate::application::tag::add_tag_to_book(book_id, &name, &state.tag_repo, &state.book_repo)
+        .await
+}
+
+/// Removes a tag from a book.
+pub async fn remove_tag_from_book(
+    book_id: i32,
+    tag_id: i32,
+    state: &AppState,
+) -> Result<(), DomainError> {
+    crate::application::tag::remove_tag_from_book(book_id, tag_id, &state.tag_repo).await
+}
+
+/// Renames a tag.
+pub async fn rename_tag(
+    tag_id: i32,
+    name: String,
+    state: &AppState,
+) -> Result<TagDto, DomainError> {
+    crate::application::tag::rename_tag(tag_id, &name, &state.tag_repo).await
+}
+
+/// Merges the source tag into the target tag.
+pub async fn merge_tags(
+    source_tag_id: i32,
+    target_tag_id: i32,
+    state: &AppState,
+) -> Result<TagDto, DomainError> {
+    crate::application::tag::merge_tags(source_tag_id, target_tag_id, &state.tag_repo).await
+}
@@ -0,0 +1,72 @@
+//! Custom URI scheme protocols registered on the Tauri builder.
+
+use tauri::http::{Request, Response, StatusCode, header};
+use tauri::{Manager, Runtime, UriSchemeContext, UriSchemeResponder};
+
+use crate::api::handlers;
+use crate::application::state::AppState;
+use crate::domain::error::DomainError;
+use crate::infrastructure::file_handlers::epub_handler::parse_book_resource_path;
+
+/// Serves EPUB manifest resources (images, fonts, stylesheets, SVG) for the
+/// `book` scheme.
+///
+/// Requests are addressed as `<book_id>/<href>` in the URL path, with the
+/// href percent-encoded (see
+/// [`book_resource_url`](crate::infrastructure::file_handlers::epub_handler::book_resource_url)
+/// and [`parse_book_resource_path`])
+/// and answered with the resource bytes streamed from the archive, using the
+/// MIME type declared in the manifest. The lookup runs on the async runtime so
+/// the webview's protocol thread is never blocked.
+///
+/// # Arguments
+///
+/// * `ctx` - Scheme context providing the app handle (and its [`AppState`]).
+/// * `request` - The webview's request.
+/// * `responder` - Used to answer the request once the resource is read.
+///
+/// Responds with `400` for malformed paths, `404` when the book or resource
+/// does not exist, and `500` for any other failure.
+pub fn book_protocol<R: Runtime>(
+    ctx: UriSchemeContext<'_, R>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    let app = ctx.app_handle().clone();
+    let path = request.uri().path().to_string();
+
+    tauri::async_runtime::spawn(async move {
+        let Some((book_id, href)) = parse_book_resource_path(&path) else {
+            responder.respond(error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Expected <book_id>/<href>, got {}", path),
+            ));
+            return;
+        };
+
+        let state = app.state::<AppState>();
+        let response = match handlers::book_handler::get_book_resource(book_id, href, &state).await
+        {
+            Ok((bytes, media_type)) => Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, media_type)
+                .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+                .body(bytes)
+                .unwrap(),
+            Err(e @ (DomainError::BookNotFound(_) | DomainError::NotFound)) => {
+                error_response(StatusCode::NOT_FOUND, e.to_string())
+            }
+            Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        };
+        responder.respond(response);
+    });
+}
+
+/// Builds a plain-text error response.
+fn error_response(status: StatusCode, message: String) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(message.into_bytes())
+        .unwrap()
+}
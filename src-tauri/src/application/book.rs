@@ -1,14 +1,45 @@
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use serde::Serialize;
 
-use crate::domain::dto::book_dto::BookDto;
+use crate::application::bundle_import::{ImportResult, import_archive, is_archive};
+use crate::application::managed_library::{ManagedLibrary, NamingFields};
+use crate::domain::dto::book_dto::{BookDto, BookPageDto};
 use crate::domain::error::DomainError;
+use crate::domain::models::book::HydratedBook;
+use crate::domain::models::contributor::Contributor;
+use crate::domain::models::tag::Tag;
 use crate::domain::repository::*;
+use crate::infrastructure::cover_cache::{self, CoverSize};
+use crate::infrastructure::file_handlers::{BookMetadata, SearchResults, TocEntry};
+use crate::infrastructure::file_handlers::archive_handler;
 use crate::infrastructure::file_handlers::epub_handler;
+use crate::infrastructure::file_handlers::epub_handler::{EpubChapter, EpubSpineItem};
+use crate::infrastructure::file_handlers::mobi_handler;
 use crate::infrastructure::file_handlers::pdf_handler;
 use crate::infrastructure::file_handlers::pdf_handler::PdfPage;
+use crate::utils::search::{SearchOptions, build_search_pattern};
+
+/// Highest star rating a book can have.
+pub const MAX_RATING: f32 = 5.0;
+
+/// Page size used by [`list_books_page`] when no limit is given.
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+
+/// Largest page size [`list_books_page`] returns.
+pub const MAX_PAGE_SIZE: i64 = 500;
+
+/// Repositories needed to import a book or edit its linked records.
+pub struct LibraryRepos<'a> {
+    pub book_repo: &'a Arc<dyn BookRepository>,
+    pub author_repo: &'a Arc<dyn AuthorRepository>,
+    pub book_author_repo: &'a Arc<dyn BookAuthorRepository>,
+    pub publisher_repo: &'a Arc<dyn PublisherRepository>,
+    pub series_repo: &'a Arc<dyn SeriesRepository>,
+    pub tag_repo: &'a Arc<dyn TagRepository>,
+}
 
 /// Serialized content returned by [`read_book`], tagged by file format.
 #[derive(Serialize, Clone)]
@@ -16,24 +47,26 @@ use crate::infrastructure::file_handlers::pdf_handler::PdfPage;
 pub enum BookContent {
     /// Raw HTML string extracted from an EPUB's spine items.
     Epub(String),
+    /// A window of EPUB chapters addressed by spine index: the requested
+    /// chapter plus any prefetched neighbours, in spine order.
+    EpubChapters(Vec<EpubChapter>),
     /// Rendered PDF page as a base64-encoded image with text spans.
     Pdf(PdfPage),
+    /// HTML string decoded from a MOBI/KF8 file's text records.
+    Mobi(String),
 }
 
-/// Retrieves a single book by ID, resolved to a [`BookDto`] with author and
-/// publisher names.
+/// Retrieves a single book by ID, resolved to a [`BookDto`] with its
+/// authors, publisher and reading progress.
 ///
 /// # Arguments
 ///
 /// * `find_id` - The book's database ID.
 /// * `book_repo` - Repository for looking up books.
-/// * `author_repo` - Repository for resolving author names.
-/// * `publisher_repo` - Repository for resolving publisher names.
 ///
 /// # Returns
 ///
-/// `Ok(None)` if no book with the given ID exists. Otherwise the book as a
-/// `BookDto` with its first author and publisher names included.
+/// `Ok(None)` if no book with the given ID exists.
 ///
 /// # Errors
 ///
@@ -42,40 +75,70 @@ pub enum BookContent {
 pub async fn get_book(
     find_id: i32,
     book_repo: &Arc<dyn BookRepository>,
-    author_repo: &Arc<dyn AuthorRepository>,
-    publisher_repo: &Arc<dyn PublisherRepository>,
 ) -> Result<Option<BookDto>, DomainError> {
-    let book = match book_repo.find_by_id(find_id).await? {
-        Some(b) => b,
-        None => return Ok(None),
-    };
+    Ok(get_books_by_ids(&[find_id], book_repo).await?.into_iter().next())
+}
 
-    let authors = author_repo.get_authors_by_book(book.id).await?;
-    let author = authors.first().map(|a| a.name.clone());
+/// Retrieves several books by ID with two queries in total.
+///
+/// # Arguments
+///
+/// * `book_ids` - The books' database IDs.
+/// * `book_repo` - Repository for looking up books.
+///
+/// # Returns
+///
+/// One [`BookDto`] per existing ID, in the order of `book_ids`. Unknown IDs
+/// are skipped.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] on query failure.
+pub async fn get_books_by_ids(
+    book_ids: &[i32],
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<Vec<BookDto>, DomainError> {
+    if book_ids.is_empty() {
+        return Ok(Vec::new());
+    }
 
-    let publisher = match book.publisher_id {
-        Some(pid) => publisher_repo.find_by_id(pid).await?.map(|p| p.name),
-        None => None,
-    };
+    let mut by_id: HashMap<i32, BookDto> = book_repo
+        .find_hydrated(&BookListQuery {
+            filter: BookFilter {
+                book_ids: Some(book_ids.to_vec()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await?
+        .into_iter()
+        .map(|hydrated| (hydrated.book.id, BookDto::from(hydrated)))
+        .collect();
 
-    Ok(Some(BookDto::new(&book, author, publisher)))
+    Ok(book_ids.iter().filter_map(|id| by_id.remove(id)).collect())
 }
 
-/// Returns the cover image bytes for the given book.
+/// Returns the cover image of a book at the requested size.
 ///
-/// For PDFs the first page is rendered as a PNG at 150 DPI. For EPUBs the
-/// embedded cover image is extracted. Returns `None` if no cover is available
-/// or extraction fails.
+/// Serves the thumbnail cached at import (see
+/// [`cover_cache`](crate::infrastructure::cover_cache)). When the cache is
+/// missing or older than the ebook file, the cover is extracted again — the
+/// first PDF page at 150 DPI, the embedded EPUB cover or the MOBI EXTH cover
+/// record — and the thumbnails are rewritten in place, under the book's
+/// stored checksum; only `cover_image_path` is updated, never the checksum.
+/// Covers that cannot be decoded, and covers of books without a checksum,
+/// are served as extracted, unscaled.
 ///
 /// # Arguments
 ///
 /// * `book_id` - The book's database ID.
-/// * `book_repo` - Repository for looking up the book record.
+/// * `size` - Which thumbnail to return.
+/// * `book_repo` - Repository for looking up and updating the book record.
 ///
 /// # Returns
 ///
 /// `Ok(None)` when the file type has no cover support or extraction fails.
-/// `Ok(Some(bytes))` with the raw cover image bytes on success.
+/// `Ok(Some(bytes))` with the cover image bytes on success.
 ///
 /// # Errors
 ///
@@ -84,88 +147,254 @@ pub async fn get_book(
 /// # Examples
 ///
 /// ```rust,ignore
-/// let bytes = get_cover(42, &book_repo).await?;
+/// let bytes = get_cover(42, CoverSize::Grid, &book_repo).await?;
 /// ```
 pub async fn get_cover(
     book_id: i32,
+    size: CoverSize,
     book_repo: &Arc<dyn BookRepository>,
 ) -> Result<Option<Vec<u8>>, DomainError> {
     let book = book_repo
         .find_by_id(book_id)
         .await?
         .ok_or(DomainError::BookNotFound(book_id))?;
+    let Some(file_path) = book.file_path.clone() else {
+        return Ok(None);
+    };
 
-    match book.file_type.as_deref() {
-        Some("pdf") => {
-            let path = book.file_path.as_deref()
-                .ok_or_else(|| DomainError::File("No file path for PDF book".into()))?;
-            match pdf_handler::get_pdf_cover(path).await {
-                Ok(img) => Ok(Some(img)),
-                Err(_) => Ok(None),
-            }
-        }
-        Some("epub") => match epub_handler::get_cover_image_by_book_id(book_id).await {
-            Ok(img) => Ok(Some(img)),
-            Err(_) => Ok(None),
-        },
-        _ => Ok(None),
+    if let Some(cover_path) = book.cover_image_path.as_deref().map(Path::new)
+        && !cover_cache::is_stale(cover_path, Path::new(&file_path))
+        && let Ok(bytes) = tokio::fs::read(cover_cache::sized_cover_path(cover_path, size)).await
+    {
+        return Ok(Some(bytes));
+    }
+
+    let Some(raw) = extract_cover(&file_path, book.file_type.as_deref()).await? else {
+        return Ok(None);
+    };
+
+    // The checksum identifies the book for duplicate detection and is only
+    // updated by imports and health checks, so the thumbnails stay where
+    // they are even when the file has changed since.
+    let Some(checksum) = book.checksum.as_deref() else {
+        return Ok(Some(raw));
+    };
+    let Some(cover_path) = cache_cover(checksum, raw.clone()).await else {
+        return Ok(Some(raw));
+    };
+
+    let cover_image_path = cover_path.to_string_lossy().to_string();
+    if book.cover_image_path.as_deref() != Some(cover_image_path.as_str()) {
+        book_repo
+            .update(
+                book_id,
+                UpdateBook {
+                    cover_image_path: Some(cover_image_path),
+                    ..Default::default()
+                },
+            )
+            .await?;
+    }
+
+    match tokio::fs::read(cover_cache::sized_cover_path(&cover_path, size)).await {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(_) => Ok(Some(raw)),
     }
 }
 
-/// Imports a single ebook file into the library.
+/// Extracts the full-size cover of an ebook file.
+///
+/// Returns `Ok(None)` for unsupported file types and when extraction fails.
+async fn extract_cover(
+    file_path: &str,
+    file_type: Option<&str>,
+) -> Result<Option<Vec<u8>>, DomainError> {
+    let cover = match file_type {
+        Some("pdf") => pdf_handler::get_pdf_cover(file_path).await,
+        Some("epub") => epub_handler::get_epub_cover(file_path).await,
+        Some("mobi") => mobi_handler::get_mobi_cover(file_path).await,
+        _ => return Ok(None),
+    };
+    Ok(cover.ok().filter(|bytes| !bytes.is_empty()))
+}
+
+/// Writes the thumbnails of a cover to the cache, off the async runtime.
+///
+/// Returns the path to store in `books.cover_image_path`, or `None` when the
+/// image cannot be decoded or written; covers are never worth failing an
+/// import for.
+async fn cache_cover(checksum: &str, bytes: Vec<u8>) -> Option<PathBuf> {
+    let checksum = checksum.to_string();
+    tokio::task::spawn_blocking(move || {
+        cover_cache::write_cover_thumbnails(&cover_cache::cover_cache_dir(), &checksum, &bytes)
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.into()))
+    .inspect_err(|e| log::warn!("Failed to cache cover: {}", e))
+    .ok()
+}
+
+/// Imports an ebook file, or the ebooks in a ZIP bundle, into the library.
 ///
 /// Parses metadata from the file, checks for duplicates via checksum, creates
-/// author and publisher records as needed, and inserts the book with all
-/// foreign-key links in a single transaction.
+/// author, publisher, series and tag records as needed, and inserts the book
+/// with all foreign-key links in a single transaction. Contributors keep the
+/// role and order in which the file credits them; tags come from the file's
+/// subject keywords. The cover is downscaled into the thumbnail cache and its
+/// path stored as `cover_image_path`; a cover that cannot be decoded leaves
+/// the path empty without failing the import.
+///
+/// A `.zip` or `.cbz` bundle is unpacked by
+/// [`import_archive`](crate::application::bundle_import::import_archive),
+/// which imports each of its EPUB, PDF and MOBI entries.
 ///
 /// # Arguments
 ///
-/// * `file_path` - Absolute path to the ebook file (`.epub` or `.pdf`).
+/// * `file_path` - Absolute path to the ebook file (`.epub`, `.pdf`, `.mobi`,
+///   `.azw3` or `.azw`) or bundle (`.zip`, `.cbz`).
 /// * `book_repo` - Repository for inserting the book record.
 /// * `author_repo` - Repository for finding or creating authors.
-/// * `_book_author_repo` - Repository for linking authors to the book.
+/// * `book_author_repo` - Repository for linking authors to the book.
 /// * `publisher_repo` - Repository for finding or creating the publisher.
+/// * `series_repo` - Repository for finding or creating the series.
+/// * `tag_repo` - Repository for finding or creating tags.
 ///
 /// # Returns
 ///
-/// A fully-resolved [`BookDto`] with author and publisher names populated.
+/// For an ebook, a single [`ImportResult::Imported`] holding the
+/// fully-resolved [`BookDto`] with contributors, publisher, series and tags
+/// populated. For a bundle, one [`ImportResult`] per entry, where entries
+/// that fail or are duplicates are reported instead of returned as errors.
 ///
 /// # Errors
 ///
 /// Returns [`DomainError::DuplicateBook`] when a book with the same SHA-256
 /// checksum already exists. Returns [`DomainError::Parse`] when the file
-/// cannot be read or parsed. Returns [`DomainError::File`] for unsupported
-/// file extensions.
+/// cannot be read or parsed, and [`DomainError::File`] for unsupported file
+/// extensions.
 pub async fn import_book(
     file_path: &Path,
     book_repo: &Arc<dyn BookRepository>,
     author_repo: &Arc<dyn AuthorRepository>,
-    _book_author_repo: &Arc<dyn BookAuthorRepository>,
+    book_author_repo: &Arc<dyn BookAuthorRepository>,
     publisher_repo: &Arc<dyn PublisherRepository>,
-) -> Result<BookDto, DomainError> {
+    series_repo: &Arc<dyn SeriesRepository>,
+    tag_repo: &Arc<dyn TagRepository>,
+) -> Result<Vec<ImportResult>, DomainError> {
+    let repos = LibraryRepos {
+        book_repo,
+        author_repo,
+        book_author_repo,
+        publisher_repo,
+        series_repo,
+        tag_repo,
+    };
+    if is_archive(file_path) {
+        return Ok(import_archive(file_path, &repos).await);
+    }
+
+    let (metadata, file_type) = parse_book_file(file_path).await?;
+    let book = store_parsed_book(file_path, metadata, file_type, &repos).await?;
+    Ok(vec![ImportResult::Imported {
+        path: file_path.to_string_lossy().to_string(),
+        book: Box::new(book),
+    }])
+}
+
+/// Parses an ebook file's metadata, including its SHA-256 checksum.
+///
+/// Does not touch the database, so several files can be parsed at once.
+///
+/// # Arguments
+///
+/// * `file_path` - Path to the ebook file (`.epub`, `.pdf`, `.mobi`, `.azw3`
+///   or `.azw`).
+///
+/// # Returns
+///
+/// The parsed [`BookMetadata`] and the file type stored for the book
+/// (`"epub"`, `"pdf"` or `"mobi"`).
+///
+/// # Errors
+///
+/// Returns [`DomainError::Parse`] when the file cannot be read or parsed,
+/// [`DomainError::InvalidInput`] for `.zip` and `.cbz` bundles, which
+/// [`import_archive`](crate::application::bundle_import::import_archive)
+/// unpacks, and [`DomainError::File`] for other unsupported file extensions.
+pub async fn parse_book_file(file_path: &Path) -> Result<(BookMetadata, String), DomainError> {
     let ext = file_path
         .extension()
         .and_then(|e| e.to_str())
         .unwrap_or("")
         .to_lowercase();
 
-    let (metadata, file_type) = match ext.as_str() {
+    match ext.as_str() {
         "pdf" => {
             let meta = pdf_handler::parse_pdf_meta(file_path.to_string_lossy().to_string())
                 .await
                 .map_err(|e| DomainError::Parse(e.to_string()))?;
-            (meta, "pdf".to_string())
+            Ok((meta, "pdf".to_string()))
         }
         "epub" => {
             let meta = epub_handler::parse_epub_meta(file_path.to_string_lossy().to_string())
                 .await
                 .map_err(|e| DomainError::Parse(e.to_string()))?;
-            (meta, "epub".to_string())
+            Ok((meta, "epub".to_string()))
         }
-        other => {
-            return Err(DomainError::File(format!("Unsupported file type: {}", other)));
+        ext if mobi_handler::is_mobi_extension(ext) => {
+            let meta = mobi_handler::parse_mobi_meta(file_path.to_string_lossy().to_string())
+                .await
+                .map_err(|e| DomainError::Parse(e.to_string()))?;
+            Ok((meta, "mobi".to_string()))
         }
-    };
+        ext if archive_handler::is_archive_extension(ext) => Err(DomainError::InvalidInput(
+            format!("{} is a bundle, not an ebook", file_path.display()),
+        )),
+        other => Err(DomainError::File(format!(
+            "Unsupported file type: {}",
+            other
+        ))),
+    }
+}
+
+/// Adds a parsed ebook to the library.
+///
+/// The second half of [`import_book`]: checks for a duplicate checksum,
+/// creates the contributor, publisher, series and tag records, caches the
+/// cover and inserts the book with all its links. In managed library mode
+/// the file is first copied or moved into the library directory (see
+/// [`ManagedLibrary`]) and the book stores its new path.
+///
+/// # Arguments
+///
+/// * `file_path` - Path of the parsed file, used in duplicate errors.
+/// * `metadata` - Metadata returned by [`parse_book_file`].
+/// * `file_type` - File type returned by [`parse_book_file`].
+/// * `repos` - Repositories for the book and its linked records.
+///
+/// # Returns
+///
+/// The imported book as a [`BookDto`].
+///
+/// # Errors
+///
+/// Returns [`DomainError::DuplicateBook`] when a book with the same checksum
+/// already exists, [`DomainError::File`] when the file cannot be placed in
+/// the managed library and [`DomainError::Database`] on query failures.
+pub async fn store_parsed_book(
+    file_path: &Path,
+    metadata: BookMetadata,
+    file_type: String,
+    repos: &LibraryRepos<'_>,
+) -> Result<BookDto, DomainError> {
+    let LibraryRepos {
+        book_repo,
+        author_repo,
+        publisher_repo,
+        series_repo,
+        tag_repo,
+        ..
+    } = *repos;
 
     if let Some(_existing) = book_repo.find_by_checksum(&metadata.checksum).await? {
         return Err(DomainError::DuplicateBook(
@@ -173,11 +402,33 @@ pub async fn import_book(
         ));
     }
 
-    let mut author_ids = Vec::new();
-    for author_name in &metadata.authors {
-        let author = author_repo.find_or_create(author_name).await?;
-        author_ids.push((author.id, author.name.clone()));
+    let mut contributors: Vec<Contributor> = Vec::new();
+    for parsed in &metadata.contributors {
+        let author = author_repo
+            .find_or_create(&parsed.name, parsed.sort_name.as_deref())
+            .await?;
+        // Two spellings can resolve to the same author record.
+        if contributors
+            .iter()
+            .any(|c| c.author_id == author.id && c.role == parsed.role)
+        {
+            continue;
+        }
+        contributors.push(Contributor {
+            author_id: author.id,
+            name: author.name,
+            sort_name: author.sort_name,
+            role: parsed.role.clone(),
+            position: contributors.len() as i32,
+        });
     }
+    let links: Vec<NewContributorLink> = contributors
+        .iter()
+        .map(|c| NewContributorLink {
+            author_id: c.author_id,
+            role: c.role.clone(),
+        })
+        .collect();
 
     let publisher_id = if let Some(pub_name) = metadata.publishers.first() {
         let publisher = publisher_repo.find_or_create(pub_name).await?;
@@ -186,7 +437,40 @@ pub async fn import_book(
         None
     };
 
-    let book = book_repo
+    let series = match &metadata.series {
+        Some(name) => Some(series_repo.find_or_create(name).await?),
+        None => None,
+    };
+
+    let mut tags: Vec<Tag> = Vec::new();
+    for name in &metadata.tags {
+        tags.push(tag_repo.find_or_create(name).await?);
+    }
+    let tag_ids: Vec<i32> = tags.iter().map(|t| t.id).collect();
+
+    let cover = match metadata.cover_data.clone() {
+        Some((bytes, _media_type)) => Some(bytes),
+        None => extract_cover(&metadata.file_path, Some(&file_type)).await?,
+    };
+    let cover_image_path = match cover {
+        Some(bytes) => cache_cover(&metadata.checksum, bytes)
+            .await
+            .map(|path| path.to_string_lossy().to_string()),
+        None => None,
+    };
+
+    let source = PathBuf::from(&metadata.file_path);
+    let managed = ManagedLibrary::current();
+    let stored_path = match &managed {
+        Some(library) => {
+            library
+                .adopt(&source, &NamingFields::from_metadata(&metadata))
+                .await?
+        }
+        None => source.clone(),
+    };
+
+    let imported = book_repo
         .import_with_links(
             NewBook {
                 title: metadata.title.clone(),
@@ -194,126 +478,559 @@ pub async fn import_book(
                 publisher_id,
                 isbn: metadata.isbn.clone(),
                 file_type,
-                file_path: metadata.file_path.clone(),
-                cover_image_path: None,
+                file_path: stored_path.to_string_lossy().to_string(),
+                cover_image_path,
                 checksum: Some(metadata.checksum.clone()),
+                series_id: series.as_ref().map(|s| s.id),
+                series_index: series.as_ref().and(metadata.series_index),
+                description: metadata.description.clone(),
+                language: metadata.language.clone(),
+                word_count: metadata.word_count,
+                page_count: metadata.page_count,
             },
-            &author_ids,
+            &links,
+            &tag_ids,
             publisher_id,
         )
-        .await?;
+        .await;
+    let book = match imported {
+        Ok(book) => book,
+        Err(e) => {
+            if let Some(library) = &managed {
+                library.release(&source, &stored_path).await;
+            }
+            return Err(e);
+        }
+    };
 
-    Ok(BookDto::new(
-        &book,
-        metadata.authors.first().cloned(),
-        metadata.publishers.first().cloned(),
-    ))
+    Ok(BookDto::from(HydratedBook {
+        book,
+        contributors,
+        publisher: metadata.publishers.first().cloned(),
+        series: series.map(|s| s.name),
+        tags: tags.into_iter().map(|t| t.name).collect(),
+        progress_percentage: None,
+        last_read_at: None,
+    }))
 }
 
-/// Returns all books in the library, each resolved to a [`BookDto`].
+/// Returns the books in the library matching a filter, each resolved to a
+/// [`BookDto`].
+///
+/// Books, publishers and reading progress are loaded in one joined query and
+/// authors in a second, regardless of library size.
 ///
 /// # Arguments
 ///
-/// * `book_repo` - Repository for listing all book records.
-/// * `author_repo` - Repository for resolving author names per book.
-/// * `publisher_repo` - Repository for resolving publisher names per book.
+/// * `filter` - Favorite, rating and file-type criteria; the default lists
+///   every book.
+/// * `book_repo` - Repository for listing book records.
 ///
 /// # Returns
 ///
-/// A vector of [`BookDto`] for every book in the database.
+/// A vector of [`BookDto`] for every matching book, ordered by title.
 ///
 /// # Errors
 ///
-/// Delegates to repository methods; returns [`DomainError::Database`] on
-/// query failures.
+/// Returns [`DomainError::InvalidInput`] if `min_rating` is out of range.
+/// Otherwise delegates to repository methods; returns
+/// [`DomainError::Database`] on query failures.
 pub async fn list_books(
+    filter: BookFilter,
     book_repo: &Arc<dyn BookRepository>,
-    author_repo: &Arc<dyn AuthorRepository>,
-    publisher_repo: &Arc<dyn PublisherRepository>,
 ) -> Result<Vec<BookDto>, DomainError> {
-    let books = book_repo.find_all().await?;
-    let mut dtos = Vec::new();
+    validate_filter(&filter)?;
+
+    let books = book_repo
+        .find_hydrated(&BookListQuery {
+            filter,
+            ..Default::default()
+        })
+        .await?;
+
+    Ok(books.into_iter().map(BookDto::from).collect())
+}
+
+/// Returns one page of the book listing together with the total count.
+///
+/// # Arguments
+///
+/// * `query` - Filter, sort order, offset and page size. A missing limit
+///   defaults to [`DEFAULT_PAGE_SIZE`]; larger limits are capped at
+///   [`MAX_PAGE_SIZE`].
+/// * `book_repo` - Repository for listing and counting books.
+///
+/// # Returns
+///
+/// A [`BookPageDto`] with the page's books, the number of books matching the
+/// filter and the offset and limit that were applied.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] for a negative offset, a
+/// non-positive limit or an out-of-range `min_rating`.
+pub async fn list_books_page(
+    mut query: BookListQuery,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<BookPageDto, DomainError> {
+    validate_filter(&query.filter)?;
+    if query.offset < 0 {
+        return Err(DomainError::InvalidInput("Offset cannot be negative".into()));
+    }
+    let limit = match query.limit {
+        Some(limit) if limit <= 0 => {
+            return Err(DomainError::InvalidInput("Limit must be positive".into()));
+        }
+        Some(limit) => limit.min(MAX_PAGE_SIZE),
+        None => DEFAULT_PAGE_SIZE,
+    };
+    query.limit = Some(limit);
+
+    let total = book_repo.count_filtered(&query.filter).await?;
+    let items = book_repo
+        .find_hydrated(&query)
+        .await?
+        .into_iter()
+        .map(BookDto::from)
+        .collect();
+
+    Ok(BookPageDto {
+        items,
+        total,
+        offset: query.offset,
+        limit,
+    })
+}
+
+/// Rejects listing filters with an out-of-range minimum rating.
+fn validate_filter(filter: &BookFilter) -> Result<(), DomainError> {
+    match filter.min_rating {
+        Some(min_rating) if !(0.0..=MAX_RATING).contains(&min_rating) => {
+            Err(DomainError::InvalidInput(format!(
+                "Minimum rating must be between 0 and {}",
+                MAX_RATING
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Marks or unmarks a book as a favorite.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `is_favorite` - The new favorite flag.
+/// * `book_repo` - Repository for updating the book.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if no book has the given ID.
+pub async fn set_book_favorite(
+    book_id: i32,
+    is_favorite: bool,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<(), DomainError> {
+    update_existing_book(
+        book_id,
+        UpdateBook {
+            is_favorite: Some(is_favorite),
+            ..Default::default()
+        },
+        book_repo,
+    )
+    .await
+}
+
+/// Sets or clears a book's star rating.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `rating` - Stars from `0` to `5` in steps of `0.5`, or `None` to clear.
+/// * `book_repo` - Repository for updating the book.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if the rating is out of range or
+/// not a multiple of `0.5`, and [`DomainError::BookNotFound`] if no book has
+/// the given ID.
+pub async fn set_book_rating(
+    book_id: i32,
+    rating: Option<f32>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<(), DomainError> {
+    if let Some(rating) = rating {
+        validate_rating(rating)?;
+    }
+
+    update_existing_book(
+        book_id,
+        UpdateBook {
+            rating: Some(rating),
+            ..Default::default()
+        },
+        book_repo,
+    )
+    .await
+}
 
-    for book in books {
-        let authors = author_repo.get_authors_by_book(book.id).await?;
-        let author = authors.first().map(|a| a.name.clone());
+/// Sets or clears a book's review.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `review` - The review text; `None` or blank text clears it.
+/// * `book_repo` - Repository for updating the book.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if no book has the given ID.
+pub async fn set_book_review(
+    book_id: i32,
+    review: Option<String>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<(), DomainError> {
+    let review = review.filter(|r| !r.trim().is_empty());
 
-        let publisher = match book.publisher_id {
-            Some(pid) => publisher_repo.find_by_id(pid).await?.map(|p| p.name),
-            None => None,
-        };
+    update_existing_book(
+        book_id,
+        UpdateBook {
+            review: Some(review),
+            ..Default::default()
+        },
+        book_repo,
+    )
+    .await
+}
 
-        dtos.push(BookDto::new(&book, author, publisher));
+/// Checks that a rating lies in `0..=5` and is a whole or half star.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] otherwise.
+pub fn validate_rating(rating: f32) -> Result<(), DomainError> {
+    if (0.0..=MAX_RATING).contains(&rating) && (rating * 2.0).fract() == 0.0 {
+        Ok(())
+    } else {
+        Err(DomainError::InvalidInput(format!(
+            "Rating must be between 0 and {} in steps of 0.5, got {}",
+            MAX_RATING, rating
+        )))
     }
+}
 
-    Ok(dtos)
+/// Applies an update after checking that the book exists.
+async fn update_existing_book(
+    book_id: i32,
+    update: UpdateBook,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<(), DomainError> {
+    book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+    book_repo.update(book_id, update).await
 }
 
 /// Reads and returns the full HTML content of an EPUB file.
 ///
-/// Spine items are concatenated, with image `src` attributes replaced by
-/// inline base64 data URIs so the HTML is self-contained.
+/// Spine items are concatenated. Image `src` attributes point at the `book://`
+/// resource protocol when `book_id` is given, and are replaced by inline
+/// base64 data URIs otherwise so the HTML is self-contained.
 ///
 /// # Arguments
 ///
 /// * `path` - Absolute path to the EPUB file on disk.
+/// * `book_id` - Optional database ID of the book, used to link images
+///   through the `book://` protocol.
 ///
 /// # Returns
 ///
-/// A single HTML string containing the body content of all spine items with
-/// inline base64 images.
+/// A single HTML string containing the body content of all spine items.
 ///
 /// # Errors
 ///
 /// Returns [`DomainError::Parse`] when the file cannot be opened, is not a
 /// valid EPUB, or contains malformed spine items.
-pub async fn read_epub(path: &str) -> Result<String, DomainError> {
-    epub_handler::get_epub_content(path)
+pub async fn read_epub(path: &str, book_id: Option<i32>) -> Result<String, DomainError> {
+    epub_handler::get_epub_content(path, book_id)
+        .await
+        .map_err(|e| DomainError::Parse(e.to_string()))
+}
+
+/// Lists the spine (reading order) of an EPUB file.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file on disk.
+///
+/// # Returns
+///
+/// One [`EpubSpineItem`] per spine entry with its idref, href and length.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Parse`] when the file cannot be opened or is not a
+/// valid EPUB.
+pub async fn get_epub_spine(path: &str) -> Result<Vec<EpubSpineItem>, DomainError> {
+    epub_handler::get_epub_spine(path)
         .await
         .map_err(|e| DomainError::Parse(e.to_string()))
 }
 
+/// Renders a single EPUB chapter, optionally with neighbouring chapters.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file on disk.
+/// * `index` - 0-based spine index of the chapter.
+/// * `prefetch` - Number of neighbouring chapters to render on each side.
+/// * `book_id` - Optional database ID of the book, used to link images
+///   through the `book://` protocol.
+///
+/// # Returns
+///
+/// The rendered chapters in spine order.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Parse`] when the file cannot be read or `index` is
+/// out of range.
+pub async fn read_epub_chapter(
+    path: &str,
+    index: usize,
+    prefetch: usize,
+    book_id: Option<i32>,
+) -> Result<Vec<EpubChapter>, DomainError> {
+    epub_handler::get_epub_chapters(path, index, prefetch, book_id)
+        .await
+        .map_err(|e| DomainError::Parse(e.to_string()))
+}
+
+/// Returns the table of contents of a library book as a nested tree.
+///
+/// EPUB entries come from the nav document (or the NCX for EPUB 2) and are
+/// mapped to spine indices; PDF entries come from the document outline and
+/// carry page indices. Each entry's `position` uses the same format the
+/// reader stores in `reading_progress.current_position`. MOBI files have no
+/// table of contents support yet and yield an empty list.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `book_repo` - Repository for looking up the book record.
+///
+/// # Returns
+///
+/// The top-level [`TocEntry`] nodes, each with nested children. Empty when
+/// the book has no table of contents.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if no book has the given ID,
+/// [`DomainError::File`] if the book has no file path or an unsupported file
+/// type, and [`DomainError::Parse`] when the file cannot be read.
+pub async fn get_table_of_contents(
+    book_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<Vec<TocEntry>, DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+    let path = book.file_path.as_deref()
+        .ok_or_else(|| DomainError::File(format!("No file path for book {}", book_id)))?;
+
+    match book.file_type.as_deref() {
+        Some("epub") => epub_handler::get_epub_toc(path)
+            .await
+            .map_err(|e| DomainError::Parse(e.to_string())),
+        Some("pdf") => pdf_handler::get_pdf_outline(path)
+            .await
+            .map_err(|e| DomainError::Parse(e.to_string())),
+        Some("mobi") => Ok(Vec::new()),
+        other => Err(DomainError::File(format!(
+            "Unsupported file type: {}",
+            other.unwrap_or("unknown")
+        ))),
+    }
+}
+
+/// Searches the full text of a library book.
+///
+/// EPUB hits are located by spine index, PDF hits by page (with the bounding
+/// box of the match), and MOBI hits by offset into the whole text. Offsets
+/// are character offsets into the plain text of the chapter or page.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `query` - Text (or regular expression) to search for.
+/// * `options` - Case-insensitive, whole-word and regex matching options.
+/// * `max_hits` - Maximum number of hits to return.
+/// * `book_repo` - Repository for looking up the book record.
+///
+/// # Returns
+///
+/// The first `max_hits` hits in reading order, flagged as truncated when the
+/// book has more. Empty when `query` is blank.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if no book has the given ID,
+/// [`DomainError::Parse`] if `query` is an invalid regular expression or the
+/// file cannot be read, and [`DomainError::File`] if the book has no file
+/// path or an unsupported file type.
+pub async fn search_in_book(
+    book_id: i32,
+    query: &str,
+    options: SearchOptions,
+    max_hits: usize,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<SearchResults, DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+    let path = book.file_path.as_deref()
+        .ok_or_else(|| DomainError::File(format!("No file path for book {}", book_id)))?;
+
+    if query.trim().is_empty() {
+        return Ok(SearchResults::default());
+    }
+    let pattern = build_search_pattern(query, &options)
+        .map_err(|e| DomainError::Parse(format!("Invalid search pattern: {}", e)))?;
+
+    let hits = match book.file_type.as_deref() {
+        Some("epub") => epub_handler::search_epub(path, pattern, max_hits).await,
+        Some("pdf") => pdf_handler::search_pdf(path, pattern, max_hits).await,
+        Some("mobi") => mobi_handler::search_mobi(path, pattern, max_hits).await,
+        other => {
+            return Err(DomainError::File(format!(
+                "Unsupported file type: {}",
+                other.unwrap_or("unknown")
+            )));
+        }
+    };
+
+    hits.map_err(|e| DomainError::Parse(e.to_string()))
+}
+
+/// Reads a manifest resource of a library EPUB for the `book://` protocol.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `href` - Manifest href of the resource inside the EPUB.
+/// * `book_repo` - Repository for looking up the book's file path.
+///
+/// # Returns
+///
+/// The resource bytes and the MIME type declared for it in the manifest.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if no book has the given ID,
+/// [`DomainError::NotFound`] if the EPUB has no manifest item with that href,
+/// [`DomainError::File`] if the book is not an EPUB or has no file path, and
+/// [`DomainError::Parse`] when the archive cannot be read.
+pub async fn read_book_resource(
+    book_id: i32,
+    href: &str,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<(Vec<u8>, String), DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+
+    if book.file_type.as_deref() != Some("epub") {
+        return Err(DomainError::File(format!("Book {} is not an EPUB", book_id)));
+    }
+    let path = book.file_path.as_deref()
+        .ok_or_else(|| DomainError::File("No file path for EPUB book".into()))?;
+
+    epub_handler::read_epub_resource(path, href)
+        .await
+        .map_err(|e| DomainError::Parse(e.to_string()))?
+        .ok_or(DomainError::NotFound)
+}
+
 /// Reads content from an ebook file based on its format.
 ///
-/// For EPUBs, returns the concatenated HTML. For PDFs, returns the first page
-/// rendered as an image with extracted text spans.
+/// For EPUBs, returns the concatenated HTML, or only the requested chapter
+/// (plus `prefetch` neighbours on each side) when `chapter` is given. For
+/// PDFs, returns the first page rendered as an image with extracted text
+/// spans. For MOBI files, returns the decoded HTML text.
 ///
 /// # Arguments
 ///
 /// * `path` - Absolute path to the ebook file on disk.
-/// * `file_type` - Either `"epub"` or `"pdf"`.
+/// * `file_type` - One of `"epub"`, `"pdf"` or `"mobi"`.
+/// * `chapter` - Optional 0-based EPUB spine index to load instead of the
+///   whole book. Ignored for other formats.
+/// * `prefetch` - Neighbouring chapters to include on each side of
+///   `chapter`. Defaults to `0`.
+/// * `book_id` - Optional database ID of the book. When given, EPUB images
+///   are linked through the `book://` protocol instead of being inlined.
 ///
 /// # Returns
 ///
-/// [`BookContent::Epub`] containing the full HTML for EPUBs, or
-/// [`BookContent::Pdf`] containing the rendered first page for PDFs.
+/// [`BookContent::Epub`] containing the full HTML for EPUBs,
+/// [`BookContent::EpubChapters`] when a chapter is requested,
+/// [`BookContent::Pdf`] containing the rendered first page for PDFs, or
+/// [`BookContent::Mobi`] containing the full HTML for MOBI files.
 ///
 /// # Errors
 ///
 /// Returns [`DomainError::Parse`] when the file cannot be read. Returns
-/// [`DomainError::File`] when `file_type` is not `"epub"` or `"pdf"`.
-pub async fn read_book(path: &str, file_type: &str) -> Result<BookContent, DomainError> {
+/// [`DomainError::File`] when `file_type` is not supported.
+pub async fn read_book(
+    path: &str,
+    file_type: &str,
+    chapter: Option<usize>,
+    prefetch: Option<usize>,
+    book_id: Option<i32>,
+) -> Result<BookContent, DomainError> {
     match file_type {
-        "epub" => {
-            let html = epub_handler::get_epub_content(path)
-                .await
-                .map_err(|e| DomainError::Parse(e.to_string()))?;
-            Ok(BookContent::Epub(html))
-        }
+        "epub" => match chapter {
+            Some(index) => {
+                let chapters =
+                    read_epub_chapter(path, index, prefetch.unwrap_or(0), book_id).await?;
+                Ok(BookContent::EpubChapters(chapters))
+            }
+            None => {
+                let html = epub_handler::get_epub_content(path, book_id)
+                    .await
+                    .map_err(|e| DomainError::Parse(e.to_string()))?;
+                Ok(BookContent::Epub(html))
+            }
+        },
         "pdf" => {
             let page = pdf_handler::read_pdf_page(path, 0)
                 .await
                 .map_err(|e| DomainError::Parse(e.to_string()))?;
             Ok(BookContent::Pdf(page))
         }
-        other => Err(DomainError::File(format!("Unsupported file type: {}", other))),
+        "mobi" => {
+            let html = mobi_handler::get_mobi_content(path)
+                .await
+                .map_err(|e| DomainError::Parse(e.to_string()))?;
+            Ok(BookContent::Mobi(html))
+        }
+        other => Err(DomainError::File(format!(
+            "Unsupported file type: {}",
+            other
+        ))),
     }
 }
 
 /// Removes a book from the library by ID.
 ///
 /// Associated records (bookmarks, annotations, reading progress, book-author
-/// links) are cascade-deleted by SQLite foreign keys.
+/// links) are cascade-deleted by SQLite foreign keys. The book's cached cover
+/// thumbnails are deleted as well, on a best-effort basis.
 ///
 /// # Arguments
 ///
@@ -328,61 +1045,16 @@ pub async fn remove_book(
     find_id: i32,
     book_repo: &Arc<dyn BookRepository>,
 ) -> Result<(), DomainError> {
-    book_repo.delete(find_id).await
-}
-
-/// Recursively scans a directory for EPUB and PDF files and imports them.
-///
-/// # Arguments
-///
-/// * `dir_path` - Directory to scan recursively for ebook files.
-/// * `book_repo` - Repository for inserting book records.
-/// * `author_repo` - Repository for finding or creating authors.
-/// * `book_author_repo` - Repository for linking authors to books.
-/// * `publisher_repo` - Repository for finding or creating publishers.
-///
-/// # Returns
-///
-/// A list of error messages for files that failed to import. Successfully
-/// imported books are silently added to the library.
-///
-/// # Errors
-///
-/// Returns [`DomainError::File`] when the directory cannot be scanned.
-pub async fn scan_directory(
-    dir_path: &Path,
-    book_repo: &Arc<dyn BookRepository>,
-    author_repo: &Arc<dyn AuthorRepository>,
-    book_author_repo: &Arc<dyn BookAuthorRepository>,
-    publisher_repo: &Arc<dyn PublisherRepository>,
-) -> Result<Vec<String>, DomainError> {
-    let epub_paths = epub_handler::scan_epubs(dir_path.to_path_buf())
-        .await
-        .map_err(|e| DomainError::File(e.to_string()))?;
-    let pdf_paths = pdf_handler::scan_pdfs(dir_path.to_path_buf())
-        .await
-        .map_err(|e| DomainError::File(e.to_string()))?;
-
-    let mut errors = Vec::new();
+    let checksum = book_repo
+        .find_by_id(find_id)
+        .await?
+        .and_then(|book| book.checksum);
+    book_repo.delete(find_id).await?;
 
-    for path in epub_paths.iter().chain(pdf_paths.iter()) {
-        if let Err(e) = import_book(
-            path,
-            book_repo,
-            author_repo,
-            book_author_repo,
-            publisher_repo,
-        )
-        .await
-        {
-            eprintln!(
-                "Failed to import {:?}: {}",
-                path.file_name().unwrap_or_default(),
-                e
-            );
-            errors.push(format!("{:?}: {}", path.file_name().unwrap_or_default(), e));
-        }
+    if let Some(checksum) = checksum
+        && let Err(e) = cover_cache::remove_cover_dir(&cover_cache::cover_cache_dir(), &checksum)
+    {
+        log::warn!("Failed to remove cached cover: {}", e);
     }
-
-    Ok(errors)
+    Ok(())
 }
@@ -0,0 +1,268 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::application::book::{LibraryRepos, parse_book_file, store_parsed_book};
+use crate::application::library_scan::discover_book_files;
+use crate::application::library_watch::is_ebook_file;
+use crate::application::managed_library::{ManagedLibrary, unique_path};
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::error::DomainError;
+use crate::infrastructure::file_handlers::archive_handler;
+
+/// Outcome of importing one file, serialized with a `status` tag.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ImportResult {
+    /// The file was added to the library.
+    Imported { path: String, book: Box<BookDto> },
+    /// A book with the same checksum is already in the library.
+    Duplicate { path: String },
+    /// The file could not be imported.
+    Failed { path: String, reason: String },
+}
+
+impl ImportResult {
+    /// The result of storing a file, reported under `path`.
+    pub fn from_stored(path: String, stored: Result<BookDto, DomainError>) -> Self {
+        match stored {
+            Ok(book) => ImportResult::Imported {
+                path,
+                book: Box::new(book),
+            },
+            Err(DomainError::DuplicateBook(_)) => ImportResult::Duplicate { path },
+            Err(e) => ImportResult::Failed {
+                path,
+                reason: e.to_string(),
+            },
+        }
+    }
+}
+
+/// Folder for bundle entries extracted outside managed mode, set at startup
+/// by [`init_import_dir`].
+static IMPORT_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// Sets the folder bundles are extracted into, normally in the app's data
+/// directory. Only the first call has an effect.
+pub fn init_import_dir(dir: PathBuf) {
+    let _ = IMPORT_DIR.set(dir);
+}
+
+/// Returns the folder bundles are extracted into outside managed mode.
+///
+/// Falls back to the `IMPORT_DIR` env var, then `./imports`, when
+/// [`init_import_dir`] was never called (e.g. in tests).
+pub fn import_dir() -> PathBuf {
+    IMPORT_DIR
+        .get()
+        .cloned()
+        .or_else(|| env::var("IMPORT_DIR").ok().map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from("./imports"))
+}
+
+/// Whether a path names a ZIP bundle (`.zip` or `.cbz`), by extension.
+pub fn is_archive(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(archive_handler::is_archive_extension)
+}
+
+/// Imports files, folders and ZIP bundles, as dropped on the window.
+///
+/// Folders are searched recursively for ebooks and bundles. A bundle is
+/// recognized by its `.zip` or `.cbz` extension: one that is really an EPUB
+/// is imported as one, any other has its EPUB, PDF and MOBI entries
+/// extracted and imported (see [`import_archive`]). Every path is imported
+/// like [`import_book`](crate::application::book::import_book).
+///
+/// # Arguments
+///
+/// * `paths` - Files and folders to import.
+/// * `repos` - Repositories for storing the books and their linked records.
+///
+/// # Returns
+///
+/// One [`ImportResult`] per imported file or bundle entry, in input order.
+/// Errors never stop the batch; they are reported as
+/// [`ImportResult::Failed`].
+pub async fn import_paths(paths: &[PathBuf], repos: &LibraryRepos<'_>) -> Vec<ImportResult> {
+    let mut results = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            results.extend(import_path(path, repos).await);
+            continue;
+        }
+
+        let mut files = match discover_book_files(path).await {
+            Ok(files) => files,
+            Err(e) => {
+                results.push(failed(path, &e));
+                continue;
+            }
+        };
+        files.sort();
+        for file in files {
+            results.extend(import_path(&file, repos).await);
+        }
+    }
+    results
+}
+
+/// Imports the ebooks inside a ZIP bundle.
+///
+/// When managed mode is on, entries are extracted into a temporary folder,
+/// from where [`store_parsed_book`] files them into the library directory;
+/// otherwise into a new folder named after the bundle in [`import_dir`],
+/// which keeps the imported files. Nothing is written next to the bundle.
+/// Extracted files that end up not being imported are deleted again.
+///
+/// # Arguments
+///
+/// * `archive` - The `.zip` or `.cbz` file.
+/// * `repos` - Repositories for storing the books and their linked records.
+///
+/// # Returns
+///
+/// One [`ImportResult`] per EPUB, PDF or MOBI entry, with the entry's path
+/// below the bundle's (e.g. `bundle.zip/books/dune.epub`); a single
+/// [`ImportResult::Failed`] for the bundle when it cannot be read or holds
+/// no ebooks.
+pub async fn import_archive(archive: &Path, repos: &LibraryRepos<'_>) -> Vec<ImportResult> {
+    let managed = ManagedLibrary::current();
+    let dest = match &managed {
+        Some(_) => staging_dir(),
+        None => unique_path(
+            &import_dir().join(archive.file_stem().unwrap_or_default()),
+            None,
+        ),
+    };
+
+    let results = match extract_archive(archive, &dest).await {
+        Ok(entries) => {
+            let mut results = Vec::new();
+            for (source, extracted) in entries {
+                let result = match extracted {
+                    Ok(file) => {
+                        let result = import_file(&file, &source, repos).await;
+                        if !matches!(result, ImportResult::Imported { .. }) {
+                            let _ = tokio::fs::remove_file(&file).await;
+                        }
+                        result
+                    }
+                    Err(e) => failed(&source, &e),
+                };
+                results.push(result);
+            }
+            if results.is_empty() {
+                results.push(failed(
+                    archive,
+                    &DomainError::File("No EPUB, PDF or MOBI files in the archive".to_string()),
+                ));
+            }
+            results
+        }
+        Err(e) => vec![failed(archive, &e)],
+    };
+
+    if managed.is_some() {
+        let _ = tokio::fs::remove_dir_all(&dest).await;
+    } else {
+        remove_empty_dirs(&dest).await;
+    }
+    results
+}
+
+/// Imports a single file or bundle.
+async fn import_path(path: &Path, repos: &LibraryRepos<'_>) -> Vec<ImportResult> {
+    if is_archive(path) {
+        import_archive(path, repos).await
+    } else {
+        vec![import_file(path, path, repos).await]
+    }
+}
+
+/// Parses and stores an ebook file, reporting it under `source`.
+async fn import_file(file: &Path, source: &Path, repos: &LibraryRepos<'_>) -> ImportResult {
+    let path = source.to_string_lossy().to_string();
+    let stored = match parse_book_file(file).await {
+        Ok((metadata, file_type)) => store_parsed_book(file, metadata, file_type, repos).await,
+        Err(e) => Err(e),
+    };
+    ImportResult::from_stored(path, stored)
+}
+
+/// Extracts a bundle's ebooks into `dest`, pairing each extracted file, or
+/// its error, with the entry's path below the bundle's. An EPUB with a ZIP
+/// extension is copied as a whole, with an `.epub` extension.
+async fn extract_archive(
+    archive: &Path,
+    dest: &Path,
+) -> Result<Vec<(PathBuf, Result<PathBuf, DomainError>)>, DomainError> {
+    let is_epub = archive_handler::is_epub_container(archive.to_path_buf())
+        .await
+        .map_err(|e| DomainError::File(format!("{}: {}", archive.display(), e)))?;
+
+    if is_epub {
+        let name = archive.with_extension("epub");
+        let target = dest.join(name.file_name().unwrap_or_default());
+        tokio::fs::create_dir_all(dest)
+            .await
+            .map_err(|e| DomainError::File(format!("{}: {}", dest.display(), e)))?;
+        let copied = tokio::fs::copy(archive, &target)
+            .await
+            .map(|_| target)
+            .map_err(|e| DomainError::File(e.to_string()));
+        return Ok(vec![(archive.to_path_buf(), copied)]);
+    }
+
+    let entries =
+        archive_handler::extract_entries(archive.to_path_buf(), dest.to_path_buf(), is_ebook_file)
+            .await
+            .map_err(|e| DomainError::File(format!("{}: {}", archive.display(), e)))?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            (
+                archive.join(&entry.name),
+                entry.result.map_err(|e| DomainError::File(e.to_string())),
+            )
+        })
+        .collect())
+}
+
+/// A new, unused folder in the system temp directory.
+fn staging_dir() -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!("stellaron-import-{}-{}", std::process::id(), nanos))
+}
+
+/// Removes the empty folders in `dir`, and `dir` itself if nothing is left.
+async fn remove_empty_dirs(dir: &Path) {
+    let dirs: Vec<PathBuf> = WalkDir::new(dir)
+        .contents_first(true)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_dir())
+        .map(|e| e.into_path())
+        .collect();
+    for dir in dirs {
+        // Fails for folders that still hold files.
+        let _ = tokio::fs::remove_dir(dir).await;
+    }
+}
+
+/// An [`ImportResult::Failed`] for a path.
+fn failed(path: &Path, error: &DomainError) -> ImportResult {
+    ImportResult::Failed {
+        path: path.to_string_lossy().to_string(),
+        reason: error.to_string(),
+    }
+}
@@ -0,0 +1,378 @@
+use std::sync::Arc;
+
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::dto::collection_dto::CollectionDto;
+use crate::domain::error::DomainError;
+use crate::domain::models::collection::Collection;
+use crate::domain::models::smart_collection::{COLLECTION_KIND_SMART, RuleGroup};
+use crate::domain::repository::*;
+
+/// Returns all collections with the IDs of their books.
+///
+/// Smart collections are evaluated, so their book IDs reflect the library as
+/// it is now.
+///
+/// # Arguments
+///
+/// * `collection_repo` - Repository for querying collections.
+///
+/// # Returns
+///
+/// One [`CollectionDto`] per collection, ordered by name.
+///
+/// # Errors
+///
+/// Delegates to the repository; returns [`DomainError::Database`] on failure.
+pub async fn list_collections(
+    collection_repo: &Arc<dyn CollectionRepository>,
+) -> Result<Vec<CollectionDto>, DomainError> {
+    let collections = collection_repo.find_all().await?;
+    let mut dtos = Vec::with_capacity(collections.len());
+
+    for collection in collections {
+        let book_ids = collection_book_ids(&collection, collection_repo).await?;
+        dtos.push(CollectionDto::new(collection, book_ids));
+    }
+
+    Ok(dtos)
+}
+
+/// Returns a single collection with the IDs of its books.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `collection_repo` - Repository for querying collections.
+///
+/// # Errors
+///
+/// Returns [`DomainError::CollectionNotFound`] if no collection has the
+/// given ID.
+pub async fn get_collection(
+    collection_id: i32,
+    collection_repo: &Arc<dyn CollectionRepository>,
+) -> Result<CollectionDto, DomainError> {
+    let collection = collection_repo
+        .find_by_id(collection_id)
+        .await?
+        .ok_or(DomainError::CollectionNotFound(collection_id))?;
+    let book_ids = collection_book_ids(&collection, collection_repo).await?;
+
+    Ok(CollectionDto::new(collection, book_ids))
+}
+
+/// Creates a new collection.
+///
+/// A collection with `rules` is a smart collection; its rules are compiled
+/// and evaluated once before saving so invalid rules are rejected up front.
+/// Without rules an empty manual collection is created.
+///
+/// # Arguments
+///
+/// * `collection` - Name and optional description, accent color, cover and
+///   rules. The name is trimmed.
+/// * `collection_repo` - Repository for inserting the collection.
+///
+/// # Returns
+///
+/// The created collection.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if the name is blank or the rules
+/// are invalid.
+pub async fn create_collection(
+    mut collection: NewCollection,
+    collection_repo: &Arc<dyn CollectionRepository>,
+) -> Result<CollectionDto, DomainError> {
+    collection.name = validate_name(&collection.name)?;
+    if let Some(rules) = &collection.rules {
+        collection_repo.find_book_ids_matching(rules).await?;
+    }
+    let id = collection_repo.insert(collection).await?;
+    get_collection(id, collection_repo).await
+}
+
+/// Renames a collection.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `name` - The new name; trimmed before saving.
+/// * `collection_repo` - Repository for updating the collection.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if the name is blank and
+/// [`DomainError::CollectionNotFound`] if the collection does not exist.
+pub async fn rename_collection(
+    collection_id: i32,
+    name: &str,
+    collection_repo: &Arc<dyn CollectionRepository>,
+) -> Result<(), DomainError> {
+    let name = validate_name(name)?;
+    ensure_collection_exists(collection_id, collection_repo).await?;
+
+    collection_repo
+        .update(
+            collection_id,
+            UpdateCollection {
+                name: Some(name),
+                description: None,
+                accent_color: None,
+                cover_image: None,
+                rules: None,
+            },
+        )
+        .await
+}
+
+/// Deletes a collection. The books it contained stay in the library.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `collection_repo` - Repository for deleting the collection.
+///
+/// # Errors
+///
+/// Delegates to the repository; returns [`DomainError::Database`] on failure.
+/// No error is returned if the ID does not exist.
+pub async fn delete_collection(
+    collection_id: i32,
+    collection_repo: &Arc<dyn CollectionRepository>,
+) -> Result<(), DomainError> {
+    collection_repo.delete(collection_id).await
+}
+
+/// Appends a book to the end of a collection.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `book_id` - The book's database ID.
+/// * `collection_repo` - Repository for linking the book.
+/// * `book_repo` - Repository for checking that the book exists.
+///
+/// # Errors
+///
+/// Returns [`DomainError::CollectionNotFound`] or
+/// [`DomainError::BookNotFound`] when either ID does not exist, and
+/// [`DomainError::InvalidInput`] for smart collections. Adding a book that
+/// is already in the collection is not an error.
+pub async fn add_book_to_collection(
+    collection_id: i32,
+    book_id: i32,
+    collection_repo: &Arc<dyn CollectionRepository>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<(), DomainError> {
+    ensure_manual_collection(collection_id, collection_repo).await?;
+    book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+
+    collection_repo.add_book(collection_id, book_id).await
+}
+
+/// Removes a book from a collection.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `book_id` - The book's database ID.
+/// * `collection_repo` - Repository for unlinking the book.
+///
+/// # Errors
+///
+/// Delegates to the repository; returns [`DomainError::Database`] on failure.
+pub async fn remove_book_from_collection(
+    collection_id: i32,
+    book_id: i32,
+    collection_repo: &Arc<dyn CollectionRepository>,
+) -> Result<(), DomainError> {
+    collection_repo.remove_book(collection_id, book_id).await
+}
+
+/// Reorders the books of a collection.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `book_ids` - Book IDs in their new order. Unlisted books keep their
+///   relative order after the listed ones.
+/// * `collection_repo` - Repository for updating positions.
+///
+/// # Returns
+///
+/// The collection with its book IDs in the new order.
+///
+/// # Errors
+///
+/// Returns [`DomainError::CollectionNotFound`] if the collection does not
+/// exist and [`DomainError::InvalidInput`] for smart collections, whose
+/// books are ordered by title.
+pub async fn reorder_collection_books(
+    collection_id: i32,
+    book_ids: Vec<i32>,
+    collection_repo: &Arc<dyn CollectionRepository>,
+) -> Result<CollectionDto, DomainError> {
+    ensure_manual_collection(collection_id, collection_repo).await?;
+    collection_repo.reorder_books(collection_id, book_ids).await?;
+    get_collection(collection_id, collection_repo).await
+}
+
+/// Returns the books of a collection, in collection order.
+///
+/// Smart collections are evaluated and their books ordered by title.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `collection_repo` - Repository for the collection's book IDs.
+/// * `book_repo` - Repository for looking up books.
+///
+/// # Returns
+///
+/// A [`BookDto`] per book in the collection.
+///
+/// # Errors
+///
+/// Returns [`DomainError::CollectionNotFound`] if the collection does not
+/// exist.
+pub async fn get_collection_books(
+    collection_id: i32,
+    collection_repo: &Arc<dyn CollectionRepository>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<Vec<BookDto>, DomainError> {
+    let collection = collection_repo
+        .find_by_id(collection_id)
+        .await?
+        .ok_or(DomainError::CollectionNotFound(collection_id))?;
+    let book_ids = collection_book_ids(&collection, collection_repo).await?;
+
+    crate::application::book::get_books_by_ids(&book_ids, book_repo).await
+}
+
+/// Replaces the rules of a smart collection.
+///
+/// # Arguments
+///
+/// * `collection_id` - The collection's database ID.
+/// * `rules` - The new rule group.
+/// * `collection_repo` - Repository for updating the collection.
+///
+/// # Returns
+///
+/// The collection with its book IDs evaluated against the new rules.
+///
+/// # Errors
+///
+/// Returns [`DomainError::CollectionNotFound`] if the collection does not
+/// exist and [`DomainError::InvalidInput`] if it is a manual collection or
+/// the rules are invalid.
+pub async fn update_smart_collection_rules(
+    collection_id: i32,
+    rules: RuleGroup,
+    collection_repo: &Arc<dyn CollectionRepository>,
+) -> Result<CollectionDto, DomainError> {
+    let collection = collection_repo
+        .find_by_id(collection_id)
+        .await?
+        .ok_or(DomainError::CollectionNotFound(collection_id))?;
+    if collection.kind != COLLECTION_KIND_SMART {
+        return Err(DomainError::InvalidInput(
+            "Rules can only be set on smart collections".into(),
+        ));
+    }
+
+    collection_repo.find_book_ids_matching(&rules).await?;
+    collection_repo
+        .update(
+            collection_id,
+            UpdateCollection {
+                name: None,
+                description: None,
+                accent_color: None,
+                cover_image: None,
+                rules: Some(rules),
+            },
+        )
+        .await?;
+    get_collection(collection_id, collection_repo).await
+}
+
+/// Evaluates unsaved smart-collection rules.
+///
+/// # Arguments
+///
+/// * `rules` - The rule group to evaluate.
+/// * `collection_repo` - Repository for evaluating the rules.
+/// * `book_repo` - Repository for looking up books.
+///
+/// # Returns
+///
+/// The matching books as [`BookDto`]s, ordered by title.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if the rules are invalid.
+pub async fn preview_smart_collection(
+    rules: &RuleGroup,
+    collection_repo: &Arc<dyn CollectionRepository>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<Vec<BookDto>, DomainError> {
+    let book_ids = collection_repo.find_book_ids_matching(rules).await?;
+    crate::application::book::get_books_by_ids(&book_ids, book_repo).await
+}
+
+/// Returns the book IDs of a collection: its stored members for manual
+/// collections, or the result of its rules for smart ones.
+async fn collection_book_ids(
+    collection: &Collection,
+    collection_repo: &Arc<dyn CollectionRepository>,
+) -> Result<Vec<i32>, DomainError> {
+    match &collection.rules {
+        Some(rules) if collection.kind == COLLECTION_KIND_SMART => {
+            collection_repo.find_book_ids_matching(rules).await
+        }
+        _ => collection_repo.find_book_ids(collection.id).await,
+    }
+}
+
+/// Returns [`DomainError::CollectionNotFound`] unless the collection exists.
+async fn ensure_collection_exists(
+    collection_id: i32,
+    collection_repo: &Arc<dyn CollectionRepository>,
+) -> Result<(), DomainError> {
+    match collection_repo.find_by_id(collection_id).await? {
+        Some(_) => Ok(()),
+        None => Err(DomainError::CollectionNotFound(collection_id)),
+    }
+}
+
+/// Like [`ensure_collection_exists`], but also rejects smart collections,
+/// whose membership is defined by their rules.
+async fn ensure_manual_collection(
+    collection_id: i32,
+    collection_repo: &Arc<dyn CollectionRepository>,
+) -> Result<(), DomainError> {
+    match collection_repo.find_by_id(collection_id).await? {
+        Some(c) if c.kind == COLLECTION_KIND_SMART => Err(DomainError::InvalidInput(
+            "Books of a smart collection are selected by its rules".into(),
+        )),
+        Some(_) => Ok(()),
+        None => Err(DomainError::CollectionNotFound(collection_id)),
+    }
+}
+
+/// Trims a collection name, rejecting blank names.
+fn validate_name(name: &str) -> Result<String, DomainError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DomainError::InvalidInput(
+            "Collection name cannot be empty".into(),
+        ));
+    }
+    Ok(name.to_string())
+}
@@ -0,0 +1,299 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::application::book::{get_book, remove_book};
+use crate::application::bundle_import::is_archive;
+use crate::application::library_scan::discover_book_files;
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::error::DomainError;
+use crate::domain::models::book::{
+    Book, FILE_STATUS_MISSING, FILE_STATUS_MODIFIED, FILE_STATUS_MOVED, FILE_STATUS_OK,
+};
+use crate::domain::models::library_root::LibraryRoot;
+use crate::domain::repository::*;
+use crate::infrastructure::cover_cache;
+use crate::utils::file::compute_checksum;
+
+/// Result of checking one book's file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BookFileCheck {
+    pub book_id: i32,
+    pub title: String,
+    /// The path stored for the book.
+    pub file_path: Option<String>,
+    /// One of the `FILE_STATUS_*` constants in
+    /// [`crate::domain::models::book`].
+    pub status: String,
+    /// Where the file of a moved book was found.
+    pub found_path: Option<String>,
+}
+
+/// Outcome of [`verify_library`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LibraryHealthReport {
+    pub ok: usize,
+    pub missing: usize,
+    pub moved: usize,
+    pub modified: usize,
+    /// The books that are not OK, in the order they were checked.
+    pub issues: Vec<BookFileCheck>,
+}
+
+impl LibraryHealthReport {
+    /// Counts a checked book, keeping it in `issues` unless it is OK.
+    pub fn record(&mut self, check: BookFileCheck) {
+        match check.status.as_str() {
+            FILE_STATUS_OK => {
+                self.ok += 1;
+                return;
+            }
+            FILE_STATUS_MOVED => self.moved += 1,
+            FILE_STATUS_MODIFIED => self.modified += 1,
+            _ => self.missing += 1,
+        }
+        self.issues.push(check);
+    }
+}
+
+/// A fix for a book flagged by [`verify_library`], deserialized from an
+/// object with an `action` tag.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum FileRepair {
+    /// Points the book at another file, e.g. the `found_path` of a moved
+    /// book.
+    Relink { path: String },
+    /// Accepts the file as it is now and stores its checksum.
+    Rehash,
+    /// Deletes the book from the library. The file is left alone.
+    Remove,
+}
+
+/// Status of a book whose file is on disk: OK when its checksum matches
+/// the stored one, or when none was stored, otherwise modified.
+pub fn status_for_checksum(stored: Option<&str>, current: &str) -> &'static str {
+    match stored {
+        Some(stored) if stored != current => FILE_STATUS_MODIFIED,
+        _ => FILE_STATUS_OK,
+    }
+}
+
+/// Checks every book's file and stores the result in its `file_status`.
+///
+/// Files on disk are checksummed: a mismatch with the stored checksum
+/// marks the book modified. Books whose file is gone are looked for under
+/// the library folders by checksum and marked moved when found, otherwise
+/// missing. Only files not belonging to a book are checksummed in that
+/// search.
+///
+/// # Arguments
+///
+/// * `book_repo` - Repository for listing and updating books.
+/// * `root_repo` - Repository holding the library folders.
+///
+/// # Returns
+///
+/// A [`LibraryHealthReport`] with the count per status and every book that
+/// is not OK.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] on query failure. Unreadable files
+/// are reported as missing.
+pub async fn verify_library(
+    book_repo: &Arc<dyn BookRepository>,
+    root_repo: &Arc<dyn LibraryRootRepository>,
+) -> Result<LibraryHealthReport, DomainError> {
+    let mut report = LibraryHealthReport::default();
+    let mut gone = Vec::new();
+    let mut known_paths = HashSet::new();
+
+    for book in book_repo.find_all().await? {
+        let Some(path) = book
+            .file_path
+            .clone()
+            .filter(|path| Path::new(path).is_file())
+        else {
+            gone.push(book);
+            continue;
+        };
+        let status = match compute_checksum(&path).await {
+            Ok(current) => status_for_checksum(book.checksum.as_deref(), &current),
+            Err(e) => {
+                log::warn!("Failed to checksum {}: {}", path, e);
+                FILE_STATUS_MISSING
+            }
+        };
+        known_paths.insert(path);
+        report.record(store_status(book, status, None, book_repo).await?);
+    }
+
+    if gone.is_empty() {
+        return Ok(report);
+    }
+    let roots = root_repo.find_all().await?;
+    let unclaimed = checksum_unclaimed_files(&roots, &known_paths).await;
+    for book in gone {
+        let found_path = book
+            .checksum
+            .as_deref()
+            .and_then(|checksum| unclaimed.get(checksum))
+            .cloned();
+        let status = if found_path.is_some() {
+            FILE_STATUS_MOVED
+        } else {
+            FILE_STATUS_MISSING
+        };
+        report.record(store_status(book, status, found_path, book_repo).await?);
+    }
+
+    Ok(report)
+}
+
+/// Applies a [`FileRepair`] to a book.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `repair` - The fix to apply.
+/// * `book_repo` - Repository for updating or deleting the book.
+///
+/// # Returns
+///
+/// The updated book, or `None` after [`FileRepair::Remove`].
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if the book does not exist,
+/// [`DomainError::File`] if the file to relink or rehash cannot be read and
+/// [`DomainError::InvalidInput`] if another book already uses the relink
+/// path.
+pub async fn repair_book_file(
+    book_id: i32,
+    repair: FileRepair,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<Option<BookDto>, DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+
+    match repair {
+        FileRepair::Relink { path } => {
+            if let Some(other) = book_repo.find_by_file_path(&path).await?
+                && other.id != book_id
+            {
+                return Err(DomainError::InvalidInput(format!(
+                    "{} already belongs to book {}",
+                    path, other.id
+                )));
+            }
+            let current = checksum_file(&path).await?;
+            let status = status_for_checksum(book.checksum.as_deref(), &current);
+            book_repo
+                .update(
+                    book_id,
+                    UpdateBook {
+                        file_path: Some(path),
+                        file_status: Some(status.to_string()),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+        }
+        FileRepair::Rehash => {
+            let path = book
+                .file_path
+                .clone()
+                .ok_or_else(|| DomainError::File(format!("Book {} has no file", book_id)))?;
+            let checksum = checksum_file(&path).await?;
+            let cover_image_path = cover_cache::rekey_cover(
+                book.cover_image_path.as_deref(),
+                book.checksum.as_deref(),
+                &checksum,
+            );
+            let update = UpdateBook {
+                checksum: Some(checksum),
+                cover_image_path,
+                file_status: Some(FILE_STATUS_OK.to_string()),
+                ..Default::default()
+            };
+            book_repo.update(book_id, update).await?;
+        }
+        FileRepair::Remove => {
+            remove_book(book_id, book_repo).await?;
+            return Ok(None);
+        }
+    }
+
+    get_book(book_id, book_repo).await
+}
+
+/// Stores a book's new status when it changed and returns its check.
+async fn store_status(
+    book: Book,
+    status: &str,
+    found_path: Option<String>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<BookFileCheck, DomainError> {
+    if book.file_status != status {
+        book_repo
+            .update(
+                book.id,
+                UpdateBook {
+                    file_status: Some(status.to_string()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+    }
+    Ok(BookFileCheck {
+        book_id: book.id,
+        title: book.title,
+        file_path: book.file_path,
+        status: status.to_string(),
+        found_path,
+    })
+}
+
+/// Checksums the ebook files under the library folders that no book points
+/// at, keyed by checksum. ZIP bundles, which are never a book's file, and
+/// folders or files that cannot be read are skipped.
+async fn checksum_unclaimed_files(
+    roots: &[LibraryRoot],
+    known_paths: &HashSet<String>,
+) -> HashMap<String, String> {
+    let mut by_checksum = HashMap::new();
+    for root in roots {
+        let files = match discover_book_files(Path::new(&root.path)).await {
+            Ok(files) => files,
+            Err(e) => {
+                log::warn!("Failed to scan {}: {}", root.path, e);
+                continue;
+            }
+        };
+        for file in files {
+            let path = file.to_string_lossy().to_string();
+            if is_archive(&file) || known_paths.contains(&path) {
+                continue;
+            }
+            match compute_checksum(&path).await {
+                Ok(checksum) => {
+                    by_checksum.entry(checksum).or_insert(path);
+                }
+                Err(e) => log::warn!("Failed to checksum {}: {}", path, e),
+            }
+        }
+    }
+    by_checksum
+}
+
+/// Checksums a file, reporting unreadable files as [`DomainError::File`].
+async fn checksum_file(path: &str) -> Result<String, DomainError> {
+    compute_checksum(path)
+        .await
+        .map_err(|e| DomainError::File(format!("{}: {}", path, e)))
+}
@@ -0,0 +1,118 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::domain::error::DomainError;
+use crate::domain::models::library_root::LibraryRoot;
+use crate::domain::repository::*;
+
+/// Returns every registered library folder.
+///
+/// # Arguments
+///
+/// * `root_repo` - Repository holding the library roots.
+///
+/// # Returns
+///
+/// The roots ordered by path.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] on query failure.
+pub async fn list_library_roots(
+    root_repo: &Arc<dyn LibraryRootRepository>,
+) -> Result<Vec<LibraryRoot>, DomainError> {
+    root_repo.find_all().await
+}
+
+/// Registers a folder as a library root.
+///
+/// The path is canonicalized first, so symlinks and `..` segments cannot
+/// register the same folder twice. Files already in the folder are not
+/// imported; the caller scans it for that.
+///
+/// # Arguments
+///
+/// * `path` - Path of the folder to watch.
+/// * `root_repo` - Repository holding the library roots.
+///
+/// # Returns
+///
+/// The new [`LibraryRoot`].
+///
+/// # Errors
+///
+/// Returns [`DomainError::File`] if the path is not a directory,
+/// [`DomainError::InvalidInput`] if it overlaps a registered root and
+/// [`DomainError::Database`] on insert failure.
+pub async fn add_library_root(
+    path: &str,
+    root_repo: &Arc<dyn LibraryRootRepository>,
+) -> Result<LibraryRoot, DomainError> {
+    let canonical = tokio::fs::canonicalize(path)
+        .await
+        .map_err(|e| DomainError::File(format!("{}: {}", path, e)))?;
+    if !canonical.is_dir() {
+        return Err(DomainError::File(format!(
+            "Not a directory: {}",
+            canonical.display()
+        )));
+    }
+
+    let roots = root_repo.find_all().await?;
+    check_root_overlap(&canonical, &roots)?;
+
+    root_repo.insert(&canonical.to_string_lossy()).await
+}
+
+/// Unregisters a library root. Books imported from it stay in the library.
+///
+/// # Arguments
+///
+/// * `root_id` - The root's database ID.
+/// * `root_repo` - Repository holding the library roots.
+///
+/// # Returns
+///
+/// The removed [`LibraryRoot`], so the caller can stop watching it.
+///
+/// # Errors
+///
+/// Returns [`DomainError::NotFound`] if no root has the given ID and
+/// [`DomainError::Database`] on query failure.
+pub async fn remove_library_root(
+    root_id: i32,
+    root_repo: &Arc<dyn LibraryRootRepository>,
+) -> Result<LibraryRoot, DomainError> {
+    let root = root_repo
+        .find_by_id(root_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+    root_repo.delete(root_id).await?;
+    Ok(root)
+}
+
+/// Rejects a folder that is, contains or lies inside a registered root.
+///
+/// Nested roots would report every change twice.
+///
+/// # Arguments
+///
+/// * `path` - Canonical path of the folder to register.
+/// * `roots` - The registered roots.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] naming the overlapping root.
+pub fn check_root_overlap(path: &Path, roots: &[LibraryRoot]) -> Result<(), DomainError> {
+    for root in roots {
+        let root_path = Path::new(&root.path);
+        if path.starts_with(root_path) || root_path.starts_with(path) {
+            return Err(DomainError::InvalidInput(format!(
+                "{} overlaps the library folder {}",
+                path.display(),
+                root.path
+            )));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tokio::task::JoinSet;
+
+use crate::application::book::{LibraryRepos, parse_book_file, store_parsed_book};
+use crate::application::bundle_import::{ImportResult, import_archive, is_archive};
+use crate::domain::error::DomainError;
+use crate::infrastructure::file_handlers::{
+    archive_handler, epub_handler, mobi_handler, pdf_handler,
+};
+
+/// Number of files parsed at once when `SCAN_CONCURRENCY` is not set.
+pub const DEFAULT_SCAN_CONCURRENCY: usize = 4;
+
+/// Running totals of a library scan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ScanCounts {
+    /// Ebook files found in the directory.
+    pub discovered: usize,
+    /// Files imported as new books.
+    pub imported: usize,
+    /// Files skipped because the library already holds a copy.
+    pub duplicates: usize,
+    /// Files that could not be imported.
+    pub failed: usize,
+}
+
+impl ScanCounts {
+    /// Number of files handled so far.
+    pub fn processed(&self) -> usize {
+        self.imported + self.duplicates + self.failed
+    }
+}
+
+/// A file a scan could not import.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScanFailure {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Outcome of a library scan.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ScanSummary {
+    pub counts: ScanCounts,
+    pub failures: Vec<ScanFailure>,
+    /// The scan was cancelled before every file was processed.
+    pub cancelled: bool,
+}
+
+/// Progress report of a library scan, serialized with a `kind` tag.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScanEvent {
+    /// An ebook file was found. Sent for every file before any is imported.
+    Discovered { path: String },
+    /// A file was imported as a new book.
+    Imported {
+        path: String,
+        book_id: i32,
+        title: String,
+    },
+    /// A file was skipped because a book with its checksum already exists.
+    Duplicate { path: String },
+    /// A file could not be parsed or stored.
+    Failed { path: String, reason: String },
+    /// Totals after a file was processed.
+    Progress { counts: ScanCounts },
+    /// The scan ended or was cancelled. Always the last event.
+    Finished { summary: ScanSummary },
+}
+
+/// Cancellation flags of the library scans in progress, by job ID.
+#[derive(Default)]
+pub struct ScanJobs {
+    last_id: AtomicU64,
+    running: Mutex<HashMap<u64, Arc<AtomicBool>>>,
+}
+
+impl ScanJobs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a new scan and returns its job ID and cancellation flag.
+    pub fn start(&self) -> (u64, Arc<AtomicBool>) {
+        let job_id = self.last_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.running
+            .lock()
+            .unwrap()
+            .insert(job_id, cancelled.clone());
+        (job_id, cancelled)
+    }
+
+    /// Asks a running scan to stop. Returns `false` if no scan has the ID.
+    pub fn cancel(&self, job_id: u64) -> bool {
+        match self.running.lock().unwrap().get(&job_id) {
+            Some(cancelled) => {
+                cancelled.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forgets a scan that has ended.
+    pub fn finish(&self, job_id: u64) {
+        self.running.lock().unwrap().remove(&job_id);
+    }
+}
+
+/// Number of files a scan parses at once: `SCAN_CONCURRENCY` when set to a
+/// positive number, otherwise [`DEFAULT_SCAN_CONCURRENCY`].
+pub fn scan_concurrency() -> usize {
+    env::var("SCAN_CONCURRENCY")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_SCAN_CONCURRENCY)
+}
+
+/// Counts a processed file and reports it, followed by
+/// [`ScanEvent::Progress`].
+fn record_result(
+    result: ImportResult,
+    summary: &mut ScanSummary,
+    on_event: &(dyn Fn(ScanEvent) + Send + Sync),
+) {
+    match result {
+        ImportResult::Imported { path, book } => {
+            summary.counts.imported += 1;
+            on_event(ScanEvent::Imported {
+                path,
+                book_id: book.id,
+                title: book.title,
+            });
+        }
+        ImportResult::Duplicate { path } => {
+            summary.counts.duplicates += 1;
+            on_event(ScanEvent::Duplicate { path });
+        }
+        ImportResult::Failed { path, reason } => {
+            summary.counts.failed += 1;
+            summary.failures.push(ScanFailure {
+                path: path.clone(),
+                reason: reason.clone(),
+            });
+            on_event(ScanEvent::Failed { path, reason });
+        }
+    }
+    on_event(ScanEvent::Progress {
+        counts: summary.counts,
+    });
+}
+
+/// Recursively finds the EPUB, PDF and MOBI files and the ZIP bundles
+/// (`.zip`, `.cbz`) in a directory.
+///
+/// # Errors
+///
+/// Returns [`DomainError::File`] when the directory cannot be scanned.
+pub async fn discover_book_files(dir_path: &Path) -> Result<Vec<PathBuf>, DomainError> {
+    let epub_paths = epub_handler::scan_epubs(dir_path.to_path_buf())
+        .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+    let pdf_paths = pdf_handler::scan_pdfs(dir_path.to_path_buf())
+        .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+    let mobi_paths = mobi_handler::scan_mobis(dir_path.to_path_buf())
+        .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+    let archive_paths = archive_handler::scan_archives(dir_path.to_path_buf())
+        .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+
+    Ok(epub_paths
+        .into_iter()
+        .chain(pdf_paths)
+        .chain(mobi_paths)
+        .chain(archive_paths)
+        .collect())
+}
+
+/// Recursively scans a directory for ebooks and imports them.
+///
+/// Files are checksummed and parsed on up to `concurrency` tasks at once,
+/// then stored one after another as their parse finishes, so duplicates
+/// within the directory are still detected. The next file is only started
+/// once a parsed one has been stored, so no more than `concurrency` parsed
+/// books, covers included, are held in memory. Every step is reported through
+/// `on_event`: a [`ScanEvent::Discovered`] per file, then per processed
+/// file an imported, duplicate or failed event followed by
+/// [`ScanEvent::Progress`], and finally [`ScanEvent::Finished`].
+///
+/// ZIP bundles are imported after the other files, one at a time, through
+/// [`import_archive`]. A bundle is discovered as one file; once opened, each
+/// of its ebooks counts as discovered and gets its own event, under the
+/// entry's path below the bundle's.
+///
+/// Setting `cancelled` stops the scan after the file or bundle being
+/// stored; files already imported stay in the library.
+///
+/// # Arguments
+///
+/// * `dir_path` - Directory to scan recursively.
+/// * `concurrency` - Maximum number of files parsed at once.
+/// * `cancelled` - Flag that cancels the scan when set.
+/// * `on_event` - Receives the scan's progress events.
+/// * `repos` - Repositories for storing the books and their linked records.
+///
+/// # Returns
+///
+/// The [`ScanSummary`], also sent as the `Finished` event.
+///
+/// # Errors
+///
+/// Returns [`DomainError::File`] when the directory cannot be scanned.
+/// Failures of single files are reported in the summary instead.
+pub async fn scan_directory(
+    dir_path: &Path,
+    concurrency: usize,
+    cancelled: &Arc<AtomicBool>,
+    on_event: &(dyn Fn(ScanEvent) + Send + Sync),
+    repos: &LibraryRepos<'_>,
+) -> Result<ScanSummary, DomainError> {
+    let paths = discover_book_files(dir_path).await?;
+
+    let mut summary = ScanSummary {
+        counts: ScanCounts {
+            discovered: paths.len(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    for path in &paths {
+        on_event(ScanEvent::Discovered {
+            path: path.to_string_lossy().to_string(),
+        });
+    }
+
+    let (archives, paths): (Vec<PathBuf>, Vec<PathBuf>) =
+        paths.into_iter().partition(|path| is_archive(path));
+    let concurrency = concurrency.max(1);
+    let mut pending = paths.into_iter();
+    let mut parsing = JoinSet::new();
+    let mut task_paths = HashMap::new();
+    loop {
+        while parsing.len() < concurrency && !cancelled.load(Ordering::Relaxed) {
+            let Some(path) = pending.next() else {
+                break;
+            };
+            let task_path = path.clone();
+            let handle = parsing.spawn(async move { parse_book_file(&task_path).await });
+            task_paths.insert(handle.id(), path);
+        }
+
+        let Some(joined) = parsing.join_next_with_id().await else {
+            // Nothing left to parse: either every file was stored, or the
+            // scan was cancelled before the remaining ones were started.
+            summary.cancelled = !pending.as_slice().is_empty();
+            break;
+        };
+        if cancelled.load(Ordering::Relaxed) {
+            parsing.abort_all();
+            summary.cancelled = true;
+            break;
+        }
+
+        let (task_id, parsed) = match joined {
+            Ok((task_id, parsed)) => (task_id, parsed),
+            Err(e) => (
+                e.id(),
+                Err(DomainError::Parse(format!("Parser crashed: {}", e))),
+            ),
+        };
+        let Some(path) = task_paths.remove(&task_id) else {
+            continue;
+        };
+        let stored = match parsed {
+            Ok((metadata, file_type)) => store_parsed_book(&path, metadata, file_type, repos).await,
+            Err(e) => Err(e),
+        };
+        let result = ImportResult::from_stored(path.to_string_lossy().to_string(), stored);
+        record_result(result, &mut summary, on_event);
+    }
+
+    for archive in archives {
+        if summary.cancelled || cancelled.load(Ordering::Relaxed) {
+            summary.cancelled = true;
+            break;
+        }
+        let results = import_archive(&archive, repos).await;
+        // The bundle was counted as one discovered file.
+        summary.counts.discovered += results.len().saturating_sub(1);
+        for result in results {
+            record_result(result, &mut summary, on_event);
+        }
+    }
+
+    on_event(ScanEvent::Finished {
+        summary: summary.clone(),
+    });
+    Ok(summary)
+}
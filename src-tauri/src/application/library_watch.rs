@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::application::book::{LibraryRepos, parse_book_file, store_parsed_book};
+use crate::application::bundle_import::{ImportResult, import_archive, is_archive};
+use crate::application::library_scan::discover_book_files;
+use crate::domain::error::DomainError;
+use crate::domain::models::book::{FILE_STATUS_MISSING, FILE_STATUS_MOVED, FILE_STATUS_OK};
+use crate::domain::repository::*;
+use crate::infrastructure::file_handlers::mobi_handler;
+use crate::utils::file::compute_checksum;
+
+/// How long a path must go without filesystem events before it is handled,
+/// so files still being copied are not parsed half-written.
+pub const WATCH_SETTLE_DELAY: Duration = Duration::from_secs(2);
+
+/// How often pending paths are checked for having settled.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A change the watcher made to the library, serialized with a `kind` tag.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WatchEvent {
+    /// A new file was imported as a book.
+    Imported {
+        path: String,
+        book_id: i32,
+        title: String,
+    },
+    /// A book's file was found at a new path, or reappeared at its old one.
+    Relinked { path: String, book_id: i32 },
+    /// A book's file was deleted or moved out of the library folders.
+    Missing { path: String, book_id: i32 },
+    /// A new file could not be imported.
+    Failed { path: String, reason: String },
+}
+
+/// Paths with recent filesystem events, waiting to settle.
+#[derive(Debug, Default)]
+pub struct PendingChanges {
+    last_event: HashMap<PathBuf, Instant>,
+}
+
+impl PendingChanges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an event for a path, restarting its settle delay.
+    pub fn record(&mut self, path: PathBuf, at: Instant) {
+        self.last_event.insert(path, at);
+    }
+
+    /// Removes and returns the paths without events for at least `delay`,
+    /// sorted.
+    pub fn take_settled(&mut self, now: Instant, delay: Duration) -> Vec<PathBuf> {
+        let mut settled: Vec<PathBuf> = self
+            .last_event
+            .iter()
+            .filter(|(_, at)| now.saturating_duration_since(**at) >= delay)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in &settled {
+            self.last_event.remove(path);
+        }
+        settled.sort();
+        settled
+    }
+
+    /// Whether no path is waiting.
+    pub fn is_empty(&self) -> bool {
+        self.last_event.is_empty()
+    }
+}
+
+/// Whether a path names an ebook (EPUB, PDF or MOBI), by extension.
+pub fn is_ebook_file(path: &Path) -> bool {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    ext == "epub" || ext == "pdf" || mobi_handler::is_mobi_extension(&ext)
+}
+
+/// Whether a path names a file the library imports, an ebook or a ZIP
+/// bundle, by extension.
+pub fn is_book_file(path: &Path) -> bool {
+    is_ebook_file(path) || is_archive(path)
+}
+
+/// Keeps the library in sync with the watched folders until the channel
+/// closes.
+///
+/// Changed paths are collected until they have been quiet for
+/// [`WATCH_SETTLE_DELAY`], then handled in batches by [`apply_changes`].
+///
+/// # Arguments
+///
+/// * `changes` - Paths reported by the
+///   [`LibraryWatcher`](crate::infrastructure::library_watcher::LibraryWatcher).
+/// * `on_event` - Receives every change made to the library.
+/// * `repos` - Repositories for storing the books and their linked records.
+pub async fn watch_library(
+    mut changes: UnboundedReceiver<PathBuf>,
+    on_event: &(dyn Fn(WatchEvent) + Send + Sync),
+    repos: &LibraryRepos<'_>,
+) {
+    let mut pending = PendingChanges::new();
+    let mut poll = tokio::time::interval(WATCH_POLL_INTERVAL);
+
+    loop {
+        tokio::select! {
+            received = changes.recv() => match received {
+                Some(path) => pending.record(path, Instant::now()),
+                None => break,
+            },
+            _ = poll.tick(), if !pending.is_empty() => {
+                let settled = pending.take_settled(Instant::now(), WATCH_SETTLE_DELAY);
+                if let Err(e) = apply_changes(&settled, on_event, repos).await {
+                    log::warn!("Failed to sync library folders: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Brings the library in line with a batch of changed paths.
+///
+/// Paths that exist are handled first: ebook files, and the ebooks inside
+/// new folders, are matched against the library by path, then by checksum.
+/// A known checksum whose old file is gone is a rename or move, so the
+/// book's `file_path` is updated instead of importing a duplicate; unknown
+/// files are imported. ZIP bundles have their ebooks imported (see
+/// [`import_archive`]); entries already in the library are skipped. Then
+/// books whose file was at, or below, a path that no longer exists are
+/// flagged [`FILE_STATUS_MISSING`].
+///
+/// # Arguments
+///
+/// * `paths` - Changed files and folders.
+/// * `on_event` - Receives every change made to the library.
+/// * `repos` - Repositories for storing the books and their linked records.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] if the books cannot be read or
+/// flagged. Files that fail to import, and folders that cannot be scanned,
+/// are reported as [`WatchEvent::Failed`] instead.
+pub async fn apply_changes(
+    paths: &[PathBuf],
+    on_event: &(dyn Fn(WatchEvent) + Send + Sync),
+    repos: &LibraryRepos<'_>,
+) -> Result<(), DomainError> {
+    let (present, gone): (Vec<&PathBuf>, Vec<&PathBuf>) =
+        paths.iter().partition(|path| path.exists());
+
+    for path in present {
+        let files = if path.is_dir() {
+            match discover_book_files(path).await {
+                Ok(files) => files,
+                Err(e) => {
+                    on_event(WatchEvent::Failed {
+                        path: path.to_string_lossy().to_string(),
+                        reason: e.to_string(),
+                    });
+                    continue;
+                }
+            }
+        } else if is_book_file(path) {
+            vec![path.clone()]
+        } else {
+            continue;
+        };
+
+        for file in files {
+            if is_archive(&file) {
+                for result in import_archive(&file, repos).await {
+                    match result {
+                        ImportResult::Imported { path, book } => on_event(WatchEvent::Imported {
+                            path,
+                            book_id: book.id,
+                            title: book.title,
+                        }),
+                        ImportResult::Duplicate { .. } => {}
+                        ImportResult::Failed { path, reason } => {
+                            on_event(WatchEvent::Failed { path, reason })
+                        }
+                    }
+                }
+                continue;
+            }
+            match sync_present_file(&file, repos).await {
+                Ok(Some(event)) => on_event(event),
+                Ok(None) => {}
+                Err(e) => {
+                    on_event(WatchEvent::Failed {
+                        path: file.to_string_lossy().to_string(),
+                        reason: e.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    if !gone.is_empty() {
+        flag_missing(&gone, on_event, repos.book_repo).await?;
+    }
+    Ok(())
+}
+
+/// Imports or relinks an ebook file that exists on disk.
+///
+/// Returns `None` when the library is already up to date, or when the file
+/// is a second copy of a book whose file still exists.
+async fn sync_present_file(
+    path: &Path,
+    repos: &LibraryRepos<'_>,
+) -> Result<Option<WatchEvent>, DomainError> {
+    let book_repo = repos.book_repo;
+    let path_str = path.to_string_lossy().to_string();
+
+    if let Some(book) = book_repo.find_by_file_path(&path_str).await? {
+        // A modified file keeps its status until it is rehashed.
+        if !matches!(
+            book.file_status.as_str(),
+            FILE_STATUS_MISSING | FILE_STATUS_MOVED
+        ) {
+            return Ok(None);
+        }
+        set_file_location(book.id, &path_str, FILE_STATUS_OK, book_repo).await?;
+        return Ok(Some(WatchEvent::Relinked {
+            path: path_str,
+            book_id: book.id,
+        }));
+    }
+
+    let checksum = compute_checksum(&path_str)
+        .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+    if let Some(book) = book_repo.find_by_checksum(&checksum).await? {
+        let old_file_exists = book
+            .file_path
+            .as_deref()
+            .is_some_and(|old| Path::new(old).exists());
+        if old_file_exists {
+            return Ok(None);
+        }
+        set_file_location(book.id, &path_str, FILE_STATUS_OK, book_repo).await?;
+        return Ok(Some(WatchEvent::Relinked {
+            path: path_str,
+            book_id: book.id,
+        }));
+    }
+
+    let (metadata, file_type) = parse_book_file(path).await?;
+    match store_parsed_book(path, metadata, file_type, repos).await {
+        Ok(book) => Ok(Some(WatchEvent::Imported {
+            path: path_str,
+            book_id: book.id,
+            title: book.title,
+        })),
+        Err(DomainError::DuplicateBook(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Flags the books whose file was at, or below, one of the removed paths.
+async fn flag_missing(
+    gone: &[&PathBuf],
+    on_event: &(dyn Fn(WatchEvent) + Send + Sync),
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<(), DomainError> {
+    for book in book_repo.find_all().await? {
+        if book.file_status == FILE_STATUS_MISSING {
+            continue;
+        }
+        let Some(file_path) = book.file_path.as_deref() else {
+            continue;
+        };
+        let file = Path::new(file_path);
+        // The file may have been recreated since the event.
+        if !gone.iter().any(|path| file.starts_with(path)) || file.exists() {
+            continue;
+        }
+
+        set_file_location(book.id, file_path, FILE_STATUS_MISSING, book_repo).await?;
+        on_event(WatchEvent::Missing {
+            path: file_path.to_string(),
+            book_id: book.id,
+        });
+    }
+    Ok(())
+}
+
+/// Stores a book's file path and status.
+async fn set_file_location(
+    book_id: i32,
+    file_path: &str,
+    file_status: &str,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<(), DomainError> {
+    book_repo
+        .update(
+            book_id,
+            UpdateBook {
+                file_path: Some(file_path.to_string()),
+                file_status: Some(file_status.to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+}
@@ -0,0 +1,583 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use crate::application::book::get_book;
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::error::DomainError;
+use crate::domain::models::library_settings::{
+    IMPORT_MODE_COPY, IMPORT_MODE_MOVE, LibrarySettings,
+};
+use crate::domain::repository::*;
+use crate::infrastructure::file_handlers::{BookMetadata, author_names};
+
+/// Naming template used when none is configured.
+pub const DEFAULT_NAMING_TEMPLATE: &str = "{author}/{series}/{title}.{ext}";
+
+/// Longest file or directory name, or placeholder value, written by a
+/// naming template, in bytes.
+pub const MAX_NAME_BYTES: usize = 150;
+
+/// Stand-in for a book without any author credit.
+const UNKNOWN_AUTHOR: &str = "Unknown Author";
+
+/// Settings saved in the app, set at startup by [`load_library_settings`]
+/// and on every [`update_library_settings`].
+static SAVED_SETTINGS: RwLock<Option<LibrarySettings>> = RwLock::new(None);
+
+/// How an imported file gets into the library directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransferMode {
+    /// The original file is left where it was.
+    #[default]
+    Copy,
+    /// The original file is moved.
+    Move,
+}
+
+/// The values a naming template can refer to.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NamingFields {
+    /// `{author}`: the first author.
+    pub author: Option<String>,
+    /// `{title}`.
+    pub title: String,
+    /// `{series}`.
+    pub series: Option<String>,
+    /// `{series_index}`, e.g. `2` or `2.5`.
+    pub series_index: Option<f32>,
+    /// `{year}`: the first four characters of the publication date.
+    pub year: Option<String>,
+    /// `{ext}`: the file extension, lowercase.
+    pub ext: String,
+}
+
+impl NamingFields {
+    /// Naming fields of a parsed file that is about to be imported.
+    pub fn from_metadata(metadata: &BookMetadata) -> Self {
+        Self {
+            author: author_names(&metadata.contributors)
+                .into_iter()
+                .next()
+                .or_else(|| metadata.contributors.first().map(|c| c.name.clone())),
+            title: metadata.title.clone(),
+            series: metadata.series.clone(),
+            series_index: metadata.series.as_ref().and(metadata.series_index),
+            year: year_of(metadata.published_date.as_deref()),
+            ext: extension_of(Path::new(&metadata.file_path)),
+        }
+    }
+
+    /// Naming fields of a book in the library.
+    pub fn from_book(book: &BookDto) -> Self {
+        Self {
+            author: book.author.clone(),
+            title: book.title.clone(),
+            series: book.series.clone(),
+            series_index: book.series.as_ref().and(book.series_index),
+            year: year_of(book.published_date.as_deref()),
+            ext: extension_of(Path::new(book.file_path.as_deref().unwrap_or_default())),
+        }
+    }
+
+    /// The sanitized value of a placeholder; empty for unknown names and
+    /// missing values.
+    fn value(&self, placeholder: &str) -> String {
+        let raw = match placeholder {
+            "author" => self.author.clone().unwrap_or_else(|| UNKNOWN_AUTHOR.into()),
+            "title" => self.title.clone(),
+            "series" => self.series.clone().unwrap_or_default(),
+            "series_index" => self.series_index.map(|i| i.to_string()).unwrap_or_default(),
+            "year" => self.year.clone().unwrap_or_default(),
+            "ext" => self.ext.clone(),
+            _ => String::new(),
+        };
+        sanitize_component(&raw)
+    }
+}
+
+/// Managed library settings in effect.
+///
+/// Managed mode is on when a library directory is configured: imported
+/// files are then placed under it at the path given by the naming template
+/// (default [`DEFAULT_NAMING_TEMPLATE`]), copied or moved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManagedLibrary {
+    pub dir: PathBuf,
+    pub template: String,
+    pub mode: TransferMode,
+}
+
+impl ManagedLibrary {
+    /// Returns the settings in effect; `None` when managed mode is off.
+    ///
+    /// These are the settings saved in the app, or, until any are saved,
+    /// the ones from the environment (see [`from_env`](Self::from_env)).
+    pub fn current() -> Option<Self> {
+        let saved = SAVED_SETTINGS
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        match saved {
+            Some(settings) => Self::from_settings(&settings),
+            None => Self::from_env(),
+        }
+    }
+
+    /// Reads the settings from the environment; `None` when managed mode is
+    /// off.
+    ///
+    /// Managed mode is on when `LIBRARY_DIR` is set. The template comes from
+    /// `LIBRARY_NAMING_TEMPLATE`, and files are moved when
+    /// `LIBRARY_IMPORT_MODE` is `move`.
+    pub fn from_env() -> Option<Self> {
+        Self::from_settings(&LibrarySettings {
+            library_dir: env::var("LIBRARY_DIR").ok(),
+            naming_template: env::var("LIBRARY_NAMING_TEMPLATE").unwrap_or_default(),
+            import_mode: env::var("LIBRARY_IMPORT_MODE").unwrap_or_default(),
+        })
+    }
+
+    /// Builds the settings from saved ones; `None` when no directory is set.
+    /// A blank template means [`DEFAULT_NAMING_TEMPLATE`], and any mode but
+    /// `move` means copy.
+    pub fn from_settings(settings: &LibrarySettings) -> Option<Self> {
+        let dir = settings
+            .library_dir
+            .as_deref()
+            .filter(|dir| !dir.trim().is_empty())?;
+        let template = Some(settings.naming_template.as_str())
+            .filter(|template| !template.trim().is_empty())
+            .unwrap_or(DEFAULT_NAMING_TEMPLATE);
+        let mode = match settings.import_mode.trim() {
+            mode if mode.eq_ignore_ascii_case(IMPORT_MODE_MOVE) => TransferMode::Move,
+            _ => TransferMode::Copy,
+        };
+        Some(Self {
+            dir: PathBuf::from(dir),
+            template: template.to_string(),
+            mode,
+        })
+    }
+
+    /// Whether a file lies inside the library directory.
+    pub fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.dir)
+    }
+
+    /// Where a book with the given fields belongs, before collision handling.
+    pub fn path_for(&self, fields: &NamingFields) -> PathBuf {
+        self.dir.join(render_template(&self.template, fields))
+    }
+
+    /// Copies or moves a file about to be imported into the library
+    /// directory. Files already inside it stay where they are.
+    ///
+    /// # Returns
+    ///
+    /// The path the book should be stored with.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::File`] when the file cannot be placed.
+    pub async fn adopt(
+        &self,
+        source: &Path,
+        fields: &NamingFields,
+    ) -> Result<PathBuf, DomainError> {
+        if self.contains(source) {
+            return Ok(source.to_path_buf());
+        }
+        let target = unique_path(&self.path_for(fields), None);
+        transfer(source, &target, self.mode).await?;
+        Ok(target)
+    }
+
+    /// Undoes [`adopt`](Self::adopt) after the import failed: deletes the
+    /// copy, or moves the file back.
+    pub async fn release(&self, source: &Path, placed: &Path) {
+        if source == placed {
+            return;
+        }
+        let result = match self.mode {
+            TransferMode::Copy => tokio::fs::remove_file(placed).await,
+            TransferMode::Move => move_file(placed, source).await,
+        };
+        if let Err(e) = result {
+            log::warn!("Failed to undo the import of {}: {}", placed.display(), e);
+        }
+        self.remove_empty_dirs(placed).await;
+    }
+
+    /// Removes the now empty directories above a file that left them, up to
+    /// the library directory.
+    async fn remove_empty_dirs(&self, old_path: &Path) {
+        let mut dir = old_path.parent();
+        while let Some(current) = dir {
+            if current == self.dir || !current.starts_with(&self.dir) {
+                break;
+            }
+            // Fails, and stops here, once a directory is not empty.
+            if tokio::fs::remove_dir(current).await.is_err() {
+                break;
+            }
+            dir = current.parent();
+        }
+    }
+}
+
+/// Moves a managed book's file to the path its metadata calls for and
+/// updates its `file_path`.
+///
+/// Does nothing when managed mode is off, the book's file lies outside the
+/// library directory or is already at the right path. The metadata change
+/// that prompted the move is already saved, so a file that cannot be moved
+/// only logs a warning and keeps its path; if storing the new path fails,
+/// the file is moved back.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `book_repo` - Repository for looking up and updating the book.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] on query failure.
+pub async fn sync_managed_path(
+    book_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<(), DomainError> {
+    let Some(library) = ManagedLibrary::current() else {
+        return Ok(());
+    };
+    let Some(book) = get_book(book_id, book_repo).await? else {
+        return Ok(());
+    };
+    let Some(current) = book.file_path.as_deref().map(PathBuf::from) else {
+        return Ok(());
+    };
+    if !library.contains(&current) || !current.is_file() {
+        return Ok(());
+    }
+
+    let target = unique_path(
+        &library.path_for(&NamingFields::from_book(&book)),
+        Some(&current),
+    );
+    if target == current {
+        return Ok(());
+    }
+    if let Err(e) = transfer(&current, &target, TransferMode::Move).await {
+        log::warn!("Failed to rename the file of book {}: {}", book_id, e);
+        return Ok(());
+    }
+    let updated = book_repo
+        .update(
+            book_id,
+            UpdateBook {
+                file_path: Some(target.to_string_lossy().to_string()),
+                ..Default::default()
+            },
+        )
+        .await;
+    if let Err(e) = updated {
+        if let Err(undo) = move_file(&target, &current).await {
+            log::warn!(
+                "Failed to move {} back to {}: {}",
+                target.display(),
+                current.display(),
+                undo
+            );
+        }
+        library.remove_empty_dirs(&target).await;
+        return Err(e);
+    }
+    library.remove_empty_dirs(&current).await;
+    Ok(())
+}
+
+/// Makes saved settings the ones in effect (see [`ManagedLibrary::current`]).
+///
+/// # Arguments
+///
+/// * `settings_repo` - Repository holding the saved settings.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] on query failure; the environment's
+/// settings then stay in effect.
+pub async fn load_library_settings(
+    settings_repo: &Arc<dyn LibrarySettingsRepository>,
+) -> Result<(), DomainError> {
+    if let Some(settings) = settings_repo.find().await? {
+        apply_library_settings(settings);
+    }
+    Ok(())
+}
+
+/// Returns the managed library settings in effect.
+///
+/// When managed mode is off, `library_dir` is `None` and the other fields
+/// hold the saved values, or the defaults.
+pub fn library_settings() -> LibrarySettings {
+    let saved = SAVED_SETTINGS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    if let Some(settings) = saved {
+        return settings;
+    }
+    let library = ManagedLibrary::from_env();
+    LibrarySettings {
+        library_dir: library
+            .as_ref()
+            .map(|library| library.dir.to_string_lossy().to_string()),
+        naming_template: library
+            .as_ref()
+            .map_or(DEFAULT_NAMING_TEMPLATE, |library| library.template.as_str())
+            .to_string(),
+        import_mode: match library.map(|library| library.mode) {
+            Some(TransferMode::Move) => IMPORT_MODE_MOVE,
+            _ => IMPORT_MODE_COPY,
+        }
+        .to_string(),
+    }
+}
+
+/// Validates, saves and applies new managed library settings.
+///
+/// A blank `library_dir` turns managed mode off; otherwise the directory is
+/// created if needed and stored canonicalized. A blank `naming_template`
+/// means [`DEFAULT_NAMING_TEMPLATE`]. Books already in the library are not
+/// moved.
+///
+/// # Arguments
+///
+/// * `settings` - The new settings.
+/// * `settings_repo` - Repository holding the saved settings.
+///
+/// # Returns
+///
+/// The settings as saved.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] for an `import_mode` other than
+/// `copy` or `move`, [`DomainError::File`] if the directory cannot be
+/// created and [`DomainError::Database`] on write failure.
+pub async fn update_library_settings(
+    settings: LibrarySettings,
+    settings_repo: &Arc<dyn LibrarySettingsRepository>,
+) -> Result<LibrarySettings, DomainError> {
+    let import_mode = settings.import_mode.trim().to_ascii_lowercase();
+    if import_mode != IMPORT_MODE_COPY && import_mode != IMPORT_MODE_MOVE {
+        return Err(DomainError::InvalidInput(format!(
+            "Unknown import mode: {}",
+            settings.import_mode
+        )));
+    }
+    let naming_template = match settings.naming_template.trim() {
+        "" => DEFAULT_NAMING_TEMPLATE.to_string(),
+        template => template.to_string(),
+    };
+    let library_dir = match settings.library_dir.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(dir) => {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .map_err(|e| DomainError::File(format!("{}: {}", dir, e)))?;
+            let canonical = tokio::fs::canonicalize(dir)
+                .await
+                .map_err(|e| DomainError::File(format!("{}: {}", dir, e)))?;
+            Some(canonical.to_string_lossy().to_string())
+        }
+    };
+
+    let settings = LibrarySettings {
+        library_dir,
+        naming_template,
+        import_mode,
+    };
+    settings_repo.save(&settings).await?;
+    apply_library_settings(settings.clone());
+    Ok(settings)
+}
+
+/// Replaces the settings in effect.
+fn apply_library_settings(settings: LibrarySettings) {
+    *SAVED_SETTINGS.write().unwrap_or_else(|e| e.into_inner()) = Some(settings);
+}
+
+/// Fills a naming template and returns the relative path it describes.
+///
+/// `/` (or `\`) separates directories. Placeholder values are sanitized
+/// with [`sanitize_component`], so they never add directories; a segment
+/// whose placeholders are all empty, such as `{series}` for a standalone
+/// book, is dropped. When nothing usable remains, `{title}.{ext}` is used.
+/// A file name that is too long loses the end of its stem, never its
+/// extension.
+pub fn render_template(template: &str, fields: &NamingFields) -> PathBuf {
+    let ext = fields.value("ext");
+    let suffix = format!(".{}", ext);
+    let mut segments: Vec<String> = Vec::new();
+    for raw in template.split(['/', '\\']) {
+        let mut segment = String::new();
+        let mut has_placeholder = false;
+        let mut has_value = false;
+        let mut rest = raw;
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            segment.push_str(&rest[..start]);
+            let value = fields.value(&rest[start + 1..start + len]);
+            has_placeholder = true;
+            has_value |= !value.is_empty();
+            segment.push_str(&value);
+            rest = &rest[start + len + 1..];
+        }
+        segment.push_str(rest);
+
+        if has_placeholder && !has_value {
+            continue;
+        }
+        let segment = match segment.strip_suffix(&suffix) {
+            Some(stem) if !ext.is_empty() => sanitize_file_name(stem, &suffix),
+            _ => sanitize_component(&segment),
+        };
+        if !segment.is_empty() {
+            segments.push(segment);
+        }
+    }
+
+    let has_file_name = segments
+        .last()
+        .is_some_and(|name| ext.is_empty() || name.ends_with(&suffix));
+    if !has_file_name {
+        segments.push(sanitize_file_name(&fields.value("title"), &suffix));
+    }
+    segments.iter().collect()
+}
+
+/// Sanitizes a file name stem and appends `suffix` (`.ext`), cutting the
+/// stem so the whole name fits in [`MAX_NAME_BYTES`]. Empty when the stem is.
+fn sanitize_file_name(stem: &str, suffix: &str) -> String {
+    let stem = sanitize_name(stem, MAX_NAME_BYTES.saturating_sub(suffix.len()));
+    if stem.is_empty() {
+        return stem;
+    }
+    stem + suffix
+}
+
+/// Makes text safe as a single file or directory name on every platform.
+///
+/// Path separators and characters Windows rejects become `_`, control
+/// characters and runs of whitespace become one space, leading and trailing
+/// dots and spaces are trimmed, names reserved by Windows (`CON`, `NUL`, `COM1`, …)
+/// get a trailing `_`, and the result is cut to [`MAX_NAME_BYTES`].
+pub fn sanitize_component(raw: &str) -> String {
+    sanitize_name(raw, MAX_NAME_BYTES)
+}
+
+/// [`sanitize_component`] with a custom length limit, in bytes.
+fn sanitize_name(raw: &str, max_bytes: usize) -> String {
+    let replaced: String = raw
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let collapsed = replaced.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut name = collapsed.trim_matches(['.', ' ']).to_string();
+
+    if name.len() > max_bytes {
+        let mut end = max_bytes;
+        while !name.is_char_boundary(end) {
+            end -= 1;
+        }
+        name.truncate(end);
+        name = name.trim_end_matches(['.', ' ']).to_string();
+    }
+
+    let stem = name
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    let reserved = matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+        || ((stem.starts_with("COM") || stem.starts_with("LPT"))
+            && stem.len() == 4
+            && stem.as_bytes()[3].is_ascii_digit());
+    if reserved {
+        name.push('_');
+    }
+    name
+}
+
+/// Returns `path`, or the first free `name (2).ext`, `name (3).ext`, …
+/// next to it when a file already exists there. `own` is a file that may
+/// keep its path.
+pub fn unique_path(path: &Path, own: Option<&Path>) -> PathBuf {
+    if !path.exists() || own == Some(path) {
+        return path.to_path_buf();
+    }
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (2..)
+        .map(|n| path.with_file_name(format!("{} ({}){}", stem, n, ext)))
+        .find(|candidate| !candidate.exists() || own == Some(candidate.as_path()))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Copies or moves a file, creating the target's directories.
+async fn transfer(source: &Path, target: &Path, mode: TransferMode) -> Result<(), DomainError> {
+    if let Some(parent) = target.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| DomainError::File(format!("{}: {}", parent.display(), e)))?;
+    }
+    let result = match mode {
+        TransferMode::Copy => tokio::fs::copy(source, target).await.map(|_| ()),
+        TransferMode::Move => move_file(source, target).await,
+    };
+    result.map_err(|e| {
+        DomainError::File(format!(
+            "Failed to place {} at {}: {}",
+            source.display(),
+            target.display(),
+            e
+        ))
+    })
+}
+
+/// Renames a file, falling back to copy and delete across file systems.
+async fn move_file(source: &Path, target: &Path) -> std::io::Result<()> {
+    if tokio::fs::rename(source, target).await.is_ok() {
+        return Ok(());
+    }
+    tokio::fs::copy(source, target).await?;
+    tokio::fs::remove_file(source).await
+}
+
+/// The year of a publication date such as `"2019-05-02"`.
+fn year_of(date: Option<&str>) -> Option<String> {
+    let year = date?.trim().get(..4)?;
+    year.chars()
+        .all(|c| c.is_ascii_digit())
+        .then(|| year.to_string())
+}
+
+/// Lowercase extension of a path, without the dot.
+fn extension_of(path: &Path) -> String {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
@@ -4,11 +4,22 @@
 //! repository calls to fulfill a specific business operation. Repository
 //! implementations use Diesel async to persist data in SQLite.
 
+pub mod provider;
 pub mod repository;
 pub mod service;
 pub mod state;
 
 pub mod annotation;
 pub mod book;
+pub mod bundle_import;
 pub mod bookmark;
+pub mod collection;
+pub mod library_health;
+pub mod library_root;
+pub mod library_scan;
+pub mod library_watch;
+pub mod managed_library;
 pub mod reading_progress;
+pub mod search_index;
+pub mod series;
+pub mod tag;
@@ -0,0 +1,153 @@
+//! Online metadata providers.
+//!
+//! A [`MetadataProvider`] looks a book up in a remote catalogue and returns
+//! candidate [`BookMetadata`] records ranked by confidence. The user picks a
+//! candidate and the fields to copy from it; see
+//! [`apply_metadata_candidate`](crate::application::service::book_service::apply_metadata_candidate).
+
+pub mod open_library;
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::DomainError;
+use crate::infrastructure::file_handlers::BookMetadata;
+
+/// Weight of the title in [`match_confidence`] when an author is queried;
+/// the author makes up the rest.
+const TITLE_WEIGHT: f32 = 0.7;
+
+/// A possible match for a book returned by a [`MetadataProvider`].
+#[derive(Serialize, Clone)]
+pub struct MetadataCandidate {
+    /// Name of the provider that returned the candidate, e.g. `"open_library"`.
+    pub provider: String,
+    /// The provider's own identifier for the record, if it has one.
+    pub source_id: Option<String>,
+    /// How well the candidate matches the query, from `0.0` to `1.0`.
+    /// Lookups by ISBN are exact and score `1.0`.
+    pub confidence: f32,
+    /// URL of the provider's cover image, if any.
+    pub cover_url: Option<String>,
+    /// The candidate's metadata. `file_path` and `checksum` are empty.
+    pub metadata: BookMetadata,
+}
+
+/// A book field that can be copied from a [`MetadataCandidate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataField {
+    Title,
+    Authors,
+    Publisher,
+    PublishedDate,
+    Isbn,
+    Series,
+    Tags,
+    Description,
+    Language,
+    PageCount,
+}
+
+/// A remote catalogue that can identify books.
+#[async_trait]
+pub trait MetadataProvider: Send + Sync {
+    /// Short identifier stored in [`MetadataCandidate::provider`].
+    fn name(&self) -> &'static str;
+
+    /// Looks up the editions with the given ISBN.
+    ///
+    /// # Arguments
+    ///
+    /// * `isbn` - A normalized ISBN-13.
+    ///
+    /// # Returns
+    ///
+    /// The matching records, usually zero or one, each with confidence `1.0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Provider`] when the request fails or the
+    /// response cannot be read.
+    async fn lookup_isbn(&self, isbn: &str) -> Result<Vec<MetadataCandidate>, DomainError>;
+
+    /// Searches by title and, optionally, author.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - The title to search for.
+    /// * `author` - An author name to narrow the search.
+    ///
+    /// # Returns
+    ///
+    /// Candidates ordered by descending confidence.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Provider`] when the request fails or the
+    /// response cannot be read.
+    async fn search(
+        &self,
+        title: &str,
+        author: Option<&str>,
+    ) -> Result<Vec<MetadataCandidate>, DomainError>;
+}
+
+/// Scores how well a candidate's title and authors match a query.
+///
+/// The title score is the overlap of the lowercased words of both titles
+/// (Jaccard index). When an author is queried, it counts for 30% and matches
+/// when any candidate author shares the queried author's last name.
+///
+/// # Returns
+///
+/// A score from `0.0` (no overlap) to `1.0` (same words, same author).
+pub fn match_confidence(
+    query_title: &str,
+    query_author: Option<&str>,
+    title: &str,
+    authors: &[String],
+) -> f32 {
+    let query_words = title_words(query_title);
+    let words = title_words(title);
+    let union = query_words.union(&words).count();
+    let title_score = if union == 0 {
+        0.0
+    } else {
+        query_words.intersection(&words).count() as f32 / union as f32
+    };
+
+    let Some(surname) = query_author.and_then(last_name) else {
+        return title_score;
+    };
+    let author_score = if authors
+        .iter()
+        .filter_map(|author| last_name(author))
+        .any(|candidate| candidate == surname)
+    {
+        1.0
+    } else {
+        0.0
+    };
+    TITLE_WEIGHT * title_score + (1.0 - TITLE_WEIGHT) * author_score
+}
+
+/// Lowercased alphanumeric words of a title.
+fn title_words(title: &str) -> HashSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Lowercased last name of `"First Last"` or `"Last, First"`.
+fn last_name(name: &str) -> Option<String> {
+    let last = match name.split_once(',') {
+        Some((surname, _)) => surname.split_whitespace().last(),
+        None => name.split_whitespace().last(),
+    };
+    last.map(str::to_lowercase)
+}
@@ -0,0 +1,341 @@
+//! [`MetadataProvider`] backed by the Open Library JSON API.
+//!
+//! ISBN lookups use the Books API (`/api/books?jscmd=data`), which resolves
+//! author and publisher names in one request; title searches use
+//! `/search.json`. The base URL is configurable so tests can point the
+//! provider at a local server.
+
+use std::collections::HashMap;
+use std::env;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::application::provider::{MetadataCandidate, MetadataProvider, match_confidence};
+use crate::domain::error::DomainError;
+use crate::domain::models::contributor::ROLE_AUTHOR;
+use crate::infrastructure::file_handlers::{
+    BookMetadata, ParsedContributor, clean_description, normalize_language_tag, split_tag_list,
+};
+use crate::utils::isbn::normalize_isbn;
+
+/// Base URL used when `OPEN_LIBRARY_URL` is not set.
+pub const DEFAULT_OPEN_LIBRARY_URL: &str = "https://openlibrary.org";
+
+/// Cover image host for search results, which only carry a cover ID.
+const COVERS_URL: &str = "https://covers.openlibrary.org";
+
+/// Number of search results requested per title search.
+pub const SEARCH_LIMIT: usize = 10;
+
+/// Subjects kept as tags per candidate; Open Library lists dozens.
+const MAX_CANDIDATE_TAGS: usize = 10;
+
+/// Request timeout for every Open Library call.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Open Library metadata provider.
+pub struct OpenLibraryProvider {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl OpenLibraryProvider {
+    /// Creates a provider for the `OPEN_LIBRARY_URL` env var, falling back to
+    /// [`DEFAULT_OPEN_LIBRARY_URL`].
+    pub fn new() -> Self {
+        Self::with_base_url(
+            env::var("OPEN_LIBRARY_URL").unwrap_or_else(|_| DEFAULT_OPEN_LIBRARY_URL.to_string()),
+        )
+    }
+
+    /// Creates a provider that sends its requests to `base_url`.
+    ///
+    /// Should the HTTP client fail to build, an error is logged and a default
+    /// client, without the timeout and user agent, is used instead.
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("Stellaron/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_else(|e| {
+                log::error!("Failed to build the Open Library HTTP client: {}", e);
+                reqwest::Client::new()
+            });
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client,
+        }
+    }
+
+    /// Sends a GET request and decodes the JSON response.
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, DomainError> {
+        self.client
+            .get(format!("{}{}", self.base_url, path))
+            .query(query)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| DomainError::Provider(e.to_string()))?
+            .json::<T>()
+            .await
+            .map_err(|e| DomainError::Provider(e.to_string()))
+    }
+}
+
+impl Default for OpenLibraryProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for OpenLibraryProvider {
+    /// Returns `"open_library"`.
+    fn name(&self) -> &'static str {
+        "open_library"
+    }
+
+    /// Looks up an ISBN with the Books API.
+    async fn lookup_isbn(&self, isbn: &str) -> Result<Vec<MetadataCandidate>, DomainError> {
+        let bibkey = format!("ISBN:{}", isbn);
+        let mut records: HashMap<String, BooksApiRecord> = self
+            .get_json(
+                "/api/books",
+                &[
+                    ("bibkeys", bibkey.as_str()),
+                    ("format", "json"),
+                    ("jscmd", "data"),
+                ],
+            )
+            .await?;
+
+        Ok(records
+            .remove(&bibkey)
+            .map(|record| record.into_candidate(self.name(), isbn))
+            .into_iter()
+            .collect())
+    }
+
+    /// Searches `/search.json` and scores each result with
+    /// [`match_confidence`].
+    async fn search(
+        &self,
+        title: &str,
+        author: Option<&str>,
+    ) -> Result<Vec<MetadataCandidate>, DomainError> {
+        let limit = SEARCH_LIMIT.to_string();
+        let mut query = vec![("title", title), ("limit", limit.as_str())];
+        if let Some(author) = author {
+            query.push(("author", author));
+        }
+        let response: SearchResponse = self.get_json("/search.json", &query).await?;
+
+        let mut candidates: Vec<MetadataCandidate> = response
+            .docs
+            .into_iter()
+            .map(|doc| {
+                let confidence = match_confidence(title, author, &doc.title, &doc.author_name);
+                doc.into_candidate(self.name(), confidence)
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        Ok(candidates)
+    }
+}
+
+/// A `{ "name": ... }` entry of the Books API.
+#[derive(Deserialize)]
+struct NamedEntry {
+    name: String,
+}
+
+/// Free text that Open Library returns either as a string or as
+/// `{ "type": "/type/text", "value": ... }`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TextValue {
+    Plain(String),
+    Typed { value: String },
+}
+
+impl TextValue {
+    fn as_str(&self) -> &str {
+        match self {
+            TextValue::Plain(text) | TextValue::Typed { value: text } => text,
+        }
+    }
+}
+
+/// ISBNs listed under `identifiers` in a Books API record.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BooksApiIdentifiers {
+    isbn_13: Vec<String>,
+    isbn_10: Vec<String>,
+}
+
+/// Cover URLs of a Books API record.
+#[derive(Deserialize)]
+struct BooksApiCover {
+    large: Option<String>,
+    medium: Option<String>,
+}
+
+/// One edition returned by `/api/books?jscmd=data`.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct BooksApiRecord {
+    key: Option<String>,
+    title: String,
+    subtitle: Option<String>,
+    authors: Vec<NamedEntry>,
+    publishers: Vec<NamedEntry>,
+    publish_date: Option<String>,
+    number_of_pages: Option<i32>,
+    subjects: Vec<NamedEntry>,
+    identifiers: BooksApiIdentifiers,
+    notes: Option<TextValue>,
+    cover: Option<BooksApiCover>,
+}
+
+impl BooksApiRecord {
+    fn into_candidate(self, provider: &str, queried_isbn: &str) -> MetadataCandidate {
+        let title = match self.subtitle.as_deref().map(str::trim) {
+            Some(subtitle) if !subtitle.is_empty() => format!("{}: {}", self.title, subtitle),
+            _ => self.title,
+        };
+        let isbn = self
+            .identifiers
+            .isbn_13
+            .iter()
+            .chain(&self.identifiers.isbn_10)
+            .find_map(|value| normalize_isbn(value))
+            .or_else(|| normalize_isbn(queried_isbn));
+        let authors: Vec<String> = self.authors.into_iter().map(|a| a.name).collect();
+        let subjects: Vec<String> = self.subjects.into_iter().map(|s| s.name).collect();
+
+        MetadataCandidate {
+            provider: provider.to_string(),
+            source_id: self.key,
+            confidence: 1.0,
+            cover_url: self.cover.and_then(|cover| cover.large.or(cover.medium)),
+            metadata: candidate_metadata(CandidateFields {
+                title,
+                authors,
+                publishers: self.publishers.into_iter().map(|p| p.name).collect(),
+                published_date: self.publish_date,
+                isbn,
+                subjects,
+                description: self
+                    .notes
+                    .as_ref()
+                    .map(TextValue::as_str)
+                    .and_then(clean_description),
+                language: None,
+                page_count: self.number_of_pages,
+            }),
+        }
+    }
+}
+
+/// Response of `/search.json`.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct SearchResponse {
+    docs: Vec<SearchDoc>,
+}
+
+/// One work returned by `/search.json`.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct SearchDoc {
+    key: Option<String>,
+    title: String,
+    author_name: Vec<String>,
+    first_publish_year: Option<i32>,
+    publisher: Vec<String>,
+    isbn: Vec<String>,
+    language: Vec<String>,
+    subject: Vec<String>,
+    number_of_pages_median: Option<i32>,
+    cover_i: Option<i64>,
+}
+
+impl SearchDoc {
+    fn into_candidate(self, provider: &str, confidence: f32) -> MetadataCandidate {
+        MetadataCandidate {
+            provider: provider.to_string(),
+            source_id: self.key,
+            confidence,
+            cover_url: self
+                .cover_i
+                .map(|id| format!("{}/b/id/{}-L.jpg", COVERS_URL, id)),
+            metadata: candidate_metadata(CandidateFields {
+                title: self.title,
+                authors: self.author_name,
+                publishers: self.publisher.into_iter().take(1).collect(),
+                published_date: self.first_publish_year.map(|year| year.to_string()),
+                isbn: self.isbn.iter().find_map(|value| normalize_isbn(value)),
+                subjects: self.subject,
+                description: None,
+                language: self
+                    .language
+                    .first()
+                    .and_then(|code| normalize_language_tag(code)),
+                page_count: self.number_of_pages_median,
+            }),
+        }
+    }
+}
+
+/// The values both Open Library endpoints provide.
+struct CandidateFields {
+    title: String,
+    authors: Vec<String>,
+    publishers: Vec<String>,
+    published_date: Option<String>,
+    isbn: Option<String>,
+    subjects: Vec<String>,
+    description: Option<String>,
+    language: Option<String>,
+    page_count: Option<i32>,
+}
+
+/// Builds a candidate's [`BookMetadata`]. Unlike parsed files, a candidate
+/// without authors has no contributors rather than an "Unknown Author".
+fn candidate_metadata(fields: CandidateFields) -> BookMetadata {
+    let contributors: Vec<ParsedContributor> = fields
+        .authors
+        .iter()
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| ParsedContributor::new(name, ROLE_AUTHOR))
+        .collect();
+    let mut tags = split_tag_list(&fields.subjects);
+    tags.truncate(MAX_CANDIDATE_TAGS);
+
+    BookMetadata {
+        title: fields.title.trim().to_string(),
+        authors: contributors.iter().map(|c| c.name.clone()).collect(),
+        contributors,
+        published_date: fields.published_date,
+        publishers: fields.publishers,
+        isbn: fields.isbn,
+        series: None,
+        series_index: None,
+        tags,
+        description: fields.description,
+        language: fields.language,
+        word_count: None,
+        page_count: fields.page_count.filter(|pages| *pages > 0),
+        file_path: String::new(),
+        cover_data: None,
+        checksum: String::new(),
+    }
+}
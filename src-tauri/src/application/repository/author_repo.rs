@@ -4,6 +4,7 @@ use diesel_async::{AsyncConnection, RunQueryDsl};
 
 use crate::domain::error::DomainError;
 use crate::domain::models::author::Author;
+use crate::domain::models::contributor::Contributor;
 use crate::domain::repository::AuthorRepository;
 use crate::infrastructure::database::database::{connect_from_pool, lock_db};
 use crate::infrastructure::database::models::author::{AuthorRow, NewAuthorRow};
@@ -27,7 +28,13 @@ impl Default for AuthorRepoImpl {
 #[async_trait]
 impl AuthorRepository for AuthorRepoImpl {
     /// Finds an existing author by name, or inserts a new one and returns it.
-    async fn find_or_create(&self, author_name: &str) -> Result<Author, DomainError> {
+    ///
+    /// Fills in `sort_name` on an existing author that has none yet.
+    async fn find_or_create(
+        &self,
+        author_name: &str,
+        sort_name: Option<&str>,
+    ) -> Result<Author, DomainError> {
         let _db_lock = lock_db();
         let mut conn = connect_from_pool().await?;
 
@@ -39,10 +46,24 @@ impl AuthorRepository for AuthorRepoImpl {
             .await?;
 
         match existing_rows.into_iter().next() {
-            Some(row) => Ok(Author::from(row)),
+            Some(mut row) => {
+                if row.sort_name.is_none()
+                    && let (Some(id), Some(sort_name)) = (row.author_id, sort_name)
+                {
+                    diesel::update(authors::table.filter(authors::author_id.eq(id)))
+                        .set(authors::sort_name.eq(sort_name))
+                        .execute(&mut conn)
+                        .await?;
+                    row.sort_name = Some(sort_name.to_string());
+                }
+                Ok(Author::from(row))
+            }
             None => {
                 // Insert new author
-                let new_row = NewAuthorRow { name: author_name };
+                let new_row = NewAuthorRow {
+                    name: author_name,
+                    sort_name,
+                };
                 
                 conn.transaction(async |connection| {
                     diesel::insert_into(authors::table)
@@ -65,17 +86,37 @@ impl AuthorRepository for AuthorRepoImpl {
         }
     }
 
-    /// Returns all authors linked to the given book via `book_authors`.
-    async fn get_authors_by_book(&self, find_book_id: i32) -> Result<Vec<Author>, DomainError> {
+    /// Returns all contributors linked to the given book via `book_authors`,
+    /// ordered by position.
+    async fn get_contributors_by_book(
+        &self,
+        find_book_id: i32,
+    ) -> Result<Vec<Contributor>, DomainError> {
         let mut conn = connect_from_pool().await?;
 
         let rows = book_authors::table
             .inner_join(authors::table)
             .filter(book_authors::book_id.eq(find_book_id))
-            .select((authors::author_id, authors::name))
-            .load::<AuthorRow>(&mut conn)
+            .order(book_authors::position.asc())
+            .select((
+                book_authors::author_id,
+                authors::name,
+                authors::sort_name,
+                book_authors::role,
+                book_authors::position,
+            ))
+            .load::<(i32, String, Option<String>, String, i32)>(&mut conn)
             .await?;
 
-        Ok(rows.into_iter().map(Author::from).collect())
+        Ok(rows
+            .into_iter()
+            .map(|(author_id, name, sort_name, role, position)| Contributor {
+                author_id,
+                name,
+                sort_name,
+                role,
+                position,
+            })
+            .collect())
     }
 }
@@ -1,10 +1,11 @@
 use async_trait::async_trait;
 
 use crate::domain::error::DomainError;
-use crate::domain::repository::BookAuthorRepository;
+use crate::domain::repository::{BookAuthorRepository, NewContributorLink};
 use crate::infrastructure::database::database::{connect_from_pool, lock_db};
 use crate::infrastructure::database::models::book_author::BookAuthorRow;
 use crate::infrastructure::database::models::schema::book_authors;
+use diesel::prelude::*;
 use diesel_async::{AsyncConnection, RunQueryDsl};
 
 /// Diesel-backed implementation of [`BookAuthorRepository`].
@@ -24,14 +25,22 @@ impl Default for BookAuthorRepoImpl {
 
 #[async_trait]
 impl BookAuthorRepository for BookAuthorRepoImpl {
-    /// Creates a book-author link in the `book_authors` join table.
-    async fn link(&self, find_book_id: i32, find_author_id: i32) -> Result<(), DomainError> {
+    /// Creates a book-contributor link in the `book_authors` join table.
+    async fn link(
+        &self,
+        find_book_id: i32,
+        find_author_id: i32,
+        role: &str,
+        position: i32,
+    ) -> Result<(), DomainError> {
         let _db_lock = lock_db();
         let mut conn = connect_from_pool().await?;
 
         let row = BookAuthorRow {
             book_id: find_book_id,
             author_id: find_author_id,
+            role: role.to_string(),
+            position,
         };
 
         conn.transaction(async |connection| {
@@ -45,4 +54,41 @@ impl BookAuthorRepository for BookAuthorRepoImpl {
 
         Ok(())
     }
+
+    /// Deletes a book's contributor links and inserts the given ones.
+    async fn replace_all(
+        &self,
+        find_book_id: i32,
+        links: &[NewContributorLink],
+    ) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        let rows: Vec<BookAuthorRow> = links
+            .iter()
+            .enumerate()
+            .map(|(position, link)| BookAuthorRow {
+                book_id: find_book_id,
+                author_id: link.author_id,
+                role: link.role.clone(),
+                position: position as i32,
+            })
+            .collect();
+
+        conn.transaction(async |connection| {
+            diesel::delete(book_authors::table.filter(book_authors::book_id.eq(find_book_id)))
+                .execute(connection)
+                .await?;
+            for row in &rows {
+                diesel::insert_or_ignore_into(book_authors::table)
+                    .values(row)
+                    .execute(connection)
+                    .await?;
+            }
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
 }
@@ -1,16 +1,66 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
+use diesel::dsl::sql;
 use diesel::prelude::*;
 use diesel::sql_query;
-use diesel::sql_types::Integer;
+use diesel::sql_types::{Integer, Nullable, Text};
+use diesel::sqlite::{Sqlite, SqliteConnection};
+use diesel_async::sync_connection_wrapper::SyncConnectionWrapper;
 use diesel_async::{AsyncConnection, RunQueryDsl};
 
 use crate::domain::error::DomainError;
-use crate::domain::models::book::Book;
-use crate::domain::repository::{BookRepository, NewBook, UpdateBook};
+use crate::domain::models::book::{Book, HydratedBook};
+use crate::domain::models::contributor::{Contributor, ROLE_AUTHOR};
+use crate::domain::repository::{
+    AuthorCredit, BookFilter, BookListQuery, BookPatch, BookRepository, BookSort, NewBook,
+    NewContributorLink, UpdateBook,
+};
 use crate::infrastructure::database::database::{connect_from_pool, lock_db};
+use crate::infrastructure::database::models::author::NewAuthorRow;
 use crate::infrastructure::database::models::book::{BookRow, NewBookRow, UpdateBookRow};
 use crate::infrastructure::database::models::book_author::BookAuthorRow;
-use crate::infrastructure::database::models::schema::{book_authors, books};
+use crate::infrastructure::database::models::schema::{
+    authors, book_authors, book_tags, books, publishers, reading_progress, series, tags,
+};
+use crate::infrastructure::database::models::tag::BookTagRow;
+
+/// Sort name of a book's first credited author, for sorting by author.
+const FIRST_AUTHOR_SQL: &str = "(SELECT COALESCE(a.sort_name, a.name) FROM book_authors ba \
+     JOIN authors a ON a.author_id = ba.author_id \
+     WHERE ba.book_id = books.book_id AND ba.role = 'author' \
+     ORDER BY ba.position LIMIT 1) COLLATE NOCASE";
+
+/// The books matching a [`BookFilter`], shared by listing and counting so
+/// both apply the same clauses.
+fn filtered_books(filter: &BookFilter) -> books::BoxedQuery<'static, Sqlite> {
+    let mut query = books::table.into_boxed();
+    if filter.favorites_only {
+        query = query.filter(books::is_favorite.eq(true));
+    }
+    if let Some(min_rating) = filter.min_rating {
+        query = query.filter(books::rating.ge(min_rating));
+    }
+    if let Some(file_type) = &filter.file_type {
+        query = query.filter(books::file_type.eq(file_type.clone()));
+    }
+    if let Some(ids) = &filter.book_ids {
+        query = query.filter(books::book_id.eq_any(ids.clone()));
+    }
+    if let Some(series_id) = filter.series_id {
+        query = query.filter(books::series_id.eq(series_id));
+    }
+    if let Some(tag_id) = filter.tag_id {
+        query = query.filter(
+            books::book_id.eq_any(
+                book_tags::table
+                    .filter(book_tags::tag_id.eq(tag_id))
+                    .select(book_tags::book_id.nullable()),
+            ),
+        );
+    }
+    query
+}
 
 /// Helper for retrieving the last inserted row ID via `last_insert_rowid()`.
 #[derive(QueryableByName)]
@@ -35,13 +85,15 @@ impl BookRepoImpl {
 
     /// Imports a new book with author and publisher links in a single transaction.
     ///
-    /// Inserts the book row, retrieves its generated ID, and links all authors
-    /// via the `book_authors` join table. The publisher link is set in a
+    /// Inserts the book row, retrieves its generated ID, and links all
+    /// contributors via the `book_authors` join table in slice order, and tags via
+    /// `book_tags`. The publisher link is set in a
     /// separate update after the transaction (since it requires the book ID).
     pub async fn import_with_links(
         &self,
         book: NewBook,
-        author_ids: &[(i32, String)],
+        contributors: &[NewContributorLink],
+        tag_ids: &[i32],
         publisher_id: Option<i32>,
     ) -> Result<Book, DomainError> {
         let _db_lock = lock_db();
@@ -56,6 +108,12 @@ impl BookRepoImpl {
             file_path: &book.file_path,
             cover_image_path: book.cover_image_path.as_deref(),
             checksum: book.checksum.as_deref(),
+            series_id: book.series_id,
+            series_index: book.series_index,
+            description: book.description.as_deref(),
+            language: book.language.as_deref(),
+            word_count: book.word_count,
+            page_count: book.page_count,
         };
         
         let book_id = conn.transaction(async |connection| {
@@ -70,11 +128,13 @@ impl BookRepoImpl {
                 .await?;
             let book_id = result.book_id;
 
-            // Link all authors in the same transaction
-            for (author_id, _author_name) in author_ids {
+            // Link all contributors in the same transaction
+            for (position, contributor) in contributors.iter().enumerate() {
                 let link = BookAuthorRow {
                     book_id,
-                    author_id: *author_id,
+                    author_id: contributor.author_id,
+                    role: contributor.role.clone(),
+                    position: position as i32,
                 };
                 diesel::insert_into(book_authors::table)
                     .values(&link)
@@ -82,6 +142,13 @@ impl BookRepoImpl {
                     .await?;
             }
 
+            for &tag_id in tag_ids {
+                diesel::insert_or_ignore_into(book_tags::table)
+                    .values(&BookTagRow { book_id, tag_id })
+                    .execute(connection)
+                    .await?;
+            }
+
             Ok::<i32, diesel::result::Error>(book_id)
         })
             .await?;
@@ -119,6 +186,124 @@ impl BookRepository for BookRepoImpl {
         Ok(rows.into_iter().map(Book::from).collect())
     }
 
+    /// Loads books left-joined to publishers, series and reading progress in
+    /// one query, then the contributors and tags of the whole page in two more.
+    async fn find_hydrated(&self, list: &BookListQuery) -> Result<Vec<HydratedBook>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+        let filter = &list.filter;
+
+        let mut query = books::table
+            .left_join(publishers::table)
+            .left_join(series::table)
+            .left_join(reading_progress::table)
+            .select((
+                BookRow::as_select(),
+                publishers::name.nullable(),
+                series::name.nullable(),
+                reading_progress::progress_percentage.nullable(),
+                reading_progress::last_read_at.nullable(),
+            ))
+            .filter(books::book_id.eq_any(filtered_books(filter).select(books::book_id)))
+            .into_boxed();
+
+        query = match (list.sort, list.descending) {
+            (BookSort::Title, false) => query.order(sql::<Text>("books.title COLLATE NOCASE").asc()),
+            (BookSort::Title, true) => query.order(sql::<Text>("books.title COLLATE NOCASE").desc()),
+            (BookSort::Author, false) => query.order(sql::<Nullable<Text>>(FIRST_AUTHOR_SQL).asc()),
+            (BookSort::Author, true) => query.order(sql::<Nullable<Text>>(FIRST_AUTHOR_SQL).desc()),
+            (BookSort::AddedAt, false) => query.order(books::added_at.asc()),
+            (BookSort::AddedAt, true) => query.order(books::added_at.desc()),
+            (BookSort::LastReadAt, false) => query.order(reading_progress::last_read_at.asc()),
+            (BookSort::LastReadAt, true) => query.order(reading_progress::last_read_at.desc()),
+            (BookSort::Series, false) => query
+                .order(sql::<Nullable<Text>>("series.name COLLATE NOCASE").asc())
+                .then_order_by(sql::<Integer>("books.series_index IS NULL").asc())
+                .then_order_by(books::series_index.asc()),
+            (BookSort::Series, true) => query
+                .order(sql::<Nullable<Text>>("series.name COLLATE NOCASE").desc())
+                .then_order_by(sql::<Integer>("books.series_index IS NULL").asc())
+                .then_order_by(books::series_index.desc()),
+        };
+        query = query.then_order_by(books::book_id.asc()).offset(list.offset.max(0));
+        if let Some(limit) = list.limit {
+            query = query.limit(limit);
+        }
+
+        let rows = query
+            .load::<(
+                BookRow,
+                Option<String>,
+                Option<String>,
+                Option<f32>,
+                Option<String>,
+            )>(&mut conn)
+            .await?;
+
+        let ids: Vec<i32> = rows.iter().filter_map(|(row, ..)| row.book_id).collect();
+        let mut contributors_by_book: HashMap<i32, Vec<Contributor>> = HashMap::new();
+        for (book_id, author_id, name, sort_name, role, position) in book_authors::table
+            .inner_join(authors::table)
+            .filter(book_authors::book_id.eq_any(&ids))
+            .order((book_authors::book_id.asc(), book_authors::position.asc()))
+            .select((
+                book_authors::book_id,
+                book_authors::author_id,
+                authors::name,
+                authors::sort_name,
+                book_authors::role,
+                book_authors::position,
+            ))
+            .load::<(i32, i32, String, Option<String>, String, i32)>(&mut conn)
+            .await?
+        {
+            contributors_by_book.entry(book_id).or_default().push(Contributor {
+                author_id,
+                name,
+                sort_name,
+                role,
+                position,
+            });
+        }
+
+        let mut tags_by_book: HashMap<i32, Vec<String>> = HashMap::new();
+        for (book_id, name) in book_tags::table
+            .inner_join(tags::table)
+            .filter(book_tags::book_id.eq_any(&ids))
+            .order(sql::<Text>("tags.name COLLATE NOCASE").asc())
+            .select((book_tags::book_id, tags::name))
+            .load::<(i32, String)>(&mut conn)
+            .await?
+        {
+            tags_by_book.entry(book_id).or_default().push(name);
+        }
+
+        Ok(rows
+            .into_iter()
+            .map(|(row, publisher, series, progress_percentage, last_read_at)| {
+                let book = Book::from(row);
+                HydratedBook {
+                    contributors: contributors_by_book.remove(&book.id).unwrap_or_default(),
+                    tags: tags_by_book.remove(&book.id).unwrap_or_default(),
+                    book,
+                    publisher,
+                    series,
+                    progress_percentage,
+                    last_read_at,
+                }
+            })
+            .collect())
+    }
+
+    /// Counts the books matching the filter.
+    async fn count_filtered(&self, filter: &BookFilter) -> Result<i64, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        Ok(filtered_books(filter)
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?)
+    }
+
     /// Returns a book by ID, or `None` if not found.
     async fn find_by_id(&self, find_id: i32) -> Result<Option<Book>, DomainError> {
         let mut conn = connect_from_pool().await?;
@@ -148,6 +333,12 @@ impl BookRepository for BookRepoImpl {
             file_path: &book.file_path,
             cover_image_path: book.cover_image_path.as_deref(),
             checksum: book.checksum.as_deref(),
+            series_id: book.series_id,
+            series_index: book.series_index,
+            description: book.description.as_deref(),
+            language: book.language.as_deref(),
+            word_count: book.word_count,
+            page_count: book.page_count,
         };
 
         let id = conn
@@ -174,13 +365,22 @@ impl BookRepository for BookRepoImpl {
 
         let update_row = UpdateBookRow {
             title: book.title.as_deref(),
-            published_date: book.published_date.as_deref(),
-            publisher_id: book.publisher_id,
-            isbn: book.isbn.as_deref(),
+            published_date: book.published_date.as_deref().map(Some),
+            publisher_id: book.publisher_id.map(Some),
+            isbn: book.isbn.as_deref().map(Some),
             file_type: book.file_type.as_deref(),
             file_path: book.file_path.as_deref(),
             cover_image_path: book.cover_image_path.as_deref(),
             checksum: book.checksum.as_deref(),
+            is_favorite: book.is_favorite,
+            rating: book.rating,
+            review: book.review.as_ref().map(Option::as_deref),
+            series_id: book.series_id,
+            series_index: book.series_index,
+            description: book.description.as_ref().map(Option::as_deref),
+            language: book.language.as_ref().map(Option::as_deref),
+            page_count: book.page_count,
+            file_status: book.file_status.as_deref(),
         };
 
         conn.transaction(async |connection| {
@@ -226,6 +426,18 @@ impl BookRepository for BookRepoImpl {
         }
     }
 
+    /// Returns the book stored at the given file path, or `None`.
+    async fn find_by_file_path(&self, path: &str) -> Result<Option<Book>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = books::dsl::books
+            .filter(books::file_path.eq(path))
+            .limit(1)
+            .load::<BookRow>(&mut conn)
+            .await?;
+        Ok(rows.into_iter().next().map(Book::from))
+    }
+
     /// Searches books by title using a LIKE query (case-insensitive).
     async fn search_by_title(&self, title_query: &str) -> Result<Vec<Book>, DomainError> {
         let mut conn = connect_from_pool().await?;
@@ -241,9 +453,271 @@ impl BookRepository for BookRepoImpl {
     async fn import_with_links(
         &self,
         book: NewBook,
-        author_ids: &[(i32, String)],
+        contributors: &[NewContributorLink],
+        tag_ids: &[i32],
         publisher_id: Option<i32>,
     ) -> Result<Book, DomainError> {
-        self.import_with_links(book, author_ids, publisher_id).await
+        self.import_with_links(book, contributors, tag_ids, publisher_id)
+            .await
+    }
+
+    /// Applies a metadata patch, creating missing authors, publishers, series
+    /// and tags, and deletes the authors and publishers it leaves unused.
+    async fn update_with_links(&self, find_id: i32, patch: BookPatch) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            let previous_publisher = books::table
+                .filter(books::book_id.eq(find_id))
+                .select(books::publisher_id)
+                .first::<Option<i32>>(connection)
+                .await?;
+            let previous_authors = book_authors::table
+                .filter(book_authors::book_id.eq(find_id))
+                .select(book_authors::author_id)
+                .load::<i32>(connection)
+                .await?;
+
+            let publisher_id = match &patch.publisher {
+                Some(Some(name)) => Some(Some(publisher_id_for(connection, name).await?)),
+                Some(None) => Some(None),
+                None => None,
+            };
+            let series_id = match &patch.series {
+                Some(Some(name)) => Some(Some(series_id_for(connection, name).await?)),
+                Some(None) => Some(None),
+                None => None,
+            };
+            let series_index = match series_id {
+                Some(None) => Some(None),
+                _ => patch.series_index,
+            };
+
+            let update_row = UpdateBookRow {
+                title: patch.title.as_deref(),
+                published_date: patch.published_date.as_ref().map(Option::as_deref),
+                publisher_id,
+                isbn: patch.isbn.as_ref().map(Option::as_deref),
+                file_type: None,
+                file_path: None,
+                cover_image_path: None,
+                checksum: None,
+                is_favorite: None,
+                rating: None,
+                review: None,
+                series_id,
+                series_index,
+                description: patch.description.as_ref().map(Option::as_deref),
+                language: None,
+                page_count: None,
+                file_status: None,
+            };
+            // Diesel rejects an UPDATE without any column to set
+            let changes_book_row = update_row.title.is_some()
+                || update_row.published_date.is_some()
+                || update_row.publisher_id.is_some()
+                || update_row.isbn.is_some()
+                || update_row.series_id.is_some()
+                || update_row.series_index.is_some()
+                || update_row.description.is_some();
+            if changes_book_row {
+                diesel::update(books::table.filter(books::book_id.eq(find_id)))
+                    .set(&update_row)
+                    .execute(connection)
+                    .await?;
+            }
+
+            if let Some(credits) = &patch.authors {
+                let mut author_ids: Vec<i32> = Vec::new();
+                for credit in credits {
+                    let author_id = author_id_for(connection, credit).await?;
+                    if !author_ids.contains(&author_id) {
+                        author_ids.push(author_id);
+                    }
+                }
+                let others = book_authors::table
+                    .filter(book_authors::book_id.eq(find_id))
+                    .filter(book_authors::role.ne(ROLE_AUTHOR))
+                    .order(book_authors::position.asc())
+                    .load::<BookAuthorRow>(connection)
+                    .await?;
+
+                diesel::delete(book_authors::table.filter(book_authors::book_id.eq(find_id)))
+                    .execute(connection)
+                    .await?;
+                let links = author_ids
+                    .into_iter()
+                    .map(|author_id| (author_id, ROLE_AUTHOR.to_string()))
+                    .chain(others.into_iter().map(|row| (row.author_id, row.role)));
+                for (position, (author_id, role)) in links.enumerate() {
+                    let link = BookAuthorRow {
+                        book_id: find_id,
+                        author_id,
+                        role,
+                        position: position as i32,
+                    };
+                    diesel::insert_or_ignore_into(book_authors::table)
+                        .values(&link)
+                        .execute(connection)
+                        .await?;
+                }
+            }
+
+            if let Some(tag_names) = &patch.tags {
+                diesel::delete(book_tags::table.filter(book_tags::book_id.eq(find_id)))
+                    .execute(connection)
+                    .await?;
+                for name in tag_names {
+                    let tag_id = tag_id_for(connection, name).await?;
+                    diesel::insert_or_ignore_into(book_tags::table)
+                        .values(&BookTagRow {
+                            book_id: find_id,
+                            tag_id,
+                        })
+                        .execute(connection)
+                        .await?;
+                }
+            }
+
+            // Garbage-collect the people and publisher the edit unlinked
+            let still_credited = book_authors::table
+                .filter(book_authors::author_id.eq_any(&previous_authors))
+                .select(book_authors::author_id)
+                .load::<i32>(connection)
+                .await?;
+            let orphaned_authors: Vec<i32> = previous_authors
+                .into_iter()
+                .filter(|author_id| !still_credited.contains(author_id))
+                .collect();
+            if !orphaned_authors.is_empty() {
+                diesel::delete(authors::table.filter(authors::author_id.eq_any(orphaned_authors)))
+                    .execute(connection)
+                    .await?;
+            }
+
+            if let Some(old_publisher_id) = previous_publisher {
+                let remaining = books::table
+                    .filter(books::publisher_id.eq(old_publisher_id))
+                    .count()
+                    .get_result::<i64>(connection)
+                    .await?;
+                if remaining == 0 {
+                    diesel::delete(
+                        publishers::table.filter(publishers::publisher_id.eq(old_publisher_id)),
+                    )
+                    .execute(connection)
+                    .await?;
+                }
+            }
+
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Returns the ID of the author named by `credit`, creating the author if
+/// missing.
+async fn author_id_for(
+    connection: &mut SyncConnectionWrapper<SqliteConnection>,
+    credit: &AuthorCredit,
+) -> QueryResult<i32> {
+    let existing = authors::table
+        .filter(authors::name.eq(&credit.name))
+        .select(authors::author_id)
+        .first::<Option<i32>>(connection)
+        .await
+        .optional()?
+        .flatten();
+    if let Some(author_id) = existing {
+        return Ok(author_id);
+    }
+
+    diesel::insert_into(authors::table)
+        .values(&NewAuthorRow {
+            name: &credit.name,
+            sort_name: credit.sort_name.as_deref(),
+        })
+        .execute(connection)
+        .await?;
+    last_insert_id(connection).await
+}
+
+/// Returns the ID of the publisher with the given name, creating it if
+/// missing.
+async fn publisher_id_for(
+    connection: &mut SyncConnectionWrapper<SqliteConnection>,
+    name: &str,
+) -> QueryResult<i32> {
+    let existing = publishers::table
+        .filter(publishers::name.eq(name))
+        .select(publishers::publisher_id)
+        .first::<Option<i32>>(connection)
+        .await
+        .optional()?
+        .flatten();
+    if let Some(publisher_id) = existing {
+        return Ok(publisher_id);
     }
+
+    diesel::insert_into(publishers::table)
+        .values(publishers::name.eq(name))
+        .execute(connection)
+        .await?;
+    last_insert_id(connection).await
+}
+
+/// Returns the ID of the series with the given name (matched
+/// case-insensitively), creating it if missing.
+async fn series_id_for(
+    connection: &mut SyncConnectionWrapper<SqliteConnection>,
+    name: &str,
+) -> QueryResult<i32> {
+    let existing = series::table
+        .filter(series::name.eq(name))
+        .select(series::series_id)
+        .first::<Option<i32>>(connection)
+        .await
+        .optional()?
+        .flatten();
+    if let Some(series_id) = existing {
+        return Ok(series_id);
+    }
+
+    diesel::insert_into(series::table)
+        .values(series::name.eq(name))
+        .execute(connection)
+        .await?;
+    last_insert_id(connection).await
+}
+
+/// Returns the ID of the tag with the given name (matched
+/// case-insensitively), creating it if missing.
+async fn tag_id_for(
+    connection: &mut SyncConnectionWrapper<SqliteConnection>,
+    name: &str,
+) -> QueryResult<i32> {
+    diesel::insert_or_ignore_into(tags::table)
+        .values(tags::name.eq(name))
+        .execute(connection)
+        .await?;
+    tags::table
+        .filter(tags::name.eq(name))
+        .select(tags::tag_id)
+        .first::<Option<i32>>(connection)
+        .await?
+        .ok_or(diesel::result::Error::NotFound)
+}
+
+/// Returns the row ID generated by the connection's last insert.
+async fn last_insert_id(
+    connection: &mut SyncConnectionWrapper<SqliteConnection>,
+) -> QueryResult<i32> {
+    Ok(sql_query("SELECT last_insert_rowid() as book_id")
+        .get_result::<LastInsertRow>(connection)
+        .await?
+        .book_id)
 }
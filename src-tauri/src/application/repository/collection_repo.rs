@@ -0,0 +1,298 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::dsl::max;
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::{Double, Integer, Text};
+use diesel::sqlite::Sqlite;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::domain::error::DomainError;
+use crate::domain::models::collection::Collection;
+use crate::domain::models::smart_collection::{
+    COLLECTION_KIND_MANUAL, COLLECTION_KIND_SMART, RuleGroup,
+};
+use crate::domain::repository::{CollectionRepository, NewCollection, UpdateCollection};
+use crate::infrastructure::database::database::{connect_from_pool, lock_db};
+use crate::infrastructure::database::models::collection::{
+    CollectionRow, NewCollectionBookRow, NewCollectionRow, UpdateCollectionRow,
+};
+use crate::infrastructure::database::models::schema::{collection_books, collections};
+use crate::infrastructure::database::smart_query::{RuleBind, SMART_QUERY_FROM, compile_rules};
+
+/// Helper for retrieving the last inserted row ID via `last_insert_rowid()`.
+#[derive(QueryableByName)]
+struct LastInsertRow {
+    #[diesel(sql_type = Integer)]
+    collection_id: i32,
+}
+
+/// Book ID returned by a compiled smart-collection query.
+#[derive(QueryableByName)]
+struct MatchingBookRow {
+    #[diesel(sql_type = Integer)]
+    book_id: i32,
+}
+
+/// Serializes smart-collection rules for the `collections.rules` column.
+fn rules_to_json(rules: &RuleGroup) -> Result<String, DomainError> {
+    serde_json::to_string(rules).map_err(|e| DomainError::InvalidInput(e.to_string()))
+}
+
+/// Diesel-backed implementation of [`CollectionRepository`].
+pub struct CollectionRepoImpl;
+
+impl CollectionRepoImpl {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CollectionRepoImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl CollectionRepository for CollectionRepoImpl {
+    /// Returns all collections ordered by name.
+    async fn find_all(&self) -> Result<Vec<Collection>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = collections::dsl::collections
+            .order(collections::name.asc())
+            .load::<CollectionRow>(&mut conn)
+            .await?;
+
+        Ok(rows.into_iter().map(Collection::from).collect())
+    }
+
+    /// Returns a collection by ID, or `None` if not found.
+    async fn find_by_id(&self, find_id: i32) -> Result<Option<Collection>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = collections::dsl::collections
+            .filter(collections::collection_id.eq(find_id))
+            .limit(1)
+            .load::<CollectionRow>(&mut conn)
+            .await?;
+        match rows.into_iter().next() {
+            Some(row) => Ok(Some(Collection::from(row))),
+            None => Ok(None),
+        }
+    }
+
+    /// Inserts a new collection and returns its generated ID.
+    async fn insert(&self, collection: NewCollection) -> Result<i32, DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+        let rules = collection.rules.as_ref().map(rules_to_json).transpose()?;
+
+        let new_row = NewCollectionRow {
+            name: &collection.name,
+            description: collection.description.as_deref(),
+            accent_color: collection.accent_color.as_deref(),
+            cover_image: collection.cover_image.as_deref(),
+            kind: if rules.is_some() {
+                COLLECTION_KIND_SMART
+            } else {
+                COLLECTION_KIND_MANUAL
+            },
+            rules: rules.as_deref(),
+        };
+
+        let id = conn
+            .transaction(async |connection| {
+                diesel::insert_into(collections::table)
+                    .values(&new_row)
+                    .execute(connection)
+                    .await?;
+
+                let result = sql_query("SELECT last_insert_rowid() as collection_id")
+                    .get_result::<LastInsertRow>(connection)
+                    .await?;
+                Ok::<i32, diesel::result::Error>(result.collection_id)
+            })
+            .await?;
+
+        Ok(id)
+    }
+
+    /// Applies a partial update and stamps `updated_at` with the current UTC time.
+    async fn update(&self, find_id: i32, collection: UpdateCollection) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+        let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let rules = collection.rules.as_ref().map(rules_to_json).transpose()?;
+
+        let update_row = UpdateCollectionRow {
+            name: collection.name.as_deref(),
+            description: collection.description.as_deref(),
+            accent_color: collection.accent_color.as_deref(),
+            cover_image: collection.cover_image.as_deref(),
+            rules: rules.as_deref(),
+            updated_at: Some(&now),
+        };
+
+        conn.transaction(async |connection| {
+            diesel::update(
+                collections::dsl::collections.filter(collections::collection_id.eq(find_id)),
+            )
+            .set(&update_row)
+            .execute(connection)
+            .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deletes a collection by ID. Cascade deletes remove its book links.
+    async fn delete(&self, find_id: i32) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            diesel::delete(
+                collections::dsl::collections.filter(collections::collection_id.eq(find_id)),
+            )
+            .execute(connection)
+            .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Appends a book after the collection's last position, ignoring duplicates.
+    async fn add_book(&self, find_collection_id: i32, add_book_id: i32) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            let last_position = collection_books::dsl::collection_books
+                .filter(collection_books::collection_id.eq(find_collection_id))
+                .select(max(collection_books::position))
+                .first::<Option<i32>>(connection)
+                .await?;
+
+            diesel::insert_or_ignore_into(collection_books::table)
+                .values(&NewCollectionBookRow {
+                    collection_id: find_collection_id,
+                    book_id: add_book_id,
+                    position: last_position.map_or(0, |p| p + 1),
+                })
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes a book from a collection.
+    async fn remove_book(
+        &self,
+        find_collection_id: i32,
+        remove_book_id: i32,
+    ) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            diesel::delete(
+                collection_books::dsl::collection_books
+                    .filter(collection_books::collection_id.eq(find_collection_id))
+                    .filter(collection_books::book_id.eq(remove_book_id)),
+            )
+            .execute(connection)
+            .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Rewrites the positions of a collection's books in a single transaction.
+    async fn reorder_books(
+        &self,
+        find_collection_id: i32,
+        book_ids: Vec<i32>,
+    ) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            let current = collection_books::dsl::collection_books
+                .filter(collection_books::collection_id.eq(find_collection_id))
+                .order(collection_books::position.asc())
+                .select(collection_books::book_id)
+                .load::<i32>(connection)
+                .await?;
+
+            let mut ordered: Vec<i32> = Vec::with_capacity(current.len());
+            for id in book_ids.iter().chain(current.iter()) {
+                if current.contains(id) && !ordered.contains(id) {
+                    ordered.push(*id);
+                }
+            }
+
+            for (position, id) in ordered.into_iter().enumerate() {
+                diesel::update(
+                    collection_books::dsl::collection_books
+                        .filter(collection_books::collection_id.eq(find_collection_id))
+                        .filter(collection_books::book_id.eq(id)),
+                )
+                .set(collection_books::position.eq(position as i32))
+                .execute(connection)
+                .await?;
+            }
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the IDs of a collection's books ordered by position.
+    async fn find_book_ids(&self, find_collection_id: i32) -> Result<Vec<i32>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let ids = collection_books::dsl::collection_books
+            .filter(collection_books::collection_id.eq(find_collection_id))
+            .order((collection_books::position.asc(), collection_books::added_at.asc()))
+            .select(collection_books::book_id)
+            .load::<i32>(&mut conn)
+            .await?;
+
+        Ok(ids)
+    }
+
+    /// Compiles the rules with [`compile_rules`] and runs them as a boxed
+    /// `sql_query` over `books`, `publishers` and `reading_progress`.
+    async fn find_book_ids_matching(&self, rules: &RuleGroup) -> Result<Vec<i32>, DomainError> {
+        let compiled = compile_rules(rules, Utc::now().naive_utc())?;
+        let mut conn = connect_from_pool().await?;
+
+        let mut query = sql_query(format!(
+            "SELECT b.book_id AS book_id {} WHERE {} ORDER BY b.title COLLATE NOCASE",
+            SMART_QUERY_FROM, compiled.sql
+        ))
+        .into_boxed::<Sqlite>();
+        for bind in compiled.binds {
+            query = match bind {
+                RuleBind::Text(value) => query.bind::<Text, _>(value),
+                RuleBind::Double(value) => query.bind::<Double, _>(value),
+            };
+        }
+
+        let rows = query.load::<MatchingBookRow>(&mut conn).await?;
+
+        Ok(rows.into_iter().map(|row| row.book_id).collect())
+    }
+}
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::domain::error::DomainError;
+use crate::domain::models::library_root::LibraryRoot;
+use crate::domain::repository::LibraryRootRepository;
+use crate::infrastructure::database::database::{connect_from_pool, lock_db};
+use crate::infrastructure::database::models::library_root::LibraryRootRow;
+use crate::infrastructure::database::models::schema::library_roots;
+
+/// Diesel-backed implementation of [`LibraryRootRepository`].
+pub struct LibraryRootRepoImpl;
+
+impl LibraryRootRepoImpl {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LibraryRootRepoImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LibraryRootRepository for LibraryRootRepoImpl {
+    /// Returns all library roots, ordered by path.
+    async fn find_all(&self) -> Result<Vec<LibraryRoot>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = library_roots::table
+            .order(library_roots::path.asc())
+            .load::<LibraryRootRow>(&mut conn)
+            .await?;
+        Ok(rows.into_iter().map(LibraryRoot::from).collect())
+    }
+
+    /// Returns the library root by ID, or `None`.
+    async fn find_by_id(&self, find_id: i32) -> Result<Option<LibraryRoot>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = library_roots::table
+            .filter(library_roots::root_id.eq(find_id))
+            .limit(1)
+            .load::<LibraryRootRow>(&mut conn)
+            .await?;
+        Ok(rows.into_iter().next().map(LibraryRoot::from))
+    }
+
+    /// Inserts a library root and reads it back.
+    async fn insert(&self, root_path: &str) -> Result<LibraryRoot, DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            diesel::insert_into(library_roots::table)
+                .values(library_roots::path.eq(root_path))
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        let rows = library_roots::table
+            .filter(library_roots::path.eq(root_path))
+            .limit(1)
+            .load::<LibraryRootRow>(&mut conn)
+            .await?;
+
+        rows.into_iter()
+            .next()
+            .map(LibraryRoot::from)
+            .ok_or_else(|| {
+                DomainError::Database("Failed to retrieve newly created library root".into())
+            })
+    }
+
+    /// Deletes a library root by ID.
+    async fn delete(&self, find_id: i32) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            diesel::delete(library_roots::table.filter(library_roots::root_id.eq(find_id)))
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::domain::error::DomainError;
+use crate::domain::models::library_settings::LibrarySettings;
+use crate::domain::repository::LibrarySettingsRepository;
+use crate::infrastructure::database::database::{connect_from_pool, lock_db};
+use crate::infrastructure::database::models::library_settings::{
+    LibrarySettingsRow, NewLibrarySettingsRow,
+};
+use crate::infrastructure::database::models::schema::library_settings;
+
+/// ID of the only row of the `library_settings` table.
+const SETTINGS_ROW_ID: i32 = 1;
+
+/// Diesel-backed implementation of [`LibrarySettingsRepository`].
+pub struct LibrarySettingsRepoImpl;
+
+impl LibrarySettingsRepoImpl {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LibrarySettingsRepoImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LibrarySettingsRepository for LibrarySettingsRepoImpl {
+    /// Returns the saved settings, or `None` if they were never saved.
+    async fn find(&self) -> Result<Option<LibrarySettings>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = library_settings::table
+            .filter(library_settings::setting_id.eq(SETTINGS_ROW_ID))
+            .limit(1)
+            .load::<LibrarySettingsRow>(&mut conn)
+            .await?;
+        Ok(rows.into_iter().next().map(LibrarySettings::from))
+    }
+
+    /// Inserts or replaces the settings row.
+    async fn save(&self, settings: &LibrarySettings) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            diesel::insert_into(library_settings::table)
+                .values(&NewLibrarySettingsRow {
+                    setting_id: SETTINGS_ROW_ID,
+                    library_dir: settings.library_dir.as_deref(),
+                    naming_template: &settings.naming_template,
+                    import_mode: &settings.import_mode,
+                })
+                .on_conflict(library_settings::setting_id)
+                .do_update()
+                .set((
+                    library_settings::library_dir.eq(&settings.library_dir),
+                    library_settings::naming_template.eq(&settings.naming_template),
+                    library_settings::import_mode.eq(&settings.import_mode),
+                ))
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+}
@@ -5,5 +5,11 @@ pub mod author_repo;
 pub mod book_author_repo;
 pub mod book_repo;
 pub mod bookmark_repo;
+pub mod collection_repo;
+pub mod library_root_repo;
+pub mod library_settings_repo;
 pub mod publisher_repo;
 pub mod reading_progress_repo;
+pub mod search_index_repo;
+pub mod series_repo;
+pub mod tag_repo;
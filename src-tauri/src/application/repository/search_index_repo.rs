@@ -0,0 +1,198 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::{Double, Integer, Nullable, Text};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::domain::error::DomainError;
+use crate::domain::models::search_index::{BookIndexStatus, FullTextMatch};
+use crate::domain::repository::{NewIndexedText, SearchIndexRepository};
+use crate::infrastructure::database::database::{connect_from_pool, lock_db};
+use crate::infrastructure::database::models::book_index_status::{
+    BookIndexStatusRow, NewBookIndexStatusRow,
+};
+use crate::infrastructure::database::models::schema::book_index_status;
+
+/// Row returned by the FTS5 search query.
+#[derive(QueryableByName)]
+struct FullTextMatchRow {
+    #[diesel(sql_type = Integer)]
+    book_id: i32,
+    #[diesel(sql_type = Nullable<Integer>)]
+    spine_index: Option<i32>,
+    #[diesel(sql_type = Nullable<Integer>)]
+    page: Option<i32>,
+    #[diesel(sql_type = Text)]
+    snippet: String,
+    #[diesel(sql_type = Double)]
+    rank: f64,
+}
+
+/// Diesel-backed implementation of [`SearchIndexRepository`] over the
+/// `book_text_fts` FTS5 table.
+pub struct SearchIndexRepoImpl;
+
+impl SearchIndexRepoImpl {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SearchIndexRepoImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SearchIndexRepository for SearchIndexRepoImpl {
+    /// Deletes the book's segments and inserts the new ones in one transaction.
+    async fn replace_book_text(
+        &self,
+        book_id: i32,
+        segments: Vec<NewIndexedText>,
+    ) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            sql_query("DELETE FROM book_text_fts WHERE book_id = ?")
+                .bind::<Integer, _>(book_id)
+                .execute(connection)
+                .await?;
+
+            for segment in &segments {
+                sql_query(
+                    "INSERT INTO book_text_fts (content, book_id, spine_index, page) \
+                     VALUES (?, ?, ?, ?)",
+                )
+                .bind::<Text, _>(&segment.content)
+                .bind::<Integer, _>(book_id)
+                .bind::<Nullable<Integer>, _>(segment.spine_index)
+                .bind::<Nullable<Integer>, _>(segment.page)
+                .execute(connection)
+                .await?;
+            }
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Empties `book_text_fts` and `book_index_status`.
+    async fn clear(&self) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            sql_query("DELETE FROM book_text_fts")
+                .execute(connection)
+                .await?;
+            diesel::delete(book_index_status::table)
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Runs an FTS5 `MATCH` query ranked by `bm25()`, with a `snippet()` of
+    /// each matching segment.
+    async fn search(&self, query: &str, limit: i32) -> Result<Vec<FullTextMatch>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = sql_query(
+            "SELECT book_id, spine_index, page, \
+                    snippet(book_text_fts, 0, char(2), char(3), '…', 16) AS snippet, \
+                    bm25(book_text_fts) AS rank \
+             FROM book_text_fts \
+             WHERE book_text_fts MATCH ? \
+             ORDER BY rank \
+             LIMIT ?",
+        )
+        .bind::<Text, _>(query)
+        .bind::<Integer, _>(limit)
+        .load::<FullTextMatchRow>(&mut conn)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| FullTextMatch {
+                book_id: row.book_id,
+                spine_index: row.spine_index,
+                page: row.page,
+                snippet: row.snippet,
+                rank: row.rank,
+            })
+            .collect())
+    }
+
+    /// Upserts the status row for a book, stamping `updated_at` with the
+    /// current UTC time.
+    async fn set_status(
+        &self,
+        book_id: i32,
+        status: &str,
+        segment_count: i32,
+        error: Option<&str>,
+    ) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+        let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+        conn.transaction(async |connection| {
+            diesel::insert_into(book_index_status::table)
+                .values(&NewBookIndexStatusRow {
+                    book_id,
+                    status,
+                    segment_count,
+                    error,
+                    updated_at: Some(&now),
+                })
+                .on_conflict(book_index_status::book_id)
+                .do_update()
+                .set((
+                    book_index_status::status.eq(status),
+                    book_index_status::segment_count.eq(segment_count),
+                    book_index_status::error.eq(error),
+                    book_index_status::updated_at.eq(&now),
+                ))
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the status row for a book, or `None` if it was never queued.
+    async fn find_status(&self, find_book_id: i32) -> Result<Option<BookIndexStatus>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = book_index_status::dsl::book_index_status
+            .filter(book_index_status::book_id.eq(find_book_id))
+            .limit(1)
+            .load::<BookIndexStatusRow>(&mut conn)
+            .await?;
+        match rows.into_iter().next() {
+            Some(row) => Ok(Some(BookIndexStatus::from(row))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns all status rows.
+    async fn find_all_status(&self) -> Result<Vec<BookIndexStatus>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = book_index_status::dsl::book_index_status
+            .load::<BookIndexStatusRow>(&mut conn)
+            .await?;
+
+        Ok(rows.into_iter().map(BookIndexStatus::from).collect())
+    }
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::domain::error::DomainError;
+use crate::domain::models::series::Series;
+use crate::domain::repository::SeriesRepository;
+use crate::infrastructure::database::database::{connect_from_pool, lock_db};
+use crate::infrastructure::database::models::schema::series;
+use crate::infrastructure::database::models::series::SeriesRow;
+
+/// Diesel-backed implementation of [`SeriesRepository`].
+pub struct SeriesRepoImpl;
+
+impl SeriesRepoImpl {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SeriesRepoImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SeriesRepository for SeriesRepoImpl {
+    /// Returns the series by ID, or `None`.
+    async fn find_by_id(&self, find_id: i32) -> Result<Option<Series>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = series::table
+            .filter(series::series_id.eq(find_id))
+            .limit(1)
+            .load::<SeriesRow>(&mut conn)
+            .await?;
+        Ok(rows.into_iter().next().map(Series::from))
+    }
+
+    /// Finds an existing series by name, or inserts a new one and returns it.
+    async fn find_or_create(&self, series_name: &str) -> Result<Series, DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        // `name` uses NOCASE collation, so this matches case-insensitively
+        let existing = series::table
+            .filter(series::name.eq(series_name))
+            .limit(1)
+            .load::<SeriesRow>(&mut conn)
+            .await?;
+
+        if let Some(row) = existing.into_iter().next() {
+            return Ok(Series::from(row));
+        }
+
+        conn.transaction(async |connection| {
+            diesel::insert_into(series::table)
+                .values(series::name.eq(series_name))
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        let rows = series::table
+            .filter(series::name.eq(series_name))
+            .limit(1)
+            .load::<SeriesRow>(&mut conn)
+            .await?;
+
+        rows.into_iter().next().map(Series::from).ok_or_else(|| {
+            DomainError::Database("Failed to retrieve newly created series".into())
+        })
+    }
+}
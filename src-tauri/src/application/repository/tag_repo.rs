@@ -0,0 +1,204 @@
+use async_trait::async_trait;
+use diesel::dsl::count;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+use crate::domain::error::DomainError;
+use crate::domain::models::tag::Tag;
+use crate::domain::repository::TagRepository;
+use crate::infrastructure::database::database::{connect_from_pool, lock_db};
+use crate::infrastructure::database::models::schema::{book_tags, tags};
+use crate::infrastructure::database::models::tag::{BookTagRow, TagRow};
+
+/// Diesel-backed implementation of [`TagRepository`].
+pub struct TagRepoImpl;
+
+impl TagRepoImpl {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TagRepoImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TagRepository for TagRepoImpl {
+    /// Returns all tags left-joined to `book_tags` and grouped for counting.
+    async fn find_all_with_counts(&self) -> Result<Vec<(Tag, i64)>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = tags::table
+            .left_join(book_tags::table)
+            .group_by((tags::tag_id, tags::name))
+            .select((TagRow::as_select(), count(book_tags::book_id.nullable())))
+            .order(sql::<Text>("tags.name COLLATE NOCASE").asc())
+            .load::<(TagRow, i64)>(&mut conn)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(row, book_count)| (Tag::from(row), book_count))
+            .collect())
+    }
+
+    /// Returns the tag by ID, or `None`.
+    async fn find_by_id(&self, find_id: i32) -> Result<Option<Tag>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = tags::table
+            .filter(tags::tag_id.eq(find_id))
+            .limit(1)
+            .load::<TagRow>(&mut conn)
+            .await?;
+        Ok(rows.into_iter().next().map(Tag::from))
+    }
+
+    /// Returns the tag by name, or `None`. `name` uses `NOCASE` collation.
+    async fn find_by_name(&self, find_name: &str) -> Result<Option<Tag>, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        let rows = tags::table
+            .filter(tags::name.eq(find_name))
+            .limit(1)
+            .load::<TagRow>(&mut conn)
+            .await?;
+        Ok(rows.into_iter().next().map(Tag::from))
+    }
+
+    /// Finds an existing tag by name, or inserts a new one and returns it.
+    async fn find_or_create(&self, tag_name: &str) -> Result<Tag, DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            diesel::insert_or_ignore_into(tags::table)
+                .values(tags::name.eq(tag_name))
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        let rows = tags::table
+            .filter(tags::name.eq(tag_name))
+            .limit(1)
+            .load::<TagRow>(&mut conn)
+            .await?;
+
+        rows.into_iter()
+            .next()
+            .map(Tag::from)
+            .ok_or_else(|| DomainError::Database("Failed to retrieve tag".into()))
+    }
+
+    /// Counts the `book_tags` rows of a tag.
+    async fn count_books(&self, find_tag_id: i32) -> Result<i64, DomainError> {
+        let mut conn = connect_from_pool().await?;
+
+        Ok(book_tags::table
+            .filter(book_tags::tag_id.eq(find_tag_id))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?)
+    }
+
+    /// Updates a tag's name.
+    async fn rename(&self, find_tag_id: i32, new_name: &str) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            diesel::update(tags::table.filter(tags::tag_id.eq(find_tag_id)))
+                .set(tags::name.eq(new_name))
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Re-links the source tag's books to the target, skipping books that
+    /// already carry it, then deletes the source; its links cascade.
+    async fn merge(&self, source_id: i32, target_id: i32) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            let book_ids = book_tags::table
+                .filter(book_tags::tag_id.eq(source_id))
+                .select(book_tags::book_id)
+                .load::<i32>(connection)
+                .await?;
+
+            for book_id in book_ids {
+                diesel::insert_or_ignore_into(book_tags::table)
+                    .values(&BookTagRow {
+                        book_id,
+                        tag_id: target_id,
+                    })
+                    .execute(connection)
+                    .await?;
+            }
+
+            diesel::delete(tags::table.filter(tags::tag_id.eq(source_id)))
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Inserts a `book_tags` link, ignoring an existing one.
+    async fn add_to_book(&self, add_book_id: i32, add_tag_id: i32) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            diesel::insert_or_ignore_into(book_tags::table)
+                .values(&BookTagRow {
+                    book_id: add_book_id,
+                    tag_id: add_tag_id,
+                })
+                .execute(connection)
+                .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deletes a `book_tags` link.
+    async fn remove_from_book(
+        &self,
+        remove_book_id: i32,
+        remove_tag_id: i32,
+    ) -> Result<(), DomainError> {
+        let _db_lock = lock_db();
+        let mut conn = connect_from_pool().await?;
+
+        conn.transaction(async |connection| {
+            diesel::delete(
+                book_tags::table
+                    .filter(book_tags::book_id.eq(remove_book_id))
+                    .filter(book_tags::tag_id.eq(remove_tag_id)),
+            )
+            .execute(connection)
+            .await?;
+            Ok::<(), diesel::result::Error>(())
+        })
+        .await?;
+
+        Ok(())
+    }
+}
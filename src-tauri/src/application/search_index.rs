@@ -0,0 +1,416 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::domain::dto::search_dto::{LibrarySearchHit, LibrarySearchResult};
+use crate::domain::error::DomainError;
+use crate::domain::models::search_index::{
+    BookIndexStatus, INDEX_STATUS_FAILED, INDEX_STATUS_INDEXED, INDEX_STATUS_INDEXING,
+    INDEX_STATUS_PENDING,
+};
+use crate::domain::repository::*;
+use crate::infrastructure::file_handlers::{BookTextSegment, epub_handler, mobi_handler, pdf_handler};
+
+/// Default maximum number of matching chapters/pages fetched per query.
+pub const DEFAULT_SEARCH_LIMIT: i32 = 200;
+
+/// Extracts the text of a book and stores it in the full-text index.
+///
+/// The book's status moves to `"indexing"` while its file is read, then to
+/// `"indexed"` with the number of stored segments, or to `"failed"` with the
+/// error message when extraction fails.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `book_repo` - Repository for looking up the book's file.
+/// * `search_index_repo` - Repository holding the full-text index.
+///
+/// # Returns
+///
+/// The number of chapters/pages indexed.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if no book has the given ID,
+/// [`DomainError::File`] if it has no file path or an unsupported type and
+/// [`DomainError::Parse`] if its text cannot be extracted, and
+/// [`DomainError::Database`] if the index cannot be written. Except for a
+/// missing book, the book's status is set to `"failed"` first; when even that
+/// fails, a warning is logged.
+pub async fn index_book(
+    book_id: i32,
+    book_repo: &Arc<dyn BookRepository>,
+    search_index_repo: &Arc<dyn SearchIndexRepository>,
+) -> Result<usize, DomainError> {
+    let book = book_repo
+        .find_by_id(book_id)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+
+    let result = store_book_text(
+        book_id,
+        book.file_path.as_deref(),
+        book.file_type.as_deref(),
+        search_index_repo,
+    )
+    .await;
+    if let Err(e) = &result
+        && let Err(status_error) = search_index_repo
+            .set_status(book_id, INDEX_STATUS_FAILED, 0, Some(&e.to_string()))
+            .await
+    {
+        log::warn!(
+            "Failed to record the index failure of book {}: {}",
+            book_id,
+            status_error
+        );
+    }
+    result
+}
+
+/// Marks a book as indexing, then extracts its text and replaces its stored
+/// segments.
+async fn store_book_text(
+    book_id: i32,
+    file_path: Option<&str>,
+    file_type: Option<&str>,
+    search_index_repo: &Arc<dyn SearchIndexRepository>,
+) -> Result<usize, DomainError> {
+    search_index_repo
+        .set_status(book_id, INDEX_STATUS_INDEXING, 0, None)
+        .await?;
+
+    let segments = extract_book_text(file_path, file_type).await?;
+
+    let count = segments.len();
+    let texts = segments
+        .into_iter()
+        .map(|segment| NewIndexedText {
+            spine_index: segment.spine_index.map(|i| i as i32),
+            page: segment.page.map(|p| p as i32),
+            content: segment.text,
+        })
+        .collect();
+    search_index_repo.replace_book_text(book_id, texts).await?;
+    search_index_repo
+        .set_status(book_id, INDEX_STATUS_INDEXED, count as i32, None)
+        .await?;
+
+    Ok(count)
+}
+
+/// Queues a book for indexing and indexes it on a background task.
+///
+/// The status is set to `"pending"` before returning, so the book shows up
+/// as queued even if the task has not started yet. Failures are recorded in
+/// the book's index status and logged rather than returned.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `book_repo` - Repository for looking up the book's file.
+/// * `search_index_repo` - Repository holding the full-text index.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] if the pending status cannot be stored.
+pub async fn spawn_index_book(
+    book_id: i32,
+    book_repo: Arc<dyn BookRepository>,
+    search_index_repo: Arc<dyn SearchIndexRepository>,
+) -> Result<(), DomainError> {
+    search_index_repo
+        .set_status(book_id, INDEX_STATUS_PENDING, 0, None)
+        .await?;
+
+    spawn_index_queue(vec![book_id], book_repo, search_index_repo);
+
+    Ok(())
+}
+
+/// Indexes, on a background task, every book that is not yet indexed.
+///
+/// Books without a status row or with a `"pending"` or `"failed"` status are
+/// marked pending and indexed one after another, so a library scan returns
+/// without waiting for text extraction. Books being indexed right now are
+/// skipped; [`resume_interrupted_indexing`] picks up the ones a restart cut
+/// short.
+///
+/// # Arguments
+///
+/// * `book_repo` - Repository for listing books.
+/// * `search_index_repo` - Repository holding the full-text index.
+///
+/// # Returns
+///
+/// The number of books queued.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] if the books or statuses cannot be read.
+pub async fn spawn_index_unindexed_books(
+    book_repo: Arc<dyn BookRepository>,
+    search_index_repo: Arc<dyn SearchIndexRepository>,
+) -> Result<usize, DomainError> {
+    let done: HashSet<i32> = search_index_repo
+        .find_all_status()
+        .await?
+        .into_iter()
+        .filter(|s| s.status == INDEX_STATUS_INDEXED || s.status == INDEX_STATUS_INDEXING)
+        .map(|s| s.book_id)
+        .collect();
+
+    let book_ids: Vec<i32> = book_repo
+        .find_all()
+        .await?
+        .into_iter()
+        .map(|b| b.id)
+        .filter(|id| !done.contains(id))
+        .collect();
+
+    for &book_id in &book_ids {
+        search_index_repo
+            .set_status(book_id, INDEX_STATUS_PENDING, 0, None)
+            .await?;
+    }
+
+    let queued = book_ids.len();
+    spawn_index_queue(book_ids, book_repo, search_index_repo);
+
+    Ok(queued)
+}
+
+/// Re-queues, on a background task, the books whose indexing was cut short.
+///
+/// Meant to run once at startup: no indexing task survives a restart, so any
+/// `"pending"` or `"indexing"` status left behind is stale and would
+/// otherwise keep the book out of [`spawn_index_unindexed_books`] forever.
+///
+/// # Arguments
+///
+/// * `book_repo` - Repository for looking up the books' files.
+/// * `search_index_repo` - Repository holding the full-text index.
+///
+/// # Returns
+///
+/// The number of books queued.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] if the statuses cannot be read or reset.
+pub async fn resume_interrupted_indexing(
+    book_repo: Arc<dyn BookRepository>,
+    search_index_repo: Arc<dyn SearchIndexRepository>,
+) -> Result<usize, DomainError> {
+    let book_ids: Vec<i32> = search_index_repo
+        .find_all_status()
+        .await?
+        .into_iter()
+        .filter(|s| s.status == INDEX_STATUS_PENDING || s.status == INDEX_STATUS_INDEXING)
+        .map(|s| s.book_id)
+        .collect();
+
+    for &book_id in &book_ids {
+        search_index_repo
+            .set_status(book_id, INDEX_STATUS_PENDING, 0, None)
+            .await?;
+    }
+
+    let queued = book_ids.len();
+    spawn_index_queue(book_ids, book_repo, search_index_repo);
+
+    Ok(queued)
+}
+
+/// Indexes the given books one after another on a background task, logging
+/// the books that fail.
+fn spawn_index_queue(
+    book_ids: Vec<i32>,
+    book_repo: Arc<dyn BookRepository>,
+    search_index_repo: Arc<dyn SearchIndexRepository>,
+) {
+    tokio::spawn(async move {
+        for book_id in book_ids {
+            if let Err(e) = index_book(book_id, &book_repo, &search_index_repo).await {
+                log::warn!("Failed to index book {}: {}", book_id, e);
+            }
+        }
+    });
+}
+
+/// Clears the full-text index and rebuilds it for every book in the
+/// background.
+///
+/// # Arguments
+///
+/// * `book_repo` - Repository for listing books.
+/// * `search_index_repo` - Repository holding the full-text index.
+///
+/// # Returns
+///
+/// The number of books queued for indexing.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] if the index cannot be cleared or the
+/// books cannot be listed.
+pub async fn rebuild_index(
+    book_repo: Arc<dyn BookRepository>,
+    search_index_repo: Arc<dyn SearchIndexRepository>,
+) -> Result<usize, DomainError> {
+    search_index_repo.clear().await?;
+    spawn_index_unindexed_books(book_repo, search_index_repo).await
+}
+
+/// Searches the text of every indexed book.
+///
+/// The query is split into words, each matched as a quoted FTS5 phrase so
+/// user input cannot inject query syntax; all words must occur in the same
+/// chapter or page. Matching segments are grouped by book, and books are
+/// ordered by their best `bm25()` rank.
+///
+/// # Arguments
+///
+/// * `query` - The user's search text.
+/// * `limit` - Maximum number of matching chapters/pages to consider;
+///   defaults to [`DEFAULT_SEARCH_LIMIT`].
+/// * `book_repo` - Repository for looking up matching books.
+/// * `search_index_repo` - Repository holding the full-text index.
+///
+/// # Returns
+///
+/// One [`LibrarySearchResult`] per matching book, most relevant first, with
+/// `<mark>`-highlighted snippets. An empty query returns no results.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] on query failure.
+pub async fn search_library(
+    query: &str,
+    limit: Option<i32>,
+    book_repo: &Arc<dyn BookRepository>,
+    search_index_repo: &Arc<dyn SearchIndexRepository>,
+) -> Result<Vec<LibrarySearchResult>, DomainError> {
+    let Some(match_query) = build_match_query(query) else {
+        return Ok(Vec::new());
+    };
+    let matches = search_index_repo
+        .search(&match_query, limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
+        .await?;
+
+    let mut book_ids: Vec<i32> = Vec::new();
+    for m in &matches {
+        if !book_ids.contains(&m.book_id) {
+            book_ids.push(m.book_id);
+        }
+    }
+    let mut results: Vec<LibrarySearchResult> =
+        crate::application::book::get_books_by_ids(&book_ids, book_repo)
+            .await?
+            .into_iter()
+            .map(|book| LibrarySearchResult {
+                book,
+                score: 0.0,
+                hits: Vec::new(),
+            })
+            .collect();
+
+    for m in matches {
+        let Some(result) = results.iter_mut().find(|r| r.book.id == m.book_id) else {
+            continue;
+        };
+        if result.hits.is_empty() {
+            result.score = m.rank;
+        }
+        result.hits.push(LibrarySearchHit {
+            spine_index: m.spine_index,
+            page: m.page,
+            snippet: highlight_snippet(&m.snippet),
+            rank: m.rank,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Returns the full-text indexing status of one book, or of every book.
+///
+/// # Arguments
+///
+/// * `book_id` - Optional book ID; when `None` all statuses are returned.
+/// * `search_index_repo` - Repository holding the full-text index.
+///
+/// # Returns
+///
+/// The matching [`BookIndexStatus`] records. Books that were never queued
+/// have no record.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] on query failure.
+pub async fn get_index_status(
+    book_id: Option<i32>,
+    search_index_repo: &Arc<dyn SearchIndexRepository>,
+) -> Result<Vec<BookIndexStatus>, DomainError> {
+    match book_id {
+        Some(id) => Ok(search_index_repo.find_status(id).await?.into_iter().collect()),
+        None => search_index_repo.find_all_status().await,
+    }
+}
+
+/// Extracts the text segments of a book file, dispatching by file type.
+async fn extract_book_text(
+    path: Option<&str>,
+    file_type: Option<&str>,
+) -> Result<Vec<BookTextSegment>, DomainError> {
+    let path = path.ok_or_else(|| DomainError::File("No file path for book".into()))?;
+
+    let segments = match file_type {
+        Some("epub") => epub_handler::extract_epub_text(path).await,
+        Some("pdf") => pdf_handler::extract_pdf_text(path).await,
+        Some("mobi") => mobi_handler::extract_mobi_text(path).await,
+        other => {
+            return Err(DomainError::File(format!(
+                "Unsupported file type: {}",
+                other.unwrap_or("unknown")
+            )));
+        }
+    };
+
+    segments.map_err(|e| DomainError::Parse(e.to_string()))
+}
+
+/// Turns free text into an FTS5 `MATCH` expression of quoted terms, or
+/// `None` if it contains no words.
+pub fn build_match_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|word| word.replace('"', ""))
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{}\"", word))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// HTML-escapes an FTS5 snippet and turns its `\u{2}`/`\u{3}` match markers
+/// into `<mark>` tags.
+pub fn highlight_snippet(snippet: &str) -> String {
+    let mut html = String::with_capacity(snippet.len());
+    for c in snippet.chars() {
+        match c {
+            '\u{2}' => html.push_str("<mark>"),
+            '\u{3}' => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            _ => html.push(c),
+        }
+    }
+    html
+}
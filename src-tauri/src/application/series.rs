@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::dto::series_dto::SeriesBooksDto;
+use crate::domain::error::DomainError;
+use crate::domain::repository::*;
+
+/// Reading progress at which a book counts as finished.
+pub const FINISHED_PERCENTAGE: f32 = 100.0;
+
+/// Returns the books of a series in order, together with the book to read
+/// next.
+///
+/// # Arguments
+///
+/// * `series_id` - The series' database ID.
+/// * `series_repo` - Repository for looking up the series.
+/// * `book_repo` - Repository for loading the series' books.
+///
+/// # Returns
+///
+/// A [`SeriesBooksDto`] whose books are ordered by series index, with
+/// unnumbered books last. See [`next_unread_in_series`] for how the next
+/// book is chosen.
+///
+/// # Errors
+///
+/// Returns [`DomainError::NotFound`] if no series has the given ID and
+/// [`DomainError::Database`] on query failure.
+pub async fn get_series_books(
+    series_id: i32,
+    series_repo: &Arc<dyn SeriesRepository>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<SeriesBooksDto, DomainError> {
+    let series = series_repo
+        .find_by_id(series_id)
+        .await?
+        .ok_or(DomainError::NotFound)?;
+
+    let books: Vec<BookDto> = book_repo
+        .find_hydrated(&BookListQuery {
+            filter: BookFilter {
+                series_id: Some(series_id),
+                ..Default::default()
+            },
+            sort: BookSort::Series,
+            ..Default::default()
+        })
+        .await?
+        .into_iter()
+        .map(BookDto::from)
+        .collect();
+
+    let next_unread = next_unread_in_series(&books).cloned();
+    Ok(SeriesBooksDto::new(series, books, next_unread))
+}
+
+/// Picks the book to read next from a series' books in reading order.
+///
+/// Returns the book after the last finished one (based on
+/// `reading_progress`), whether it is in progress or unstarted; books skipped
+/// earlier in the series are not suggested again. Returns the first book when
+/// nothing has been finished yet, and `None` when the last book is finished.
+pub fn next_unread_in_series(books: &[BookDto]) -> Option<&BookDto> {
+    let next = books
+        .iter()
+        .rposition(|book| book.progress_percentage.unwrap_or(0.0) >= FINISHED_PERCENTAGE)
+        .map_or(0, |last_finished| last_finished + 1);
+
+    books.get(next)
+}
@@ -1,5 +1,23 @@
-use crate::domain::repository::BookRepository;
-use crate::infrastructure::file_handlers::BookMetadata;
+use crate::application::book::{LibraryRepos, get_book};
+use crate::application::managed_library::sync_managed_path;
+use crate::application::provider::{MetadataCandidate, MetadataField, MetadataProvider};
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::error::DomainError;
+use crate::domain::models::contributor::ROLE_AUTHOR;
+use crate::domain::repository::{
+    AuthorCredit, BookPatch, BookRepository, NewContributorLink, UpdateBook,
+};
+use crate::infrastructure::cover_cache;
+use crate::infrastructure::file_handlers::epub_handler;
+use crate::infrastructure::file_handlers::{
+    BookMetadata, ParsedContributor, authors_as_contributors, clean_description,
+    normalize_language_tag, split_tag_list,
+};
+use crate::utils::file::compute_checksum;
+use crate::utils::isbn::normalize_isbn;
+use image::ImageFormat;
+use serde::{Deserialize, Deserializer};
+use std::path::Path;
 use std::sync::Arc;
 
 /// Re-parses the original ebook file for a book and returns fresh metadata.
@@ -44,6 +62,11 @@ pub async fn fetch_metadata(
                 .await
                 .map_err(|e| crate::domain::error::DomainError::Parse(e.to_string()))?
         }
+        Some("mobi") => {
+            crate::infrastructure::file_handlers::mobi_handler::parse_mobi_meta(path)
+                .await
+                .map_err(|e| crate::domain::error::DomainError::Parse(e.to_string()))?
+        }
         _ => {
             crate::infrastructure::file_handlers::epub_handler::parse_epub_meta(path)
                 .await
@@ -84,6 +107,10 @@ pub async fn list_metadata(
                     crate::infrastructure::file_handlers::pdf_handler::parse_pdf_meta(path.clone())
                         .await
                 }
+                Some("mobi") => {
+                    crate::infrastructure::file_handlers::mobi_handler::parse_mobi_meta(path.clone())
+                        .await
+                }
                 _ => {
                     crate::infrastructure::file_handlers::epub_handler::parse_epub_meta(path.clone())
                         .await
@@ -98,48 +125,465 @@ pub async fn list_metadata(
     Ok(all_metadata)
 }
 
-/// Updates metadata fields for a book found by title search.
+/// A metadata edit made in the book details form.
+///
+/// Fields left out are not changed. `null` clears the publisher,
+/// publication date, ISBN, description, series, series index or tags, as
+/// does blank text or an empty tag list; `null` is ignored for the title and
+/// authors, which cannot be cleared. Authors are given in credit order and
+/// replace the book's author credits; other contributors such as
+/// translators are kept.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct MetadataPatch {
+    pub title: Option<String>,
+    pub authors: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub publisher: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub published_date: Option<Option<String>>,
+    /// ISBN-10 or ISBN-13, with or without hyphens; stored as ISBN-13.
+    #[serde(default, deserialize_with = "present")]
+    pub isbn: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub series: Option<Option<String>>,
+    /// Position within the series, such as `2` or `2.5`.
+    #[serde(default, deserialize_with = "present")]
+    pub series_index: Option<Option<f32>>,
+    #[serde(default, deserialize_with = "present")]
+    pub tags: Option<Option<Vec<String>>>,
+}
+
+/// Deserializes a field that is present, even as `null`, into `Some`, so a
+/// `null` that clears a field can be told apart from a missing one.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Applies a metadata edit to a book.
 ///
-/// Only the provided (non-`None`) fields are updated. The book is located
-/// by searching for the first title match.
+/// The whole patch is written in one transaction (see
+/// [`BookRepository::update_with_links`]): missing authors, publishers,
+/// series and tags are created, and authors and publishers left without any
+/// book are deleted. Tag names are normalized like imported ones, and an
+/// empty author list credits "Unknown Author". In managed library mode the
+/// book's file is renamed to match (see [`sync_managed_path`]).
 ///
 /// # Arguments
 ///
-/// * `book_name` - Substring to search book titles against.
-/// * `title` - New title, or `None` to keep existing.
-/// * `published_date` - New publication date, or `None` to keep existing.
-/// * `isbn` - New ISBN, or `None` to keep existing.
-/// * `book_repo` - Repository for searching and updating the book.
+/// * `book_id` - The book's database ID.
+/// * `patch` - The fields to change.
+/// * `book_repo` - Repository for looking up and updating the book.
+///
+/// # Returns
+///
+/// The updated book.
 ///
 /// # Errors
 ///
-/// Returns [`DomainError::NotFound`] when no book matches the title search.
+/// Returns [`DomainError::BookNotFound`] if no book has the given ID,
+/// [`DomainError::InvalidIsbn`] when the ISBN fails check-digit validation
+/// and [`DomainError::InvalidInput`] for a blank title or a negative series
+/// index.
 pub async fn update_metadata(
-    book_name: &str,
-    title: Option<&str>,
-    published_date: Option<&str>,
-    isbn: Option<&str>,
+    book_id: i32,
+    patch: &MetadataPatch,
     book_repo: &Arc<dyn BookRepository>,
-) -> Result<(), crate::domain::error::DomainError> {
-    let books = book_repo.search_by_title(book_name).await?;
-    let book = books
-        .into_iter()
-        .next()
-        .ok_or(crate::domain::error::DomainError::NotFound)?;
-
-    book_repo
-        .update(
-            book.id,
-            crate::domain::repository::UpdateBook {
-                title: title.map(|s| s.to_string()),
-                published_date: published_date.map(|s| s.to_string()),
-                publisher_id: None,
-                isbn: isbn.map(|s| s.to_string()),
-                file_type: None,
-                file_path: None,
-                cover_image_path: None,
-                checksum: None,
-            },
-        )
+) -> Result<BookDto, DomainError> {
+    let book_patch = validate_metadata_patch(patch)?;
+    if book_repo.find_by_id(book_id).await?.is_none() {
+        return Err(DomainError::BookNotFound(book_id));
+    }
+
+    book_repo.update_with_links(book_id, book_patch).await?;
+    sync_managed_path(book_id, book_repo).await?;
+    get_book(book_id, book_repo)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))
+}
+
+/// Validates and normalizes a [`MetadataPatch`] into a [`BookPatch`].
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] for a blank title or a negative or
+/// non-finite series index, and [`DomainError::InvalidIsbn`] for an ISBN
+/// with a bad check digit.
+pub fn validate_metadata_patch(patch: &MetadataPatch) -> Result<BookPatch, DomainError> {
+    let title = match patch.title.as_deref().map(str::trim) {
+        Some("") => return Err(DomainError::InvalidInput("Title cannot be empty".into())),
+        title => title.map(str::to_string),
+    };
+    let isbn = match optional_text(&patch.isbn) {
+        Some(Some(raw)) => Some(Some(
+            normalize_isbn(&raw).ok_or(DomainError::InvalidIsbn(raw))?,
+        )),
+        isbn => isbn,
+    };
+    if let Some(Some(index)) = patch.series_index
+        && !(index.is_finite() && index >= 0.0)
+    {
+        return Err(DomainError::InvalidInput(format!(
+            "Invalid series index: {}",
+            index
+        )));
+    }
+
+    Ok(BookPatch {
+        title,
+        authors: patch.authors.as_deref().map(|names| {
+            authors_as_contributors(names)
+                .into_iter()
+                .map(|c| AuthorCredit {
+                    name: c.name,
+                    sort_name: c.sort_name,
+                })
+                .collect()
+        }),
+        publisher: optional_text(&patch.publisher),
+        published_date: optional_text(&patch.published_date),
+        isbn,
+        description: patch
+            .description
+            .as_ref()
+            .map(|text| text.as_deref().and_then(clean_description)),
+        series: optional_text(&patch.series),
+        series_index: patch.series_index,
+        tags: patch
+            .tags
+            .as_ref()
+            .map(|tags| tags.as_deref().map(split_tag_list).unwrap_or_default()),
+    })
+}
+
+/// Trims an edited text field; `null` and blank text become `Some(None)` to
+/// clear it.
+fn optional_text(value: &Option<Option<String>>) -> Option<Option<String>> {
+    value.as_ref().map(|text| {
+        text.as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+    })
+}
+
+/// Asks a metadata provider for records matching a book.
+///
+/// Books with an ISBN are looked up by ISBN first. When that finds nothing,
+/// or the book has no ISBN, the provider is searched by title and first
+/// author.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `provider` - The online catalogue to ask.
+/// * `book_repo` - Repository for looking up the book.
+///
+/// # Returns
+///
+/// Candidates ordered by descending confidence.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if no book has the given ID and
+/// [`DomainError::Provider`] when the provider cannot be reached.
+pub async fn find_metadata_candidates(
+    book_id: i32,
+    provider: &Arc<dyn MetadataProvider>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<Vec<MetadataCandidate>, DomainError> {
+    let book = get_book(book_id, book_repo)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+
+    if let Some(isbn) = book.isbn.as_deref().and_then(normalize_isbn) {
+        let candidates = provider.lookup_isbn(&isbn).await?;
+        if !candidates.is_empty() {
+            return Ok(candidates);
+        }
+    }
+
+    provider
+        .search(&book.title, book.authors.first().map(String::as_str))
         .await
 }
+
+/// Copies the chosen fields of a metadata candidate onto a book.
+///
+/// A field the candidate has no value for is left unchanged on the book.
+/// Authors replace the book's author credits while other contributors
+/// (editors, translators, ...) are kept after them. Tags are added to the
+/// book's existing tags. The series index is copied along with the series.
+/// In managed library mode the book's file is renamed to match.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `candidate` - The metadata to copy from, usually a
+///   [`MetadataCandidate::metadata`].
+/// * `fields` - Which fields to copy.
+/// * `repos` - Repositories for the book and its linked records.
+///
+/// # Returns
+///
+/// The updated book.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if no book has the given ID and
+/// [`DomainError::InvalidIsbn`] if the ISBN field is chosen and the
+/// candidate's ISBN is invalid.
+pub async fn apply_metadata_candidate(
+    book_id: i32,
+    candidate: &BookMetadata,
+    fields: &[MetadataField],
+    repos: &LibraryRepos<'_>,
+) -> Result<BookDto, DomainError> {
+    if repos.book_repo.find_by_id(book_id).await?.is_none() {
+        return Err(DomainError::BookNotFound(book_id));
+    }
+
+    let mut update = UpdateBook::default();
+    let mut changed = false;
+    for field in fields {
+        match field {
+            MetadataField::Title => {
+                let title = candidate.title.trim();
+                if !title.is_empty() {
+                    update.title = Some(title.to_string());
+                    changed = true;
+                }
+            }
+            MetadataField::Authors => {
+                apply_authors(book_id, &candidate.contributors, repos).await?;
+            }
+            MetadataField::Publisher => {
+                if let Some(name) = candidate.publishers.first() {
+                    let publisher = repos.publisher_repo.find_or_create(name).await?;
+                    update.publisher_id = Some(publisher.id);
+                    changed = true;
+                }
+            }
+            MetadataField::PublishedDate => {
+                if let Some(date) = &candidate.published_date {
+                    update.published_date = Some(date.clone());
+                    changed = true;
+                }
+            }
+            MetadataField::Isbn => {
+                if let Some(raw) = &candidate.isbn {
+                    let isbn =
+                        normalize_isbn(raw).ok_or_else(|| DomainError::InvalidIsbn(raw.clone()))?;
+                    update.isbn = Some(isbn);
+                    changed = true;
+                }
+            }
+            MetadataField::Series => {
+                if let Some(name) = &candidate.series {
+                    let series = repos.series_repo.find_or_create(name).await?;
+                    update.series_id = Some(Some(series.id));
+                    update.series_index = Some(candidate.series_index);
+                    changed = true;
+                }
+            }
+            MetadataField::Tags => {
+                for name in split_tag_list(&candidate.tags) {
+                    let tag = repos.tag_repo.find_or_create(&name).await?;
+                    repos.tag_repo.add_to_book(book_id, tag.id).await?;
+                }
+            }
+            MetadataField::Description => {
+                if let Some(description) =
+                    candidate.description.as_deref().and_then(clean_description)
+                {
+                    update.description = Some(Some(description));
+                    changed = true;
+                }
+            }
+            MetadataField::Language => {
+                if let Some(language) = candidate
+                    .language
+                    .as_deref()
+                    .and_then(normalize_language_tag)
+                {
+                    update.language = Some(Some(language));
+                    changed = true;
+                }
+            }
+            MetadataField::PageCount => {
+                if let Some(pages) = candidate.page_count.filter(|pages| *pages > 0) {
+                    update.page_count = Some(Some(pages));
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    if changed {
+        repos.book_repo.update(book_id, update).await?;
+        sync_managed_path(book_id, repos.book_repo).await?;
+    }
+    get_book(book_id, repos.book_repo)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))
+}
+
+/// Writes a book's library metadata into its EPUB file.
+///
+/// The title, contributors with their roles, publisher, publication date,
+/// ISBN, tags, series, description and language stored for the book replace
+/// the ones in the file's package document (see
+/// [`embed_epub_metadata`](epub_handler::embed_epub_metadata)); fields the
+/// book has no value for are left as they are in the file. The file is
+/// replaced atomically, then its new checksum is stored so duplicate
+/// detection keeps recognising it, and the cached cover thumbnails move
+/// along with the checksum.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `cover_path` - An image file (JPEG, PNG, GIF or WebP) to embed as the
+///   new cover, or `None` to keep the file's cover.
+/// * `book_repo` - Repository for looking up and updating the book.
+///
+/// # Returns
+///
+/// The updated book.
+///
+/// # Errors
+///
+/// Returns [`DomainError::BookNotFound`] if no book has the given ID,
+/// [`DomainError::InvalidInput`] if the book is not an EPUB or the cover is
+/// not a supported image, and [`DomainError::File`] when the book has no
+/// file path or the file cannot be rewritten.
+pub async fn embed_metadata(
+    book_id: i32,
+    cover_path: Option<&Path>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<BookDto, DomainError> {
+    let book = get_book(book_id, book_repo)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))?;
+    if book.file_type.as_deref() != Some("epub") {
+        return Err(DomainError::InvalidInput(
+            "Metadata can only be embedded into EPUB files".into(),
+        ));
+    }
+    let path = book
+        .file_path
+        .clone()
+        .ok_or_else(|| DomainError::File("No file path".into()))?;
+
+    let cover_data = match cover_path {
+        Some(cover_path) => Some(read_cover_image(cover_path).await?),
+        None => None,
+    };
+    let metadata = BookMetadata {
+        title: book.title.clone(),
+        authors: book.authors.clone(),
+        contributors: book
+            .contributors
+            .iter()
+            .map(|c| ParsedContributor {
+                name: c.name.clone(),
+                sort_name: c.sort_name.clone(),
+                role: c.role.clone(),
+            })
+            .collect(),
+        publishers: book.publisher.iter().cloned().collect(),
+        published_date: book.published_date.clone(),
+        isbn: book.isbn.clone(),
+        series: book.series.clone(),
+        series_index: book.series_index,
+        tags: book.tags.clone(),
+        description: book.description.clone(),
+        language: book.language.clone(),
+        word_count: book.word_count,
+        page_count: book.page_count,
+        file_path: path.clone(),
+        cover_data,
+        checksum: book.checksum.clone().unwrap_or_default(),
+    };
+    epub_handler::embed_epub_metadata(path.clone(), metadata)
+        .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+
+    let checksum = compute_checksum(&path)
+        .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+    let cover_image_path = cover_cache::rekey_cover(
+        book.cover_image_path.as_deref(),
+        book.checksum.as_deref(),
+        &checksum,
+    );
+    let update = UpdateBook {
+        checksum: Some(checksum),
+        cover_image_path,
+        ..Default::default()
+    };
+    book_repo.update(book_id, update).await?;
+
+    get_book(book_id, book_repo)
+        .await?
+        .ok_or(DomainError::BookNotFound(book_id))
+}
+
+/// Reads a cover image to embed and returns it with its MIME type.
+async fn read_cover_image(path: &Path) -> Result<(Vec<u8>, String), DomainError> {
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|e| DomainError::File(e.to_string()))?;
+    match image::guess_format(&bytes) {
+        Ok(
+            format @ (ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::Gif | ImageFormat::WebP),
+        ) => Ok((bytes, format.to_mime_type().to_string())),
+        _ => Err(DomainError::InvalidInput(format!(
+            "Unsupported cover image: {}",
+            path.display()
+        ))),
+    }
+}
+
+/// Replaces a book's author credits with the candidate's authors, keeping
+/// its other contributors after them. Does nothing when the candidate names
+/// no authors.
+async fn apply_authors(
+    book_id: i32,
+    contributors: &[ParsedContributor],
+    repos: &LibraryRepos<'_>,
+) -> Result<(), DomainError> {
+    let mut links: Vec<NewContributorLink> = Vec::new();
+    for parsed in contributors.iter().filter(|c| c.role == ROLE_AUTHOR) {
+        let author = repos
+            .author_repo
+            .find_or_create(&parsed.name, parsed.sort_name.as_deref())
+            .await?;
+        if !links.iter().any(|link| link.author_id == author.id) {
+            links.push(NewContributorLink {
+                author_id: author.id,
+                role: ROLE_AUTHOR.to_string(),
+            });
+        }
+    }
+    if links.is_empty() {
+        return Ok(());
+    }
+
+    let others = repos.author_repo.get_contributors_by_book(book_id).await?;
+    links.extend(
+        others
+            .into_iter()
+            .filter(|c| c.role != ROLE_AUTHOR)
+            .map(|c| NewContributorLink {
+                author_id: c.author_id,
+                role: c.role,
+            }),
+    );
+    repos.book_author_repo.replace_all(book_id, &links).await
+}
@@ -1,11 +1,15 @@
+use crate::application::library_scan::ScanJobs;
+use crate::application::provider::MetadataProvider;
 use crate::domain::repository::*;
+use crate::infrastructure::library_watcher::LibraryWatcher;
 use std::sync::Arc;
 
 /// Application-wide dependency container.
 ///
-/// Holds `Arc`-wrapped trait objects for every repository, enabling
-/// dependency injection and testability. Passed to Tauri commands as
-/// `State<'_, AppState>`.
+/// Holds `Arc`-wrapped trait objects for every repository and the online
+/// metadata provider, enabling dependency injection and testability, plus
+/// the registry of running library scans and the library folder watcher.
+/// Passed to Tauri commands as `State<'_, AppState>`.
 pub struct AppState {
     pub book_repo: Arc<dyn BookRepository>,
     pub author_repo: Arc<dyn AuthorRepository>,
@@ -14,4 +18,13 @@ pub struct AppState {
     pub bookmark_repo: Arc<dyn BookmarkRepository>,
     pub annotation_repo: Arc<dyn AnnotationRepository>,
     pub reading_progress_repo: Arc<dyn ReadingProgressRepository>,
+    pub search_index_repo: Arc<dyn SearchIndexRepository>,
+    pub collection_repo: Arc<dyn CollectionRepository>,
+    pub series_repo: Arc<dyn SeriesRepository>,
+    pub tag_repo: Arc<dyn TagRepository>,
+    pub library_root_repo: Arc<dyn LibraryRootRepository>,
+    pub library_settings_repo: Arc<dyn LibrarySettingsRepository>,
+    pub metadata_provider: Arc<dyn MetadataProvider>,
+    pub scan_jobs: Arc<ScanJobs>,
+    pub library_watcher: Arc<LibraryWatcher>,
 }
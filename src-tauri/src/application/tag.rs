@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::dto::tag_dto::TagDto;
+use crate::domain::error::DomainError;
+use crate::domain::models::tag::{MAX_TAG_LENGTH, Tag, normalize_tag_name};
+use crate::domain::repository::*;
+
+/// Returns every tag with the number of books carrying it.
+///
+/// # Arguments
+///
+/// * `tag_repo` - Repository for listing tags.
+///
+/// # Returns
+///
+/// Tags ordered by name, case-insensitively, including unused ones.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] on query failure.
+pub async fn list_tags(tag_repo: &Arc<dyn TagRepository>) -> Result<Vec<TagDto>, DomainError> {
+    Ok(tag_repo
+        .find_all_with_counts()
+        .await?
+        .into_iter()
+        .map(|(tag, book_count)| TagDto::new(tag, book_count))
+        .collect())
+}
+
+/// Returns the books carrying a tag, ordered by title.
+///
+/// # Arguments
+///
+/// * `tag_id` - The tag's database ID.
+/// * `tag_repo` - Repository for looking up the tag.
+/// * `book_repo` - Repository for loading the tagged books.
+///
+/// # Errors
+///
+/// Returns [`DomainError::NotFound`] if no tag has the given ID and
+/// [`DomainError::Database`] on query failure.
+pub async fn get_tag_books(
+    tag_id: i32,
+    tag_repo: &Arc<dyn TagRepository>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<Vec<BookDto>, DomainError> {
+    find_tag(tag_id, tag_repo).await?;
+
+    Ok(book_repo
+        .find_hydrated(&BookListQuery {
+            filter: BookFilter {
+                tag_id: Some(tag_id),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await?
+        .into_iter()
+        .map(BookDto::from)
+        .collect())
+}
+
+/// Tags a book, creating the tag if no tag has that name yet.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `name` - The tag name; surrounding and repeated whitespace is dropped.
+/// * `tag_repo` - Repository for finding or creating the tag.
+/// * `book_repo` - Repository for checking the book exists.
+///
+/// # Returns
+///
+/// The tag with its updated book count.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if the name is blank or too long,
+/// [`DomainError::BookNotFound`] if the book does not exist and
+/// [`DomainError::Database`] on failure.
+pub async fn add_tag_to_book(
+    book_id: i32,
+    name: &str,
+    tag_repo: &Arc<dyn TagRepository>,
+    book_repo: &Arc<dyn BookRepository>,
+) -> Result<TagDto, DomainError> {
+    let name = validated_name(name)?;
+    if book_repo.find_by_id(book_id).await?.is_none() {
+        return Err(DomainError::BookNotFound(book_id));
+    }
+
+    let tag = tag_repo.find_or_create(&name).await?;
+    tag_repo.add_to_book(book_id, tag.id).await?;
+    with_count(tag, tag_repo).await
+}
+
+/// Removes a tag from a book. The tag is kept even if no book uses it.
+///
+/// # Errors
+///
+/// Returns [`DomainError::Database`] on failure.
+pub async fn remove_tag_from_book(
+    book_id: i32,
+    tag_id: i32,
+    tag_repo: &Arc<dyn TagRepository>,
+) -> Result<(), DomainError> {
+    tag_repo.remove_from_book(book_id, tag_id).await
+}
+
+/// Renames a tag.
+///
+/// Changing only the case of a name is allowed. Renaming onto a name that
+/// another tag already uses is rejected; use [`merge_tags`] for that.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] if the name is blank, too long or
+/// taken, [`DomainError::NotFound`] if the tag does not exist and
+/// [`DomainError::Database`] on failure.
+pub async fn rename_tag(
+    tag_id: i32,
+    name: &str,
+    tag_repo: &Arc<dyn TagRepository>,
+) -> Result<TagDto, DomainError> {
+    let name = validated_name(name)?;
+    let mut tag = find_tag(tag_id, tag_repo).await?;
+
+    if let Some(existing) = tag_repo.find_by_name(&name).await?
+        && existing.id != tag_id
+    {
+        return Err(DomainError::InvalidInput(format!(
+            "A tag named \"{}\" already exists; merge the tags instead",
+            existing.name
+        )));
+    }
+
+    tag_repo.rename(tag_id, &name).await?;
+    tag.name = name;
+    with_count(tag, tag_repo).await
+}
+
+/// Merges one tag into another: every book of the source tag gets the
+/// target tag, and the source tag is deleted.
+///
+/// # Returns
+///
+/// The target tag with its updated book count.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] when both IDs are the same,
+/// [`DomainError::NotFound`] if either tag does not exist and
+/// [`DomainError::Database`] on failure.
+pub async fn merge_tags(
+    source_tag_id: i32,
+    target_tag_id: i32,
+    tag_repo: &Arc<dyn TagRepository>,
+) -> Result<TagDto, DomainError> {
+    if source_tag_id == target_tag_id {
+        return Err(DomainError::InvalidInput(
+            "Cannot merge a tag into itself".into(),
+        ));
+    }
+    find_tag(source_tag_id, tag_repo).await?;
+    let target = find_tag(target_tag_id, tag_repo).await?;
+
+    tag_repo.merge(source_tag_id, target_tag_id).await?;
+    with_count(target, tag_repo).await
+}
+
+/// Normalizes a user-supplied tag name or explains why it is rejected.
+fn validated_name(name: &str) -> Result<String, DomainError> {
+    normalize_tag_name(name).ok_or_else(|| {
+        DomainError::InvalidInput(format!(
+            "Tag names must be between 1 and {} characters",
+            MAX_TAG_LENGTH
+        ))
+    })
+}
+
+async fn find_tag(tag_id: i32, tag_repo: &Arc<dyn TagRepository>) -> Result<Tag, DomainError> {
+    tag_repo
+        .find_by_id(tag_id)
+        .await?
+        .ok_or(DomainError::NotFound)
+}
+
+async fn with_count(tag: Tag, tag_repo: &Arc<dyn TagRepository>) -> Result<TagDto, DomainError> {
+    let book_count = tag_repo.count_books(tag.id).await?;
+    Ok(TagDto::new(tag, book_count))
+}
@@ -1,27 +1,51 @@
 use serde::Serialize;
 
+use crate::domain::models::book::HydratedBook;
+use crate::domain::models::contributor::Contributor;
+
 /// Data transfer object for books sent to the frontend.
 ///
-/// Combines a [`Book`](crate::domain::models::book::Book) with its first author
-/// and publisher names for convenient rendering in the UI.
+/// Combines a [`Book`](crate::domain::models::book::Book) with its author and
+/// publisher names and reading progress for convenient rendering in the UI.
+/// `authors` lists the contributors with the author role and `author` is the
+/// first of them, falling back to the first contributor of any role.
 #[derive(Debug, Clone, Serialize)]
 pub struct BookDto {
     pub id: i32,
     pub title: String,
     pub author: Option<String>,
+    pub authors: Vec<String>,
+    pub contributors: Vec<Contributor>,
     pub published_date: Option<String>,
     pub publisher: Option<String>,
+    pub series_id: Option<i32>,
+    pub series: Option<String>,
+    pub series_index: Option<f32>,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub language: Option<String>,
+    pub word_count: Option<i32>,
+    pub page_count: Option<i32>,
     pub isbn: Option<String>,
     pub file_type: Option<String>,
     pub file_path: Option<String>,
+    /// `"ok"`, `"missing"`, `"moved"` or `"modified"`; see
+    /// [`FILE_STATUS_OK`](crate::domain::models::book::FILE_STATUS_OK).
+    pub file_status: String,
     pub cover_image_path: Option<String>,
     pub checksum: Option<String>,
     pub added_at: Option<String>,
+    pub is_favorite: bool,
+    pub rating: Option<f32>,
+    pub review: Option<String>,
+    pub progress_percentage: Option<f32>,
+    pub last_read_at: Option<String>,
 }
 
 impl BookDto {
     /// Constructs a `BookDto` from a domain [`Book`](crate::domain::models::book::Book) and
-    /// resolved author/publisher names.
+    /// resolved author/publisher names. Contributors, series name, tags and
+    /// reading progress are left empty.
     pub fn new(
         book: &crate::domain::models::book::Book,
         author: Option<String>,
@@ -30,15 +54,62 @@ impl BookDto {
         Self {
             id: book.id,
             title: book.title.clone(),
+            authors: author.iter().cloned().collect(),
             author,
+            contributors: Vec::new(),
             published_date: book.published_date.clone(),
             publisher,
+            series_id: book.series_id,
+            series: None,
+            series_index: book.series_index,
+            tags: Vec::new(),
+            description: book.description.clone(),
+            language: book.language.clone(),
+            word_count: book.word_count,
+            page_count: book.page_count,
             isbn: book.isbn.clone(),
             file_type: book.file_type.clone(),
             file_path: book.file_path.clone(),
+            file_status: book.file_status.clone(),
             cover_image_path: book.cover_image_path.clone(),
             checksum: book.checksum.clone(),
             added_at: book.added_at.clone(),
+            is_favorite: book.is_favorite,
+            rating: book.rating,
+            review: book.review.clone(),
+            progress_percentage: None,
+            last_read_at: None,
         }
     }
 }
+
+impl From<HydratedBook> for BookDto {
+    fn from(hydrated: HydratedBook) -> Self {
+        let authors = hydrated.author_names();
+        let author = authors
+            .first()
+            .or_else(|| hydrated.contributors.first().map(|c| &c.name))
+            .cloned();
+        let mut dto = Self::new(&hydrated.book, author, hydrated.publisher);
+        dto.authors = authors;
+        dto.contributors = hydrated.contributors;
+        dto.series = hydrated.series;
+        dto.tags = hydrated.tags;
+        dto.progress_percentage = hydrated.progress_percentage;
+        dto.last_read_at = hydrated.last_read_at;
+        dto
+    }
+}
+
+/// One page of a book listing.
+#[derive(Debug, Clone, Serialize)]
+pub struct BookPageDto {
+    /// The books on this page.
+    pub items: Vec<BookDto>,
+    /// Number of books matching the filter across all pages.
+    pub total: i64,
+    /// Offset of the first item.
+    pub offset: i64,
+    /// Page size that was applied.
+    pub limit: i64,
+}
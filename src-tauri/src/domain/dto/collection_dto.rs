@@ -0,0 +1,41 @@
+use serde::Serialize;
+
+use crate::domain::models::collection::Collection;
+use crate::domain::models::smart_collection::RuleGroup;
+
+/// Data transfer object for collections sent to the frontend.
+///
+/// Combines a [`Collection`] with the IDs of its books in collection order.
+/// For smart collections the IDs are the current result of its rules.
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionDto {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub accent_color: Option<String>,
+    pub cover_image: Option<String>,
+    pub kind: String,
+    pub rules: Option<RuleGroup>,
+    pub book_ids: Vec<i32>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl CollectionDto {
+    /// Constructs a `CollectionDto` from a domain [`Collection`] and its
+    /// ordered book IDs.
+    pub fn new(collection: Collection, book_ids: Vec<i32>) -> Self {
+        Self {
+            id: collection.id,
+            name: collection.name,
+            description: collection.description,
+            accent_color: collection.accent_color,
+            cover_image: collection.cover_image,
+            kind: collection.kind,
+            rules: collection.rules,
+            book_ids,
+            created_at: collection.created_at,
+            updated_at: collection.updated_at,
+        }
+    }
+}
@@ -1,3 +1,7 @@
 //! Data transfer objects for presentation to the frontend.
 
 pub mod book_dto;
+pub mod collection_dto;
+pub mod search_dto;
+pub mod series_dto;
+pub mod tag_dto;
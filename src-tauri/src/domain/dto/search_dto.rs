@@ -0,0 +1,28 @@
+use serde::Serialize;
+
+use crate::domain::dto::book_dto::BookDto;
+
+/// A book matching a library-wide full-text search, with its best-matching
+/// chapters or pages.
+#[derive(Debug, Clone, Serialize)]
+pub struct LibrarySearchResult {
+    /// The matching book.
+    pub book: BookDto,
+    /// `bm25()` rank of the book's best hit; lower is more relevant.
+    pub score: f64,
+    /// Matching chapters (EPUB) or pages (PDF), most relevant first.
+    pub hits: Vec<LibrarySearchHit>,
+}
+
+/// A chapter or page of a book matching a library-wide full-text search.
+#[derive(Debug, Clone, Serialize)]
+pub struct LibrarySearchHit {
+    /// 0-based EPUB spine index of the matching chapter.
+    pub spine_index: Option<i32>,
+    /// 0-based PDF page index of the matching page.
+    pub page: Option<i32>,
+    /// HTML-escaped excerpt with matched terms wrapped in `<mark>` tags.
+    pub snippet: String,
+    /// `bm25()` rank of the hit; lower is more relevant.
+    pub rank: f64,
+}
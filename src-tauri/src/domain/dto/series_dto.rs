@@ -0,0 +1,28 @@
+use serde::Serialize;
+
+use crate::domain::dto::book_dto::BookDto;
+use crate::domain::models::series::Series;
+
+/// A series with its books in reading order, sent to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesBooksDto {
+    pub id: i32,
+    pub name: String,
+    /// Books ordered by series index; unnumbered books come last.
+    pub books: Vec<BookDto>,
+    /// The book to read next, if the series is not finished.
+    pub next_unread: Option<BookDto>,
+}
+
+impl SeriesBooksDto {
+    /// Constructs a `SeriesBooksDto` from a domain [`Series`], its ordered
+    /// books and the next book to read.
+    pub fn new(series: Series, books: Vec<BookDto>, next_unread: Option<BookDto>) -> Self {
+        Self {
+            id: series.id,
+            name: series.name,
+            books,
+            next_unread,
+        }
+    }
+}
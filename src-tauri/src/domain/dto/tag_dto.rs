@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+use crate::domain::models::tag::Tag;
+
+/// Data transfer object for tags sent to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct TagDto {
+    pub id: i32,
+    pub name: String,
+    /// Number of books carrying the tag.
+    pub book_count: i64,
+}
+
+impl TagDto {
+    /// Constructs a `TagDto` from a domain [`Tag`] and its book count.
+    pub fn new(tag: Tag, book_count: i64) -> Self {
+        Self {
+            id: tag.id,
+            name: tag.name,
+            book_count,
+        }
+    }
+}
@@ -31,6 +31,23 @@ pub enum DomainError {
     #[error("Parse error: {0}")]
     Parse(String),
 
+    /// A collection with the given ID was not found in the database.
+    #[error("Collection not found: {0}")]
+    CollectionNotFound(i32),
+
+    /// Input supplied by the caller was rejected (e.g. an empty name).
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    /// A value given as an ISBN is not a valid ISBN-10 or ISBN-13.
+    #[error("Invalid ISBN: {0}")]
+    InvalidIsbn(String),
+
+    /// An online metadata provider could not be reached or returned an
+    /// unreadable response.
+    #[error("Metadata provider error: {0}")]
+    Provider(String),
+
     /// A generic not-found error for non-book lookups.
     #[error("Not found")]
     NotFound,
@@ -5,4 +5,6 @@ pub struct Author {
     pub id: i32,
     /// Author's full name as extracted from ebook metadata.
     pub name: String,
+    /// Sort key such as `"Le Guin, Ursula K."`, if known.
+    pub sort_name: Option<String>,
 }
@@ -1,3 +1,15 @@
+use crate::domain::models::contributor::{Contributor, ROLE_AUTHOR};
+
+/// The book's file is where the library expects it.
+pub const FILE_STATUS_OK: &str = "ok";
+/// The book's file was deleted or moved out of the watched library folders.
+pub const FILE_STATUS_MISSING: &str = "missing";
+/// The book's file is gone, but a file with its checksum exists elsewhere
+/// under a library folder.
+pub const FILE_STATUS_MOVED: &str = "moved";
+/// The book's file no longer matches the stored checksum.
+pub const FILE_STATUS_MODIFIED: &str = "modified";
+
 /// A book in the user's library.
 #[derive(Debug, Clone)]
 pub struct Book {
@@ -21,4 +33,57 @@ pub struct Book {
     pub checksum: Option<String>,
     /// ISO 8601 timestamp of when the book was imported.
     pub added_at: Option<String>,
+    /// Whether the user marked the book as a favorite.
+    pub is_favorite: bool,
+    /// Star rating from `0.0` to `5.0` in half-star steps, if rated.
+    pub rating: Option<f32>,
+    /// The user's free-text review, if any.
+    pub review: Option<String>,
+    /// Foreign key to the [`Series`](super::series::Series) record, if any.
+    pub series_id: Option<i32>,
+    /// Position within the series; fractional for novellas such as `2.5`.
+    pub series_index: Option<f32>,
+    /// Plain-text synopsis from the file's metadata.
+    pub description: Option<String>,
+    /// BCP-47 language tag, e.g. `"en"` or `"pt-BR"`.
+    pub language: Option<String>,
+    /// Number of words in the text (EPUB only).
+    pub word_count: Option<i32>,
+    /// Page count: the real count for PDFs, estimated from `word_count`
+    /// for EPUBs.
+    pub page_count: Option<i32>,
+    /// Result of the last file check: [`FILE_STATUS_OK`],
+    /// [`FILE_STATUS_MISSING`], [`FILE_STATUS_MOVED`] or
+    /// [`FILE_STATUS_MODIFIED`].
+    pub file_status: String,
+}
+
+/// A [`Book`] together with the related data shown in listings.
+#[derive(Debug, Clone)]
+pub struct HydratedBook {
+    /// The book record.
+    pub book: Book,
+    /// Everyone credited on the book, ordered by position.
+    pub contributors: Vec<Contributor>,
+    /// Name of the linked publisher, if any.
+    pub publisher: Option<String>,
+    /// Name of the linked series, if any.
+    pub series: Option<String>,
+    /// Names of the book's tags, ordered case-insensitively.
+    pub tags: Vec<String>,
+    /// Reading progress percentage, if the book was ever opened.
+    pub progress_percentage: Option<f32>,
+    /// Timestamp of the last reading session, if any.
+    pub last_read_at: Option<String>,
+}
+
+impl HydratedBook {
+    /// Names of the contributors with the author role, in credit order.
+    pub fn author_names(&self) -> Vec<String> {
+        self.contributors
+            .iter()
+            .filter(|c| c.role == ROLE_AUTHOR)
+            .map(|c| c.name.clone())
+            .collect()
+    }
 }
@@ -0,0 +1,23 @@
+/// A user-created collection (shelf) of books.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Collection {
+    /// Auto-generated primary key.
+    pub id: i32,
+    /// Display name of the collection.
+    pub name: String,
+    /// Optional longer description shown on the collection card.
+    pub description: Option<String>,
+    /// Accent color or style class used by the frontend.
+    pub accent_color: Option<String>,
+    /// Cover image URL or path, if set.
+    pub cover_image: Option<String>,
+    /// ISO 8601 timestamp of when the collection was created.
+    pub created_at: Option<String>,
+    /// ISO 8601 timestamp of the last modification.
+    pub updated_at: Option<String>,
+    /// `"manual"` or `"smart"`.
+    pub kind: String,
+    /// Rules selecting the books of a smart collection; `None` for manual
+    /// collections.
+    pub rules: Option<crate::domain::models::smart_collection::RuleGroup>,
+}
@@ -0,0 +1,52 @@
+use serde::Serialize;
+
+/// Wrote the book.
+pub const ROLE_AUTHOR: &str = "author";
+/// Edited the book or compiled an anthology.
+pub const ROLE_EDITOR: &str = "editor";
+/// Translated the book.
+pub const ROLE_TRANSLATOR: &str = "translator";
+/// Illustrated the book.
+pub const ROLE_ILLUSTRATOR: &str = "illustrator";
+
+/// A person credited on a book, with their role and display position.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Contributor {
+    /// Foreign key to the [`Author`](super::author::Author) record.
+    pub author_id: i32,
+    /// Display name, e.g. `"Ursula K. Le Guin"`.
+    pub name: String,
+    /// Sort key, e.g. `"Le Guin, Ursula K."`, if known.
+    pub sort_name: Option<String>,
+    /// One of [`ROLE_AUTHOR`], [`ROLE_EDITOR`], [`ROLE_TRANSLATOR`] or
+    /// [`ROLE_ILLUSTRATOR`].
+    pub role: String,
+    /// 0-based order in which the book credits this person.
+    pub position: i32,
+}
+
+/// Maps a MARC relator code (`"aut"`, `"edt"`, `"trl"`, `"ill"`) or a role
+/// name to one of the supported roles.
+///
+/// Returns `None` for roles the library does not track, such as `"bkp"`
+/// (book producer) that conversion tools add for themselves.
+pub fn role_from_marc_relator(code: &str) -> Option<&'static str> {
+    match code.trim().to_ascii_lowercase().as_str() {
+        "aut" | "cre" | "author" => Some(ROLE_AUTHOR),
+        "edt" | "editor" => Some(ROLE_EDITOR),
+        "trl" | "translator" => Some(ROLE_TRANSLATOR),
+        "ill" | "illustrator" => Some(ROLE_ILLUSTRATOR),
+        _ => None,
+    }
+}
+
+/// Maps one of the supported roles to its MARC relator code, the inverse of
+/// [`role_from_marc_relator`]. Unknown roles map to `"aut"`.
+pub fn marc_relator_for_role(role: &str) -> &'static str {
+    match role {
+        ROLE_EDITOR => "edt",
+        ROLE_TRANSLATOR => "trl",
+        ROLE_ILLUSTRATOR => "ill",
+        _ => "aut",
+    }
+}
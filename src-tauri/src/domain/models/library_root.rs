@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+/// A folder whose ebooks are imported and kept in sync automatically.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LibraryRoot {
+    /// Auto-generated primary key.
+    pub id: i32,
+    /// Absolute, canonical path of the folder. Unique.
+    pub path: String,
+    /// ISO 8601 timestamp of when the folder was added.
+    pub added_at: Option<String>,
+}
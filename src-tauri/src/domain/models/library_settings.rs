@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// `import_mode` that copies imported files into the library directory.
+pub const IMPORT_MODE_COPY: &str = "copy";
+/// `import_mode` that moves imported files into the library directory.
+pub const IMPORT_MODE_MOVE: &str = "move";
+
+/// Managed library settings chosen in the app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibrarySettings {
+    /// Directory imported files are placed in; managed mode is off when
+    /// `None`.
+    pub library_dir: Option<String>,
+    /// Template giving a book's path inside the library directory.
+    pub naming_template: String,
+    /// How imported files get into the directory: [`IMPORT_MODE_COPY`] or
+    /// [`IMPORT_MODE_MOVE`].
+    pub import_mode: String,
+}
@@ -4,5 +4,13 @@ pub mod annotation;
 pub mod author;
 pub mod book;
 pub mod bookmark;
+pub mod collection;
+pub mod contributor;
+pub mod library_root;
+pub mod library_settings;
 pub mod publisher;
 pub mod reading_progress;
+pub mod search_index;
+pub mod series;
+pub mod smart_collection;
+pub mod tag;
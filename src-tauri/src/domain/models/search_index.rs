@@ -0,0 +1,39 @@
+/// The book is queued for indexing.
+pub const INDEX_STATUS_PENDING: &str = "pending";
+/// The book's text is currently being extracted and indexed.
+pub const INDEX_STATUS_INDEXING: &str = "indexing";
+/// The book's text is in the full-text index.
+pub const INDEX_STATUS_INDEXED: &str = "indexed";
+/// Text extraction or indexing failed; see [`BookIndexStatus::error`].
+pub const INDEX_STATUS_FAILED: &str = "failed";
+
+/// Full-text indexing state of a book.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BookIndexStatus {
+    /// The book this status belongs to.
+    pub book_id: i32,
+    /// One of `"pending"`, `"indexing"`, `"indexed"` or `"failed"`.
+    pub status: String,
+    /// Number of chapters/pages stored in the index for the book.
+    pub segment_count: i32,
+    /// Error message of the last failed indexing attempt, if any.
+    pub error: Option<String>,
+    /// ISO 8601 timestamp of the last status change.
+    pub updated_at: Option<String>,
+}
+
+/// A chapter or page matching a library-wide full-text query.
+#[derive(Debug, Clone)]
+pub struct FullTextMatch {
+    /// The book containing the match.
+    pub book_id: i32,
+    /// 0-based EPUB spine index of the matching chapter.
+    pub spine_index: Option<i32>,
+    /// 0-based PDF page index of the matching page.
+    pub page: Option<i32>,
+    /// FTS5 `snippet()` of the segment, with matches wrapped in `\u{2}` /
+    /// `\u{3}` marker characters.
+    pub snippet: String,
+    /// FTS5 `bm25()` rank; lower is more relevant.
+    pub rank: f64,
+}
@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+/// A named book series, such as "Discworld".
+#[derive(Debug, Clone, Serialize)]
+pub struct Series {
+    /// Auto-generated primary key.
+    pub id: i32,
+    /// Series name as extracted from ebook metadata. Unique, case-insensitive.
+    pub name: String,
+}
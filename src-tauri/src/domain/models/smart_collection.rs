@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+/// A manual collection whose books are added and ordered by the user.
+pub const COLLECTION_KIND_MANUAL: &str = "manual";
+/// A smart collection whose books are selected by its [`RuleGroup`].
+pub const COLLECTION_KIND_SMART: &str = "smart";
+
+/// How the rules of a [`RuleGroup`] are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleMatch {
+    /// Every rule must match (`AND`).
+    All,
+    /// At least one rule must match (`OR`).
+    Any,
+}
+
+/// A group of smart-collection rules combined with `AND` or `OR`.
+///
+/// Serialized as `{"match": "all", "rules": [...]}`. An empty `all` group
+/// matches every book; an empty `any` group matches none.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleGroup {
+    /// Combinator applied to `rules`.
+    #[serde(rename = "match")]
+    pub combinator: RuleMatch,
+    /// Conditions and nested groups.
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+/// A single entry of a [`RuleGroup`]: a condition or a nested group.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Rule {
+    Group(RuleGroup),
+    Condition(RuleCondition),
+}
+
+/// A comparison of one book field against a value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleCondition {
+    /// The book attribute to test.
+    pub field: RuleField,
+    /// The comparison to apply.
+    pub operator: RuleOperator,
+    /// Operand: a string, a number, or a two-element array for `between`.
+    /// Unused by `is_empty` / `is_not_empty`.
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+/// Book attributes a smart-collection rule can test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleField {
+    /// `books.title`.
+    Title,
+    /// Name of any of the book's authors.
+    Author,
+    /// `publishers.name`.
+    Publisher,
+    /// `books.file_type` (`"epub"`, `"pdf"`, `"mobi"`).
+    FileType,
+    /// `books.isbn`.
+    Isbn,
+    /// `books.published_date`.
+    PublishedDate,
+    /// `books.added_at`.
+    AddedAt,
+    /// `reading_progress.progress_percentage`; books never opened count as `0`.
+    Progress,
+    /// `reading_progress.last_read_at`.
+    LastReadAt,
+    /// `"unread"` (0%), `"reading"` (between 0% and 100%) or `"finished"` (100%).
+    ReadStatus,
+    /// `books.is_favorite`, compared against `true` or `false`.
+    Favorite,
+    /// `books.rating` in stars; unrated books never match a comparison.
+    Rating,
+}
+
+/// Comparisons available in smart-collection rules.
+///
+/// Text fields accept `equals`, `not_equals`, `contains`, `not_contains`,
+/// `starts_with` and `ends_with` (all case-insensitive). `progress` and
+/// `rating` accept `equals`, `not_equals`, `greater_than`, `less_than` and
+/// `between`. Date fields accept `before`, `after`, `between` and
+/// `in_last_days`. `read_status` and `favorite` accept `equals` and
+/// `not_equals`. Every field except `progress`, `read_status` and `favorite`
+/// accepts `is_empty` and `is_not_empty`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleOperator {
+    Equals,
+    NotEquals,
+    Contains,
+    NotContains,
+    StartsWith,
+    EndsWith,
+    GreaterThan,
+    LessThan,
+    Between,
+    Before,
+    After,
+    InLastDays,
+    IsEmpty,
+    IsNotEmpty,
+}
@@ -0,0 +1,25 @@
+use serde::Serialize;
+
+/// Longest tag name accepted, in characters.
+pub const MAX_TAG_LENGTH: usize = 64;
+
+/// A user- or metadata-assigned label such as "Fantasy".
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Tag {
+    /// Auto-generated primary key.
+    pub id: i32,
+    /// Tag name. Unique, case-insensitive.
+    pub name: String,
+}
+
+/// Trims a tag name and collapses inner whitespace.
+///
+/// Returns `None` for names that are blank or longer than
+/// [`MAX_TAG_LENGTH`] characters.
+pub fn normalize_tag_name(raw: &str) -> Option<String> {
+    let name = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() || name.chars().count() > MAX_TAG_LENGTH {
+        return None;
+    }
+    Some(name)
+}
@@ -1,12 +1,30 @@
 use async_trait::async_trait;
+use serde::Deserialize;
 
 use crate::domain::error::DomainError;
 use crate::domain::models::annotation::Annotation;
 use crate::domain::models::author::Author;
-use crate::domain::models::book::Book;
+use crate::domain::models::book::{Book, HydratedBook};
 use crate::domain::models::bookmark::Bookmark;
+use crate::domain::models::collection::Collection;
+use crate::domain::models::contributor::Contributor;
+use crate::domain::models::library_root::LibraryRoot;
+use crate::domain::models::library_settings::LibrarySettings;
 use crate::domain::models::publisher::Publisher;
 use crate::domain::models::reading_progress::ReadingProgress;
+use crate::domain::models::search_index::{BookIndexStatus, FullTextMatch};
+use crate::domain::models::series::Series;
+use crate::domain::models::smart_collection::RuleGroup;
+use crate::domain::models::tag::Tag;
+
+/// A contributor to link to a newly imported book.
+///
+/// Links are stored in slice order, which becomes the contributor `position`.
+pub struct NewContributorLink {
+    pub author_id: i32,
+    /// One of the role constants in [`crate::domain::models::contributor`].
+    pub role: String,
+}
 
 /// Input data for creating a new book record.
 pub struct NewBook {
@@ -18,9 +36,19 @@ pub struct NewBook {
     pub file_path: String,
     pub cover_image_path: Option<String>,
     pub checksum: Option<String>,
+    pub series_id: Option<i32>,
+    pub series_index: Option<f32>,
+    pub description: Option<String>,
+    pub language: Option<String>,
+    pub word_count: Option<i32>,
+    pub page_count: Option<i32>,
 }
 
 /// Partial update input for an existing book. Only `Some` fields are applied.
+///
+/// `rating`, `review`, `series_id`, `series_index`, `description`,
+/// `language` and `page_count` can be cleared: `Some(None)` stores `NULL`.
+#[derive(Default)]
 pub struct UpdateBook {
     pub title: Option<String>,
     pub published_date: Option<String>,
@@ -30,6 +58,93 @@ pub struct UpdateBook {
     pub file_path: Option<String>,
     pub cover_image_path: Option<String>,
     pub checksum: Option<String>,
+    pub is_favorite: Option<bool>,
+    pub rating: Option<Option<f32>>,
+    pub review: Option<Option<String>>,
+    pub series_id: Option<Option<i32>>,
+    pub series_index: Option<Option<f32>>,
+    pub description: Option<Option<String>>,
+    pub language: Option<Option<String>>,
+    pub page_count: Option<Option<i32>>,
+    /// One of the `FILE_STATUS_*` constants in
+    /// [`crate::domain::models::book`].
+    pub file_status: Option<String>,
+}
+
+/// An author to credit on a book, looked up by name and created if missing.
+pub struct AuthorCredit {
+    pub name: String,
+    pub sort_name: Option<String>,
+}
+
+/// Metadata edit applied to a book by [`BookRepository::update_with_links`].
+///
+/// `None` leaves a field unchanged. For the doubly optional fields
+/// `Some(None)` clears the value. Publishers and series are given by name
+/// and created if missing.
+#[derive(Default)]
+pub struct BookPatch {
+    pub title: Option<String>,
+    /// Replaces the book's author credits. Other contributors are kept after
+    /// the authors.
+    pub authors: Option<Vec<AuthorCredit>>,
+    pub publisher: Option<Option<String>>,
+    pub published_date: Option<Option<String>>,
+    pub isbn: Option<Option<String>>,
+    pub description: Option<Option<String>>,
+    /// Clearing the series also clears `series_index`.
+    pub series: Option<Option<String>>,
+    pub series_index: Option<Option<f32>>,
+    /// Replaces the book's tags.
+    pub tags: Option<Vec<String>>,
+}
+
+/// Criteria for listing books. Default values match every book.
+#[derive(Debug, Clone, Default)]
+pub struct BookFilter {
+    /// Only return books marked as favorites.
+    pub favorites_only: bool,
+    /// Only return books rated at least this many stars.
+    pub min_rating: Option<f32>,
+    /// Only return books of this file type (`"epub"`, `"pdf"`, `"mobi"`).
+    pub file_type: Option<String>,
+    /// Only return books with these IDs.
+    pub book_ids: Option<Vec<i32>>,
+    /// Only return books in this series.
+    pub series_id: Option<i32>,
+    /// Only return books carrying this tag.
+    pub tag_id: Option<i32>,
+}
+
+/// Sort key for book listings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookSort {
+    /// Title, case-insensitive.
+    #[default]
+    Title,
+    /// Name of the first linked author, case-insensitive.
+    Author,
+    /// Import timestamp.
+    AddedAt,
+    /// Last reading session; never-opened books sort as `NULL`.
+    LastReadAt,
+    /// Series name, then position within the series with unnumbered books
+    /// last; books outside a series sort as `NULL`.
+    Series,
+}
+
+/// A filtered, sorted window of the book listing.
+#[derive(Debug, Clone, Default)]
+pub struct BookListQuery {
+    pub filter: BookFilter,
+    pub sort: BookSort,
+    /// Sort in descending order.
+    pub descending: bool,
+    /// Number of matching books to skip.
+    pub offset: i64,
+    /// Maximum number of books to return; `None` returns all.
+    pub limit: Option<i64>,
 }
 
 /// Persistence operations for books.
@@ -46,6 +161,42 @@ pub trait BookRepository: Send + Sync {
     /// Returns [`DomainError::Database`] on query failure.
     async fn find_all(&self) -> Result<Vec<Book>, DomainError>;
 
+    /// Returns a page of books with their authors, publisher and reading
+    /// progress.
+    ///
+    /// Implementations must not issue per-book queries: books, publishers and
+    /// progress are loaded in one joined query and the page's authors in a
+    /// second.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Filter, sort order and window.
+    ///
+    /// # Returns
+    ///
+    /// The matching [`HydratedBook`]s in the requested order. Ties are
+    /// broken by book ID so pages are stable.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_hydrated(&self, query: &BookListQuery) -> Result<Vec<HydratedBook>, DomainError>;
+
+    /// Counts the books matching a [`BookFilter`].
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The listing criteria.
+    ///
+    /// # Returns
+    ///
+    /// The number of matching books, ignoring any pagination.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn count_filtered(&self, filter: &BookFilter) -> Result<i64, DomainError>;
+
     /// Returns the book with the given ID.
     ///
     /// # Arguments
@@ -122,6 +273,24 @@ pub trait BookRepository: Send + Sync {
     /// Returns [`DomainError::Database`] on query failure.
     async fn find_by_checksum(&self, checksum: &str) -> Result<Option<Book>, DomainError>;
 
+    /// Returns the book whose file is stored at the given path.
+    ///
+    /// Used by the library folder watcher to tell changed files of known
+    /// books from new ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Absolute path of the ebook file.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Book)` if a book has this `file_path`, `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_by_file_path(&self, path: &str) -> Result<Option<Book>, DomainError>;
+
     /// Returns books whose title contains the given query string.
     ///
     /// Uses a SQL `LIKE` query (`%query%`) for substring matching. Case
@@ -148,7 +317,8 @@ pub trait BookRepository: Send + Sync {
     /// # Arguments
     ///
     /// * `book` - The new book data.
-    /// * `author_ids` - Slice of `(author_id, author_name)` tuples to link.
+    /// * `contributors` - Contributors to link, in credit order.
+    /// * `tag_ids` - Tags to attach.
     /// * `publisher_id` - Optional publisher to associate.
     ///
     /// # Returns
@@ -163,9 +333,27 @@ pub trait BookRepository: Send + Sync {
     async fn import_with_links(
         &self,
         book: NewBook,
-        author_ids: &[(i32, String)],
+        contributors: &[NewContributorLink],
+        tag_ids: &[i32],
         publisher_id: Option<i32>,
     ) -> Result<Book, DomainError>;
+
+    /// Applies a metadata edit to a book in a single transaction.
+    ///
+    /// Authors, the publisher, the series and tags named in the patch are
+    /// looked up or created. Authors and publishers that were linked to the
+    /// book before the edit and are left without any book are deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The book's database ID.
+    /// * `patch` - The fields to change.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] if any statement fails; nothing is
+    /// changed in that case.
+    async fn update_with_links(&self, id: i32, patch: BookPatch) -> Result<(), DomainError>;
 }
 
 /// Persistence operations for authors.
@@ -173,9 +361,12 @@ pub trait BookRepository: Send + Sync {
 pub trait AuthorRepository: Send + Sync {
     /// Returns an existing author by name, or creates and returns a new one.
     ///
+    /// An existing author without a sort name is given `sort_name`.
+    ///
     /// # Arguments
     ///
     /// * `name` - The author's full name.
+    /// * `sort_name` - Optional sort key such as `"Le Guin, Ursula K."`.
     ///
     /// # Returns
     ///
@@ -184,9 +375,13 @@ pub trait AuthorRepository: Send + Sync {
     /// # Errors
     ///
     /// Returns [`DomainError::Database`] on query or insert failure.
-    async fn find_or_create(&self, name: &str) -> Result<Author, DomainError>;
+    async fn find_or_create(
+        &self,
+        name: &str,
+        sort_name: Option<&str>,
+    ) -> Result<Author, DomainError>;
 
-    /// Returns all authors linked to the given book.
+    /// Returns everyone credited on the given book.
     ///
     /// # Arguments
     ///
@@ -194,12 +389,14 @@ pub trait AuthorRepository: Send + Sync {
     ///
     /// # Returns
     ///
-    /// A vector of [`Author`] entities linked via `book_authors`.
+    /// A vector of [`Contributor`] entries linked via `book_authors`, ordered
+    /// by position.
     ///
     /// # Errors
     ///
     /// Returns [`DomainError::Database`] on query failure.
-    async fn get_authors_by_book(&self, book_id: i32) -> Result<Vec<Author>, DomainError>;
+    async fn get_contributors_by_book(&self, book_id: i32)
+    -> Result<Vec<Contributor>, DomainError>;
 }
 
 /// Persistence operations for publishers.
@@ -236,20 +433,203 @@ pub trait PublisherRepository: Send + Sync {
     async fn find_or_create(&self, name: &str) -> Result<Publisher, DomainError>;
 }
 
+/// Persistence operations for tags and the `book_tags` links.
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    /// Returns every tag with the number of books carrying it.
+    ///
+    /// # Returns
+    ///
+    /// `(Tag, book_count)` pairs ordered by name, case-insensitively.
+    /// Tags without books are included with a count of `0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_all_with_counts(&self) -> Result<Vec<(Tag, i64)>, DomainError>;
+
+    /// Returns the tag with the given ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The tag's database ID.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Tag)` if found, `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_by_id(&self, id: i32) -> Result<Option<Tag>, DomainError>;
+
+    /// Returns the tag with the given name, compared case-insensitively.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The tag name.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Tag)` if found, `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_by_name(&self, name: &str) -> Result<Option<Tag>, DomainError>;
+
+    /// Returns an existing tag by name (case-insensitive), or creates and
+    /// returns a new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The tag name, already normalized.
+    ///
+    /// # Returns
+    ///
+    /// The existing or newly-created [`Tag`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query or insert failure.
+    async fn find_or_create(&self, name: &str) -> Result<Tag, DomainError>;
+
+    /// Returns the number of books carrying a tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag_id` - The tag's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn count_books(&self, tag_id: i32) -> Result<i64, DomainError>;
+
+    /// Renames a tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag_id` - The tag's database ID.
+    /// * `name` - The new name, already normalized.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on update failure, including when
+    /// another tag already has the name.
+    async fn rename(&self, tag_id: i32, name: &str) -> Result<(), DomainError>;
+
+    /// Moves every book of `source_id` onto `target_id` and deletes the
+    /// source tag, in one transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_id` - The tag to merge away.
+    /// * `target_id` - The tag to keep.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on failure.
+    async fn merge(&self, source_id: i32, target_id: i32) -> Result<(), DomainError>;
+
+    /// Attaches a tag to a book. Attaching it twice is a no-op.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    /// * `tag_id` - The tag's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on insert failure.
+    async fn add_to_book(&self, book_id: i32, tag_id: i32) -> Result<(), DomainError>;
+
+    /// Detaches a tag from a book. The tag itself is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    /// * `tag_id` - The tag's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on delete failure.
+    async fn remove_from_book(&self, book_id: i32, tag_id: i32) -> Result<(), DomainError>;
+}
+
+/// Persistence operations for book series.
+#[async_trait]
+pub trait SeriesRepository: Send + Sync {
+    /// Returns the series with the given ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The series' database ID.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Series)` if found, `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_by_id(&self, id: i32) -> Result<Option<Series>, DomainError>;
+
+    /// Returns an existing series by name (case-insensitive), or creates and
+    /// returns a new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The series name.
+    ///
+    /// # Returns
+    ///
+    /// The existing or newly-created [`Series`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query or insert failure.
+    async fn find_or_create(&self, name: &str) -> Result<Series, DomainError>;
+}
+
 /// Persistence operations for the book-author many-to-many join table.
 #[async_trait]
 pub trait BookAuthorRepository: Send + Sync {
-    /// Creates a link between a book and an author.
+    /// Creates a link between a book and a contributor.
     ///
     /// # Arguments
     ///
     /// * `book_id` - The book's database ID.
     /// * `author_id` - The author's database ID.
+    /// * `role` - The contributor role, e.g. `"author"` or `"translator"`.
+    /// * `position` - 0-based credit order on the book.
     ///
     /// # Errors
     ///
     /// Returns [`DomainError::Database`] on insert failure.
-    async fn link(&self, book_id: i32, author_id: i32) -> Result<(), DomainError>;
+    async fn link(
+        &self,
+        book_id: i32,
+        author_id: i32,
+        role: &str,
+        position: i32,
+    ) -> Result<(), DomainError>;
+
+    /// Replaces every contributor link of a book in one transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    /// * `links` - The new contributors, stored in slice order as their
+    ///   `position`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn replace_all(
+        &self,
+        book_id: i32,
+        links: &[NewContributorLink],
+    ) -> Result<(), DomainError>;
 }
 
 /// Persistence operations for bookmarks.
@@ -396,3 +776,323 @@ pub struct NewReadingProgress {
     pub page_number: Option<i32>,
     pub progress_percentage: Option<f32>,
 }
+
+/// Plain text of one chapter or page to store in the full-text index.
+pub struct NewIndexedText {
+    pub spine_index: Option<i32>,
+    pub page: Option<i32>,
+    pub content: String,
+}
+
+/// Persistence operations for the library-wide full-text index.
+#[async_trait]
+pub trait SearchIndexRepository: Send + Sync {
+    /// Replaces all indexed text of a book.
+    ///
+    /// Existing segments for the book are removed and `segments` inserted in
+    /// a single transaction, so re-indexing never leaves duplicates.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    /// * `segments` - One entry per chapter or page.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn replace_book_text(
+        &self,
+        book_id: i32,
+        segments: Vec<NewIndexedText>,
+    ) -> Result<(), DomainError>;
+
+    /// Removes every segment from the full-text index and resets all statuses.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn clear(&self) -> Result<(), DomainError>;
+
+    /// Runs a full-text query against the index.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - An FTS5 `MATCH` expression.
+    /// * `limit` - Maximum number of matching segments to return.
+    ///
+    /// # Returns
+    ///
+    /// Matching segments ordered by `bm25()` rank, most relevant first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure, including malformed
+    /// `MATCH` expressions.
+    async fn search(&self, query: &str, limit: i32) -> Result<Vec<FullTextMatch>, DomainError>;
+
+    /// Inserts or updates the indexing status of a book.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    /// * `status` - One of the `INDEX_STATUS_*` values.
+    /// * `segment_count` - Number of indexed segments.
+    /// * `error` - Error message when `status` is `"failed"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn set_status(
+        &self,
+        book_id: i32,
+        status: &str,
+        segment_count: i32,
+        error: Option<&str>,
+    ) -> Result<(), DomainError>;
+
+    /// Returns the indexing status of a book.
+    ///
+    /// # Arguments
+    ///
+    /// * `book_id` - The book's database ID.
+    ///
+    /// # Returns
+    ///
+    /// `Some(BookIndexStatus)` if the book has been queued or indexed, `None`
+    /// otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_status(&self, book_id: i32) -> Result<Option<BookIndexStatus>, DomainError>;
+
+    /// Returns the indexing status of every book that has one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_all_status(&self) -> Result<Vec<BookIndexStatus>, DomainError>;
+}
+
+/// Input data for creating a new collection.
+pub struct NewCollection {
+    pub name: String,
+    pub description: Option<String>,
+    pub accent_color: Option<String>,
+    pub cover_image: Option<String>,
+    /// Rules of a smart collection; `None` creates a manual collection.
+    pub rules: Option<RuleGroup>,
+}
+
+/// Partial update input for an existing collection. Only `Some` fields are
+/// applied.
+pub struct UpdateCollection {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub accent_color: Option<String>,
+    pub cover_image: Option<String>,
+    pub rules: Option<RuleGroup>,
+}
+
+/// Persistence operations for collections and their ordered book lists.
+#[async_trait]
+pub trait CollectionRepository: Send + Sync {
+    /// Returns all collections, ordered by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_all(&self) -> Result<Vec<Collection>, DomainError>;
+
+    /// Returns the collection with the given ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The collection's database ID.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Collection)` if found, `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_by_id(&self, id: i32) -> Result<Option<Collection>, DomainError>;
+
+    /// Creates a new, empty collection and returns its generated ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection` - The collection data to insert.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on insert failure.
+    async fn insert(&self, collection: NewCollection) -> Result<i32, DomainError>;
+
+    /// Updates an existing collection with the provided fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The collection's database ID.
+    /// * `collection` - Fields to update.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn update(&self, id: i32, collection: UpdateCollection) -> Result<(), DomainError>;
+
+    /// Deletes a collection. Its book links are removed by cascade; the books
+    /// themselves are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The collection's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn delete(&self, id: i32) -> Result<(), DomainError>;
+
+    /// Appends a book to the end of a collection. Adding a book that is
+    /// already in the collection is a no-op.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - The collection's database ID.
+    /// * `book_id` - The book's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure, including when
+    /// either ID does not exist.
+    async fn add_book(&self, collection_id: i32, book_id: i32) -> Result<(), DomainError>;
+
+    /// Removes a book from a collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - The collection's database ID.
+    /// * `book_id` - The book's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn remove_book(&self, collection_id: i32, book_id: i32) -> Result<(), DomainError>;
+
+    /// Reorders the books of a collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - The collection's database ID.
+    /// * `book_ids` - Book IDs in their new order. Books of the collection
+    ///   not listed keep their relative order after the listed ones; IDs not
+    ///   in the collection are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn reorder_books(&self, collection_id: i32, book_ids: Vec<i32>) -> Result<(), DomainError>;
+
+    /// Returns the IDs of the books in a collection, in collection order.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - The collection's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_book_ids(&self, collection_id: i32) -> Result<Vec<i32>, DomainError>;
+
+    /// Evaluates smart-collection rules against the library.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - The rule group to evaluate.
+    ///
+    /// # Returns
+    ///
+    /// IDs of the matching books, ordered by title.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidInput`] if the rules cannot be compiled
+    /// and [`DomainError::Database`] on query failure.
+    async fn find_book_ids_matching(&self, rules: &RuleGroup) -> Result<Vec<i32>, DomainError>;
+}
+
+/// Persistence operations for watched library folders.
+#[async_trait]
+pub trait LibraryRootRepository: Send + Sync {
+    /// Returns all library roots, ordered by path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_all(&self) -> Result<Vec<LibraryRoot>, DomainError>;
+
+    /// Returns the library root with the given ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The root's database ID.
+    ///
+    /// # Returns
+    ///
+    /// `Some(LibraryRoot)` if found, `None` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find_by_id(&self, id: i32) -> Result<Option<LibraryRoot>, DomainError>;
+
+    /// Registers a folder and returns the new root.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Absolute, canonical path of the folder.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on insert failure, including when
+    /// the path is already registered.
+    async fn insert(&self, path: &str) -> Result<LibraryRoot, DomainError>;
+
+    /// Unregisters a folder. Books imported from it are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The root's database ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn delete(&self, id: i32) -> Result<(), DomainError>;
+}
+
+/// Persistence of the managed library settings, a single record.
+#[async_trait]
+pub trait LibrarySettingsRepository: Send + Sync {
+    /// Returns the saved settings.
+    ///
+    /// # Returns
+    ///
+    /// `Some(LibrarySettings)` once saved, `None` before.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on query failure.
+    async fn find(&self) -> Result<Option<LibrarySettings>, DomainError>;
+
+    /// Saves the settings, replacing the ones saved before.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The validated settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::Database`] on write failure.
+    async fn save(&self, settings: &LibrarySettings) -> Result<(), DomainError>;
+}
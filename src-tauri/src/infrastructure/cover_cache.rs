@@ -0,0 +1,204 @@
+//! On-disk cache of downscaled cover thumbnails.
+//!
+//! Covers are written once at import to `<cache dir>/<checksum>/`, one JPEG
+//! per [`CoverSize`]. Keying the directory by the file's SHA-256 checksum
+//! means a changed file never picks up a stale cover; the cached images are
+//! also treated as stale when the ebook file is modified after them.
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image::DynamicImage;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+
+/// JPEG quality used for every thumbnail.
+pub const COVER_JPEG_QUALITY: u8 = 85;
+
+/// The thumbnail sizes kept for each cover.
+///
+/// Each size fits the cover inside a bounding box, keeping its aspect ratio.
+/// Covers smaller than the box are not upscaled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverSize {
+    /// Small thumbnail for list rows (at most 96×144).
+    List,
+    /// Library grid tile (at most 240×360).
+    Grid,
+    /// Book detail view (at most 600×900).
+    #[default]
+    Detail,
+}
+
+impl CoverSize {
+    /// Every size, smallest first.
+    pub const ALL: [CoverSize; 3] = [CoverSize::List, CoverSize::Grid, CoverSize::Detail];
+
+    /// Maximum `(width, height)` of this size in pixels.
+    pub fn max_dimensions(self) -> (u32, u32) {
+        match self {
+            CoverSize::List => (96, 144),
+            CoverSize::Grid => (240, 360),
+            CoverSize::Detail => (600, 900),
+        }
+    }
+
+    /// File name of this size inside a cover's cache directory.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            CoverSize::List => "list.jpg",
+            CoverSize::Grid => "grid.jpg",
+            CoverSize::Detail => "detail.jpg",
+        }
+    }
+}
+
+/// Cache root set at startup by [`init_cover_cache`].
+static COVER_CACHE_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// Sets the cache root, normally the app's cache directory. Only the first
+/// call has an effect.
+pub fn init_cover_cache(dir: PathBuf) {
+    let _ = COVER_CACHE_DIR.set(dir);
+}
+
+/// Returns the cache root.
+///
+/// Falls back to the `COVER_CACHE_DIR` env var, then `./covers`, when
+/// [`init_cover_cache`] was never called (e.g. in tests).
+pub fn cover_cache_dir() -> PathBuf {
+    COVER_CACHE_DIR
+        .get()
+        .cloned()
+        .or_else(|| env::var("COVER_CACHE_DIR").ok().map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from("./covers"))
+}
+
+/// Directory holding the thumbnails of the file with the given checksum.
+pub fn cover_dir(cache_dir: &Path, checksum: &str) -> PathBuf {
+    cache_dir.join(checksum)
+}
+
+/// Path of one size, given the stored `cover_image_path` of any size.
+pub fn sized_cover_path(cover_image_path: &Path, size: CoverSize) -> PathBuf {
+    cover_image_path.with_file_name(size.file_name())
+}
+
+/// Decodes a cover image and writes every [`CoverSize`] as JPEG.
+///
+/// Files are written to a temporary name first and renamed into place, so a
+/// concurrent reader never sees a half-written thumbnail.
+///
+/// # Arguments
+///
+/// * `cache_dir` - The cache root, usually [`cover_cache_dir`].
+/// * `checksum` - SHA-256 checksum of the ebook file.
+/// * `bytes` - The cover image in any format the `image` crate can decode.
+///
+/// # Returns
+///
+/// The path of the [`CoverSize::Detail`] thumbnail, to be stored in
+/// `books.cover_image_path`.
+///
+/// # Errors
+///
+/// Returns a boxed error when the image cannot be decoded or a file cannot
+/// be written.
+pub fn write_cover_thumbnails(
+    cache_dir: &Path,
+    checksum: &str,
+    bytes: &[u8],
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    let cover = image::load_from_memory(bytes)?;
+    let dir = cover_dir(cache_dir, checksum);
+    fs::create_dir_all(&dir)?;
+
+    for size in CoverSize::ALL {
+        let mut encoded = Vec::new();
+        let thumbnail = DynamicImage::ImageRgb8(scale_to_fit(&cover, size).to_rgb8());
+        thumbnail.write_with_encoder(JpegEncoder::new_with_quality(
+            &mut encoded,
+            COVER_JPEG_QUALITY,
+        ))?;
+
+        let path = dir.join(size.file_name());
+        let partial = path.with_extension("jpg.part");
+        fs::write(&partial, &encoded)?;
+        fs::rename(&partial, &path)?;
+    }
+
+    Ok(dir.join(CoverSize::Detail.file_name()))
+}
+
+/// Whether a cached thumbnail must be regenerated: it is missing, or the
+/// ebook file was modified after it was written.
+pub fn is_stale(cover_path: &Path, book_path: &Path) -> bool {
+    let Ok(cover_modified) = fs::metadata(cover_path).and_then(|m| m.modified()) else {
+        return true;
+    };
+    fs::metadata(book_path)
+        .and_then(|m| m.modified())
+        .is_ok_and(|book_modified| book_modified > cover_modified)
+}
+
+/// Deletes the cached thumbnails of a checksum. Missing directories are
+/// ignored.
+pub fn remove_cover_dir(cache_dir: &Path, checksum: &str) -> std::io::Result<()> {
+    match fs::remove_dir_all(cover_dir(cache_dir, checksum)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Moves the cached thumbnails of a file whose checksum changed, e.g. after
+/// its metadata was rewritten. Replaces thumbnails already cached for the
+/// new checksum; a missing source directory is ignored.
+pub fn rename_cover_dir(
+    cache_dir: &Path,
+    old_checksum: &str,
+    new_checksum: &str,
+) -> std::io::Result<()> {
+    let from = cover_dir(cache_dir, old_checksum);
+    if !from.exists() {
+        return Ok(());
+    }
+    remove_cover_dir(cache_dir, new_checksum)?;
+    fs::rename(from, cover_dir(cache_dir, new_checksum))
+}
+
+/// Moves a book's cached thumbnails after its file checksum changed, e.g.
+/// after a rehash or a metadata rewrite.
+///
+/// Returns the book's new `cover_image_path`, or `None` when the book has no
+/// cover, the checksum is unchanged or the thumbnails could not be moved. The
+/// thumbnails are older than the rewritten file, so `get_cover` refreshes them
+/// on the next request either way.
+pub fn rekey_cover(
+    cover_image_path: Option<&str>,
+    old_checksum: Option<&str>,
+    new_checksum: &str,
+) -> Option<String> {
+    let old_checksum = old_checksum.filter(|old| *old != new_checksum)?;
+    cover_image_path?;
+    let cache_dir = cover_cache_dir();
+    if let Err(e) = rename_cover_dir(&cache_dir, old_checksum, new_checksum) {
+        log::warn!("Failed to move cached cover: {}", e);
+        return None;
+    }
+    let cover = cover_dir(&cache_dir, new_checksum).join(CoverSize::Detail.file_name());
+    Some(cover.to_string_lossy().to_string())
+}
+
+/// Downscales an image to fit a size's bounding box; never upscales.
+fn scale_to_fit(cover: &DynamicImage, size: CoverSize) -> DynamicImage {
+    let (max_width, max_height) = size.max_dimensions();
+    if cover.width() <= max_width && cover.height() <= max_height {
+        return cover.clone();
+    }
+    cover.resize(max_width, max_height, FilterType::Lanczos3)
+}
@@ -0,0 +1,4 @@
+DROP TRIGGER IF EXISTS trg_books_delete_fts;
+DROP INDEX IF EXISTS idx_book_index_status_status;
+DROP TABLE IF EXISTS book_index_status;
+DROP TABLE IF EXISTS book_text_fts;
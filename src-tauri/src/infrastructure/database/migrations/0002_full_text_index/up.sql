@@ -0,0 +1,25 @@
+-- Plain text of every EPUB chapter / PDF page, one row per segment.
+CREATE VIRTUAL TABLE book_text_fts USING fts5(
+    content,
+    book_id     UNINDEXED,
+    spine_index UNINDEXED,
+    page        UNINDEXED,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TABLE book_index_status (
+    book_id       INTEGER PRIMARY KEY,
+    status        TEXT NOT NULL DEFAULT 'pending',
+    segment_count INTEGER NOT NULL DEFAULT 0,
+    error         TEXT,
+    updated_at    TEXT DEFAULT (datetime('now')),
+    FOREIGN KEY (book_id) REFERENCES books(book_id) ON DELETE CASCADE
+);
+CREATE INDEX idx_book_index_status_status ON book_index_status(status);
+
+-- Virtual tables cannot take part in foreign keys, so drop a deleted book's
+-- text explicitly.
+CREATE TRIGGER trg_books_delete_fts AFTER DELETE ON books
+BEGIN
+    DELETE FROM book_text_fts WHERE book_id = old.book_id;
+END;
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_collection_books_position;
+DROP INDEX IF EXISTS idx_collection_books_book;
+
+DROP TABLE IF EXISTS collection_books;
+DROP TABLE IF EXISTS collections;
@@ -0,0 +1,2 @@
+ALTER TABLE collections ADD COLUMN kind TEXT NOT NULL DEFAULT 'manual';
+ALTER TABLE collections ADD COLUMN rules TEXT;
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_books_favorite;
+ALTER TABLE books DROP COLUMN review;
+ALTER TABLE books DROP COLUMN rating;
+ALTER TABLE books DROP COLUMN is_favorite;
@@ -0,0 +1,4 @@
+ALTER TABLE books ADD COLUMN is_favorite BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE books ADD COLUMN rating REAL CHECK (rating IS NULL OR (rating BETWEEN 0 AND 5 AND rating * 2 = CAST(rating * 2 AS INTEGER)));
+ALTER TABLE books ADD COLUMN review TEXT;
+CREATE INDEX idx_books_favorite ON books(is_favorite);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_reading_progress_last_read;
+DROP INDEX IF EXISTS idx_books_file_type;
+DROP INDEX IF EXISTS idx_books_added_at;
+DROP INDEX IF EXISTS idx_books_title;
@@ -0,0 +1,4 @@
+CREATE INDEX idx_books_title ON books(title COLLATE NOCASE);
+CREATE INDEX idx_books_added_at ON books(added_at);
+CREATE INDEX idx_books_file_type ON books(file_type);
+CREATE INDEX idx_reading_progress_last_read ON reading_progress(last_read_at);
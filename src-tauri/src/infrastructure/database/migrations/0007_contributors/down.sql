@@ -0,0 +1,18 @@
+DROP INDEX IF EXISTS idx_book_authors_position;
+DROP INDEX IF EXISTS idx_book_authors_author;
+
+CREATE TABLE book_authors_old (
+    book_id   INTEGER NOT NULL,
+    author_id INTEGER NOT NULL,
+    PRIMARY KEY (book_id, author_id),
+    FOREIGN KEY (book_id)   REFERENCES books(book_id)      ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES authors(author_id) ON DELETE CASCADE
+);
+
+INSERT OR IGNORE INTO book_authors_old (book_id, author_id)
+SELECT book_id, author_id FROM book_authors ORDER BY book_id, position;
+
+DROP TABLE book_authors;
+ALTER TABLE book_authors_old RENAME TO book_authors;
+
+ALTER TABLE authors DROP COLUMN sort_name;
@@ -0,0 +1,26 @@
+ALTER TABLE authors ADD COLUMN sort_name TEXT;
+
+CREATE TABLE book_authors_new (
+    book_id   INTEGER NOT NULL,
+    author_id INTEGER NOT NULL,
+    role      TEXT    NOT NULL DEFAULT 'author',
+    position  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (book_id, author_id, role),
+    FOREIGN KEY (book_id)   REFERENCES books(book_id)      ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES authors(author_id) ON DELETE CASCADE
+);
+
+-- Existing links are all authors; keep their insertion order as the position.
+INSERT INTO book_authors_new (book_id, author_id, role, position)
+SELECT ba.book_id,
+       ba.author_id,
+       'author',
+       (SELECT COUNT(*) FROM book_authors prev
+         WHERE prev.book_id = ba.book_id AND prev.rowid < ba.rowid)
+FROM book_authors ba;
+
+DROP TABLE book_authors;
+ALTER TABLE book_authors_new RENAME TO book_authors;
+
+CREATE INDEX idx_book_authors_author ON book_authors(author_id);
+CREATE INDEX idx_book_authors_position ON book_authors(book_id, position);
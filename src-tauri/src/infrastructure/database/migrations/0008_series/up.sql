@@ -0,0 +1,8 @@
+CREATE TABLE series (
+    series_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name      TEXT NOT NULL UNIQUE COLLATE NOCASE
+);
+
+ALTER TABLE books ADD COLUMN series_id INTEGER REFERENCES series(series_id) ON DELETE SET NULL;
+ALTER TABLE books ADD COLUMN series_index REAL;
+CREATE INDEX idx_books_series ON books(series_id, series_index);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_book_tags_tag;
+DROP TABLE IF EXISTS book_tags;
+DROP TABLE IF EXISTS tags;
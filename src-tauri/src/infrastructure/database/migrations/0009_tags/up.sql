@@ -0,0 +1,13 @@
+CREATE TABLE tags (
+    tag_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name   TEXT NOT NULL UNIQUE COLLATE NOCASE
+);
+
+CREATE TABLE book_tags (
+    book_id INTEGER NOT NULL,
+    tag_id  INTEGER NOT NULL,
+    PRIMARY KEY (book_id, tag_id),
+    FOREIGN KEY (book_id) REFERENCES books(book_id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id)  REFERENCES tags(tag_id)   ON DELETE CASCADE
+);
+CREATE INDEX idx_book_tags_tag ON book_tags(tag_id);
@@ -0,0 +1,4 @@
+ALTER TABLE books DROP COLUMN page_count;
+ALTER TABLE books DROP COLUMN word_count;
+ALTER TABLE books DROP COLUMN language;
+ALTER TABLE books DROP COLUMN description;
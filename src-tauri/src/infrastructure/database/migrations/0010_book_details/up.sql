@@ -0,0 +1,4 @@
+ALTER TABLE books ADD COLUMN description TEXT;
+ALTER TABLE books ADD COLUMN language TEXT;
+ALTER TABLE books ADD COLUMN word_count INTEGER CHECK (word_count IS NULL OR word_count >= 0);
+ALTER TABLE books ADD COLUMN page_count INTEGER CHECK (page_count IS NULL OR page_count >= 0);
@@ -0,0 +1,3 @@
+DROP INDEX idx_books_file_path;
+ALTER TABLE books DROP COLUMN file_status;
+DROP TABLE library_roots;
@@ -0,0 +1,8 @@
+CREATE TABLE library_roots (
+    root_id  INTEGER PRIMARY KEY AUTOINCREMENT,
+    path     TEXT NOT NULL UNIQUE,
+    added_at TEXT DEFAULT (datetime('now'))
+);
+
+ALTER TABLE books ADD COLUMN file_status TEXT NOT NULL DEFAULT 'ok';
+CREATE INDEX idx_books_file_path ON books(file_path);
@@ -0,0 +1,6 @@
+CREATE TABLE library_settings (
+    setting_id      INTEGER PRIMARY KEY CHECK (setting_id = 1),
+    library_dir     TEXT,
+    naming_template TEXT NOT NULL,
+    import_mode     TEXT NOT NULL DEFAULT 'copy'
+);
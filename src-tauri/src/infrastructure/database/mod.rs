@@ -3,3 +3,4 @@
 #[allow(clippy::module_inception)]
 pub mod database;
 pub mod models;
+pub mod smart_query;
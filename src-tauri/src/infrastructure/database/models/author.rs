@@ -9,6 +9,7 @@ use diesel::prelude::*;
 pub struct AuthorRow {
     pub author_id: Option<i32>,
     pub name: String,
+    pub sort_name: Option<String>,
 }
 
 /// Insertable row for creating a new author record.
@@ -16,6 +17,7 @@ pub struct AuthorRow {
 #[diesel(table_name = authors)]
 pub struct NewAuthorRow<'a> {
     pub name: &'a str,
+    pub sort_name: Option<&'a str>,
 }
 
 /// Converts a `AuthorRow` into a domain [`Author`](crate::domain::models::author::Author).
@@ -24,6 +26,7 @@ impl From<AuthorRow> for crate::domain::models::author::Author {
         crate::domain::models::author::Author {
             id: row.author_id.unwrap_or(0),
             name: row.name,
+            sort_name: row.sort_name,
         }
     }
 }
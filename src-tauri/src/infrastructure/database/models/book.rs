@@ -17,6 +17,16 @@ pub struct BookRow {
     pub cover_image_path: Option<String>,
     pub checksum: Option<String>,
     pub added_at: Option<String>,
+    pub is_favorite: bool,
+    pub rating: Option<f32>,
+    pub review: Option<String>,
+    pub series_id: Option<i32>,
+    pub series_index: Option<f32>,
+    pub description: Option<String>,
+    pub language: Option<String>,
+    pub word_count: Option<i32>,
+    pub page_count: Option<i32>,
+    pub file_status: String,
 }
 
 /// Insertable row for creating a new book record.
@@ -31,20 +41,39 @@ pub struct NewBookRow<'a> {
     pub file_path: &'a str,
     pub cover_image_path: Option<&'a str>,
     pub checksum: Option<&'a str>,
+    pub series_id: Option<i32>,
+    pub series_index: Option<f32>,
+    pub description: Option<&'a str>,
+    pub language: Option<&'a str>,
+    pub word_count: Option<i32>,
+    pub page_count: Option<i32>,
 }
 
 /// Partial update row for modifying an existing book.
+///
+/// `published_date`, `publisher_id`, `isbn`, `rating`, `review`,
+/// `series_id`, `series_index`, `description`, `language` and `page_count`
+/// are doubly optional: `Some(None)` writes `NULL`.
 #[derive(AsChangeset, PartialEq, Debug)]
 #[diesel(table_name = books)]
 pub struct UpdateBookRow<'a> {
     pub title: Option<&'a str>,
-    pub published_date: Option<&'a str>,
-    pub publisher_id: Option<i32>,
-    pub isbn: Option<&'a str>,
+    pub published_date: Option<Option<&'a str>>,
+    pub publisher_id: Option<Option<i32>>,
+    pub isbn: Option<Option<&'a str>>,
     pub file_type: Option<&'a str>,
     pub file_path: Option<&'a str>,
     pub cover_image_path: Option<&'a str>,
     pub checksum: Option<&'a str>,
+    pub is_favorite: Option<bool>,
+    pub rating: Option<Option<f32>>,
+    pub review: Option<Option<&'a str>>,
+    pub series_id: Option<Option<i32>>,
+    pub series_index: Option<Option<f32>>,
+    pub description: Option<Option<&'a str>>,
+    pub language: Option<Option<&'a str>>,
+    pub page_count: Option<Option<i32>>,
+    pub file_status: Option<&'a str>,
 }
 
 /// Converts a domain [`Book`](crate::domain::models::book::Book) into a `BookRow`.
@@ -61,6 +90,16 @@ impl From<&crate::domain::models::book::Book> for BookRow {
             cover_image_path: book.cover_image_path.clone(),
             checksum: book.checksum.clone(),
             added_at: book.added_at.clone(),
+            is_favorite: book.is_favorite,
+            rating: book.rating,
+            review: book.review.clone(),
+            series_id: book.series_id,
+            series_index: book.series_index,
+            description: book.description.clone(),
+            language: book.language.clone(),
+            word_count: book.word_count,
+            page_count: book.page_count,
+            file_status: book.file_status.clone(),
         }
     }
 }
@@ -79,6 +118,16 @@ impl From<BookRow> for crate::domain::models::book::Book {
             cover_image_path: row.cover_image_path,
             checksum: row.checksum,
             added_at: row.added_at,
+            is_favorite: row.is_favorite,
+            rating: row.rating,
+            review: row.review,
+            series_id: row.series_id,
+            series_index: row.series_index,
+            description: row.description,
+            language: row.language,
+            word_count: row.word_count,
+            page_count: row.page_count,
+            file_status: row.file_status,
         }
     }
 }
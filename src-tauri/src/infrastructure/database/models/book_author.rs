@@ -2,10 +2,14 @@ use crate::infrastructure::database::models::schema::book_authors;
 use diesel::prelude::*;
 
 /// Diesel row for the `book_authors` many-to-many join table.
+///
+/// A person credited in several roles has one row per role.
 #[derive(Queryable, Identifiable, PartialEq, Insertable, Debug)]
 #[diesel(table_name = book_authors)]
-#[diesel(primary_key(book_id, author_id))]
+#[diesel(primary_key(book_id, author_id, role))]
 pub struct BookAuthorRow {
     pub book_id: i32,
     pub author_id: i32,
+    pub role: String,
+    pub position: i32,
 }
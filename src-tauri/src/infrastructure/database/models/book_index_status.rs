@@ -0,0 +1,39 @@
+use crate::infrastructure::database::models::schema::book_index_status;
+use diesel::prelude::*;
+
+/// Diesel queryable row for the `book_index_status` table.
+#[derive(Queryable, Identifiable, Selectable, PartialEq, Debug)]
+#[diesel(table_name = book_index_status)]
+#[diesel(primary_key(book_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct BookIndexStatusRow {
+    pub book_id: i32,
+    pub status: String,
+    pub segment_count: i32,
+    pub error: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// Insertable row for creating or upserting a book's index status.
+#[derive(Insertable, PartialEq, Debug)]
+#[diesel(table_name = book_index_status)]
+pub struct NewBookIndexStatusRow<'a> {
+    pub book_id: i32,
+    pub status: &'a str,
+    pub segment_count: i32,
+    pub error: Option<&'a str>,
+    pub updated_at: Option<&'a str>,
+}
+
+/// Converts a `BookIndexStatusRow` into a domain [`BookIndexStatus`](crate::domain::models::search_index::BookIndexStatus).
+impl From<BookIndexStatusRow> for crate::domain::models::search_index::BookIndexStatus {
+    fn from(row: BookIndexStatusRow) -> Self {
+        crate::domain::models::search_index::BookIndexStatus {
+            book_id: row.book_id,
+            status: row.status,
+            segment_count: row.segment_count,
+            error: row.error,
+            updated_at: row.updated_at,
+        }
+    }
+}
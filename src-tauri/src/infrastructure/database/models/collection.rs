@@ -0,0 +1,69 @@
+use crate::infrastructure::database::models::schema::{collection_books, collections};
+use diesel::prelude::*;
+
+/// Diesel queryable row for the `collections` table.
+#[derive(Queryable, Identifiable, Selectable, PartialEq, Debug)]
+#[diesel(table_name = collections)]
+#[diesel(primary_key(collection_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct CollectionRow {
+    pub collection_id: Option<i32>,
+    pub name: String,
+    pub description: Option<String>,
+    pub accent_color: Option<String>,
+    pub cover_image: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub kind: String,
+    pub rules: Option<String>,
+}
+
+/// Insertable row for creating a new collection.
+#[derive(Insertable, PartialEq, Debug)]
+#[diesel(table_name = collections)]
+pub struct NewCollectionRow<'a> {
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+    pub accent_color: Option<&'a str>,
+    pub cover_image: Option<&'a str>,
+    pub kind: &'a str,
+    pub rules: Option<&'a str>,
+}
+
+/// Partial update row for modifying an existing collection.
+#[derive(AsChangeset, PartialEq, Debug)]
+#[diesel(table_name = collections)]
+pub struct UpdateCollectionRow<'a> {
+    pub name: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub accent_color: Option<&'a str>,
+    pub cover_image: Option<&'a str>,
+    pub rules: Option<&'a str>,
+    pub updated_at: Option<&'a str>,
+}
+
+/// Insertable row for the `collection_books` join table.
+#[derive(Insertable, PartialEq, Debug)]
+#[diesel(table_name = collection_books)]
+pub struct NewCollectionBookRow {
+    pub collection_id: i32,
+    pub book_id: i32,
+    pub position: i32,
+}
+
+/// Converts a `CollectionRow` into a domain [`Collection`](crate::domain::models::collection::Collection).
+impl From<CollectionRow> for crate::domain::models::collection::Collection {
+    fn from(row: CollectionRow) -> Self {
+        crate::domain::models::collection::Collection {
+            id: row.collection_id.unwrap_or(0),
+            name: row.name,
+            description: row.description,
+            accent_color: row.accent_color,
+            cover_image: row.cover_image,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            kind: row.kind,
+            rules: row.rules.and_then(|json| serde_json::from_str(&json).ok()),
+        }
+    }
+}
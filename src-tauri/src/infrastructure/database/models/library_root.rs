@@ -0,0 +1,25 @@
+use crate::infrastructure::database::models::schema::library_roots;
+use diesel::prelude::*;
+
+/// Diesel queryable row for the `library_roots` table.
+#[derive(Queryable, Identifiable, Selectable, PartialEq, Debug)]
+#[diesel(table_name = library_roots)]
+#[diesel(primary_key(root_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct LibraryRootRow {
+    pub root_id: Option<i32>,
+    pub path: String,
+    pub added_at: Option<String>,
+}
+
+/// Converts a `LibraryRootRow` into a domain
+/// [`LibraryRoot`](crate::domain::models::library_root::LibraryRoot).
+impl From<LibraryRootRow> for crate::domain::models::library_root::LibraryRoot {
+    fn from(row: LibraryRootRow) -> Self {
+        crate::domain::models::library_root::LibraryRoot {
+            id: row.root_id.unwrap_or(0),
+            path: row.path,
+            added_at: row.added_at,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::infrastructure::database::models::schema::library_settings;
+use diesel::prelude::*;
+
+/// Diesel queryable row for the single-row `library_settings` table.
+#[derive(Queryable, Identifiable, Selectable, PartialEq, Debug)]
+#[diesel(table_name = library_settings)]
+#[diesel(primary_key(setting_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct LibrarySettingsRow {
+    pub setting_id: Option<i32>,
+    pub library_dir: Option<String>,
+    pub naming_template: String,
+    pub import_mode: String,
+}
+
+/// Insertable row for saving the settings; `setting_id` is always 1.
+#[derive(Insertable, PartialEq, Debug)]
+#[diesel(table_name = library_settings)]
+pub struct NewLibrarySettingsRow<'a> {
+    pub setting_id: i32,
+    pub library_dir: Option<&'a str>,
+    pub naming_template: &'a str,
+    pub import_mode: &'a str,
+}
+
+/// Converts a `LibrarySettingsRow` into domain
+/// [`LibrarySettings`](crate::domain::models::library_settings::LibrarySettings).
+impl From<LibrarySettingsRow> for crate::domain::models::library_settings::LibrarySettings {
+    fn from(row: LibrarySettingsRow) -> Self {
+        crate::domain::models::library_settings::LibrarySettings {
+            library_dir: row.library_dir,
+            naming_template: row.naming_template,
+            import_mode: row.import_mode,
+        }
+    }
+}
@@ -8,7 +8,13 @@ pub mod annotation;
 pub mod author;
 pub mod book;
 pub mod book_author;
+pub mod book_index_status;
 pub mod bookmark;
+pub mod collection;
+pub mod library_root;
+pub mod library_settings;
 pub mod publisher;
 pub mod reading_progress;
 pub mod schema;
+pub mod series;
+pub mod tag;
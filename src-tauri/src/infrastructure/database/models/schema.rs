@@ -22,13 +22,33 @@ diesel::table! {
     authors (author_id) {
         author_id -> Nullable<Integer>,
         name -> Text,
+        sort_name -> Nullable<Text>,
     }
 }
 
 diesel::table! {
-    book_authors (book_id, author_id) {
+    book_authors (book_id, author_id, role) {
         book_id -> Integer,
         author_id -> Integer,
+        role -> Text,
+        position -> Integer,
+    }
+}
+
+diesel::table! {
+    book_index_status (book_id) {
+        book_id -> Integer,
+        status -> Text,
+        segment_count -> Integer,
+        error -> Nullable<Text>,
+        updated_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    book_tags (book_id, tag_id) {
+        book_id -> Integer,
+        tag_id -> Integer,
     }
 }
 
@@ -55,6 +75,56 @@ diesel::table! {
         cover_image_path -> Nullable<Text>,
         checksum -> Nullable<Text>,
         added_at -> Nullable<Text>,
+        is_favorite -> Bool,
+        rating -> Nullable<Float>,
+        review -> Nullable<Text>,
+        series_id -> Nullable<Integer>,
+        series_index -> Nullable<Float>,
+        description -> Nullable<Text>,
+        language -> Nullable<Text>,
+        word_count -> Nullable<Integer>,
+        page_count -> Nullable<Integer>,
+        file_status -> Text,
+    }
+}
+
+diesel::table! {
+    collection_books (collection_id, book_id) {
+        collection_id -> Integer,
+        book_id -> Integer,
+        position -> Integer,
+        added_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    collections (collection_id) {
+        collection_id -> Nullable<Integer>,
+        name -> Text,
+        description -> Nullable<Text>,
+        accent_color -> Nullable<Text>,
+        cover_image -> Nullable<Text>,
+        created_at -> Nullable<Text>,
+        updated_at -> Nullable<Text>,
+        kind -> Text,
+        rules -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    library_roots (root_id) {
+        root_id -> Nullable<Integer>,
+        path -> Text,
+        added_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    library_settings (setting_id) {
+        setting_id -> Nullable<Integer>,
+        library_dir -> Nullable<Text>,
+        naming_template -> Text,
+        import_mode -> Text,
     }
 }
 
@@ -77,19 +147,47 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    series (series_id) {
+        series_id -> Nullable<Integer>,
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    tags (tag_id) {
+        tag_id -> Nullable<Integer>,
+        name -> Text,
+    }
+}
+
 diesel::joinable!(annotations -> books (book_id));
 diesel::joinable!(book_authors -> authors (author_id));
 diesel::joinable!(book_authors -> books (book_id));
+diesel::joinable!(book_index_status -> books (book_id));
+diesel::joinable!(book_tags -> books (book_id));
+diesel::joinable!(book_tags -> tags (tag_id));
 diesel::joinable!(bookmarks -> books (book_id));
 diesel::joinable!(books -> publishers (publisher_id));
+diesel::joinable!(books -> series (series_id));
+diesel::joinable!(collection_books -> books (book_id));
+diesel::joinable!(collection_books -> collections (collection_id));
 diesel::joinable!(reading_progress -> books (book_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     annotations,
     authors,
     book_authors,
+    book_index_status,
+    book_tags,
     bookmarks,
     books,
+    collection_books,
+    collections,
+    library_roots,
+    library_settings,
     publishers,
     reading_progress,
+    series,
+    tags,
 );
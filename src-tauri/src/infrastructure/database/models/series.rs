@@ -0,0 +1,22 @@
+use crate::infrastructure::database::models::schema::series;
+use diesel::prelude::*;
+
+/// Diesel queryable row for the `series` table.
+#[derive(Queryable, Identifiable, Selectable, PartialEq, Debug)]
+#[diesel(table_name = series)]
+#[diesel(primary_key(series_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct SeriesRow {
+    pub series_id: Option<i32>,
+    pub name: String,
+}
+
+/// Converts a `SeriesRow` into a domain [`Series`](crate::domain::models::series::Series).
+impl From<SeriesRow> for crate::domain::models::series::Series {
+    fn from(row: SeriesRow) -> Self {
+        crate::domain::models::series::Series {
+            id: row.series_id.unwrap_or(0),
+            name: row.name,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::infrastructure::database::models::schema::{book_tags, tags};
+use diesel::prelude::*;
+
+/// Diesel queryable row for the `tags` table.
+#[derive(Queryable, Identifiable, Selectable, PartialEq, Debug)]
+#[diesel(table_name = tags)]
+#[diesel(primary_key(tag_id))]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TagRow {
+    pub tag_id: Option<i32>,
+    pub name: String,
+}
+
+/// Diesel row for the `book_tags` many-to-many join table.
+#[derive(Queryable, Identifiable, Insertable, PartialEq, Debug)]
+#[diesel(table_name = book_tags)]
+#[diesel(primary_key(book_id, tag_id))]
+pub struct BookTagRow {
+    pub book_id: i32,
+    pub tag_id: i32,
+}
+
+/// Converts a `TagRow` into a domain [`Tag`](crate::domain::models::tag::Tag).
+impl From<TagRow> for crate::domain::models::tag::Tag {
+    fn from(row: TagRow) -> Self {
+        crate::domain::models::tag::Tag {
+            id: row.tag_id.unwrap_or(0),
+            name: row.name,
+        }
+    }
+}
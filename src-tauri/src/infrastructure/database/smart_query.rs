@@ -0,0 +1,362 @@
+//! Compiles smart-collection rules into a SQL `WHERE` clause with bind
+//! parameters.
+//!
+//! Column references come from a fixed whitelist and every user-supplied
+//! value is bound, so rules can never inject SQL. The clause is evaluated
+//! against [`SMART_QUERY_FROM`], which aliases `books` as `b`, `publishers`
+//! as `p` and `reading_progress` as `rp`.
+
+use chrono::{NaiveDateTime, TimeDelta};
+use serde_json::Value;
+
+use crate::domain::error::DomainError;
+use crate::domain::models::smart_collection::{
+    Rule, RuleCondition, RuleField, RuleGroup, RuleMatch, RuleOperator,
+};
+
+/// `FROM` clause the compiled conditions are written against.
+pub const SMART_QUERY_FROM: &str = "FROM books b \
+     LEFT JOIN publishers p ON p.publisher_id = b.publisher_id \
+     LEFT JOIN reading_progress rp ON rp.book_id = b.book_id";
+
+/// Maximum nesting depth of rule groups.
+const MAX_DEPTH: usize = 8;
+
+/// A value bound to a `?` placeholder of a compiled clause.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleBind {
+    Text(String),
+    Double(f64),
+}
+
+/// A compiled `WHERE` clause and its bind values, in placeholder order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledRules {
+    pub sql: String,
+    pub binds: Vec<RuleBind>,
+}
+
+/// Compiles a rule group into a SQL condition over [`SMART_QUERY_FROM`].
+///
+/// # Arguments
+///
+/// * `group` - The root rule group.
+/// * `now` - Reference time for `in_last_days`.
+///
+/// # Returns
+///
+/// The condition and its bind values.
+///
+/// # Errors
+///
+/// Returns [`DomainError::InvalidInput`] when an operator is not supported
+/// for its field, a value has the wrong type, or groups nest deeper than
+/// eight levels.
+pub fn compile_rules(group: &RuleGroup, now: NaiveDateTime) -> Result<CompiledRules, DomainError> {
+    let mut binds = Vec::new();
+    let sql = compile_group(group, now, 0, &mut binds)?;
+    Ok(CompiledRules { sql, binds })
+}
+
+fn compile_group(
+    group: &RuleGroup,
+    now: NaiveDateTime,
+    depth: usize,
+    binds: &mut Vec<RuleBind>,
+) -> Result<String, DomainError> {
+    if depth >= MAX_DEPTH {
+        return Err(invalid("rule groups are nested too deeply"));
+    }
+    if group.rules.is_empty() {
+        return Ok(match group.combinator {
+            RuleMatch::All => "1".into(),
+            RuleMatch::Any => "0".into(),
+        });
+    }
+
+    let parts = group
+        .rules
+        .iter()
+        .map(|rule| match rule {
+            Rule::Group(inner) => compile_group(inner, now, depth + 1, binds),
+            Rule::Condition(condition) => compile_condition(condition, now, binds),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let joiner = match group.combinator {
+        RuleMatch::All => " AND ",
+        RuleMatch::Any => " OR ",
+    };
+
+    Ok(format!("({})", parts.join(joiner)))
+}
+
+fn compile_condition(
+    condition: &RuleCondition,
+    now: NaiveDateTime,
+    binds: &mut Vec<RuleBind>,
+) -> Result<String, DomainError> {
+    use RuleField::*;
+
+    match condition.field {
+        Title => text_condition("b.title", condition, binds),
+        FileType => text_condition("b.file_type", condition, binds),
+        Isbn => text_condition("b.isbn", condition, binds),
+        Publisher => text_condition("p.name", condition, binds),
+        Author => author_condition(condition, binds),
+        PublishedDate => date_condition("b.published_date", condition, now, binds),
+        AddedAt => date_condition("b.added_at", condition, now, binds),
+        LastReadAt => date_condition("rp.last_read_at", condition, now, binds),
+        Progress => numeric_condition("COALESCE(rp.progress_percentage, 0)", condition, binds),
+        Rating => rating_condition(condition, binds),
+        ReadStatus => read_status_condition(condition),
+        Favorite => favorite_condition(condition),
+    }
+}
+
+/// Case-insensitive text comparison; `NULL` is treated as the empty string.
+fn text_condition(
+    column: &str,
+    condition: &RuleCondition,
+    binds: &mut Vec<RuleBind>,
+) -> Result<String, DomainError> {
+    let column = format!("COALESCE({}, '')", column);
+    let (sql, negate) = text_predicate(&column, condition, binds)?;
+    Ok(if negate { format!("NOT ({})", sql) } else { sql })
+}
+
+/// Builds the positive form of a text predicate and whether it is negated.
+fn text_predicate(
+    column: &str,
+    condition: &RuleCondition,
+    binds: &mut Vec<RuleBind>,
+) -> Result<(String, bool), DomainError> {
+    use RuleOperator::*;
+
+    let (pattern, negate) = match condition.operator {
+        IsEmpty => return Ok((format!("{} = ''", column), false)),
+        IsNotEmpty => return Ok((format!("{} = ''", column), true)),
+        Equals | NotEquals => {
+            binds.push(RuleBind::Text(text_value(condition)?));
+            return Ok((
+                format!("{} = ? COLLATE NOCASE", column),
+                condition.operator == NotEquals,
+            ));
+        }
+        Contains => (format!("%{}%", escape_like(&text_value(condition)?)), false),
+        NotContains => (format!("%{}%", escape_like(&text_value(condition)?)), true),
+        StartsWith => (format!("{}%", escape_like(&text_value(condition)?)), false),
+        EndsWith => (format!("%{}", escape_like(&text_value(condition)?)), false),
+        _ => return Err(unsupported(condition)),
+    };
+
+    binds.push(RuleBind::Text(pattern));
+    Ok((format!("{} LIKE ? ESCAPE '\\'", column), negate))
+}
+
+/// Matches books by the name of any of their authors. Editors, translators
+/// and illustrators are not considered.
+fn author_condition(
+    condition: &RuleCondition,
+    binds: &mut Vec<RuleBind>,
+) -> Result<String, DomainError> {
+    let linked = "SELECT 1 FROM book_authors ba \
+                  JOIN authors a ON a.author_id = ba.author_id \
+                  WHERE ba.book_id = b.book_id AND ba.role = 'author'";
+
+    match condition.operator {
+        RuleOperator::IsEmpty => return Ok(format!("NOT EXISTS ({})", linked)),
+        RuleOperator::IsNotEmpty => return Ok(format!("EXISTS ({})", linked)),
+        _ => {}
+    }
+
+    let (predicate, negate) = text_predicate("a.name", condition, binds)?;
+    let exists = format!("EXISTS ({} AND {})", linked, predicate);
+    Ok(if negate { format!("NOT {}", exists) } else { exists })
+}
+
+/// Compares a `YYYY-MM-DD[ HH:MM:SS]` text column. `before`/`after` compare
+/// the text directly; `between` compares calendar dates, inclusive.
+fn date_condition(
+    column: &str,
+    condition: &RuleCondition,
+    now: NaiveDateTime,
+    binds: &mut Vec<RuleBind>,
+) -> Result<String, DomainError> {
+    use RuleOperator::*;
+
+    match condition.operator {
+        IsEmpty => Ok(format!("COALESCE({}, '') = ''", column)),
+        IsNotEmpty => Ok(format!("COALESCE({}, '') <> ''", column)),
+        Before => {
+            binds.push(RuleBind::Text(text_value(condition)?));
+            Ok(format!("{} < ?", column))
+        }
+        After => {
+            binds.push(RuleBind::Text(text_value(condition)?));
+            Ok(format!("{} > ?", column))
+        }
+        Between => {
+            let (from, to) = range_value(condition, Value::as_str)?;
+            binds.push(RuleBind::Text(from.to_string()));
+            binds.push(RuleBind::Text(to.to_string()));
+            Ok(format!("date({}) BETWEEN date(?) AND date(?)", column))
+        }
+        InLastDays => {
+            let days = number_value(condition)?;
+            // `as` saturates, so huge day counts fail `try_seconds` instead
+            // of wrapping.
+            let since = (days.is_finite() && days >= 0.0)
+                .then(|| TimeDelta::try_seconds((days * 86_400.0) as i64))
+                .flatten()
+                .and_then(|span| now.checked_sub_signed(span))
+                .ok_or_else(|| {
+                    invalid(&format!(
+                        "{:?} {:?} expects a non-negative number of days, got {}",
+                        condition.field, condition.operator, condition.value
+                    ))
+                })?;
+            binds.push(RuleBind::Text(since.format("%Y-%m-%d %H:%M:%S").to_string()));
+            Ok(format!("{} >= ?", column))
+        }
+        _ => Err(unsupported(condition)),
+    }
+}
+
+/// Compares the star rating; `is_empty` matches unrated books.
+fn rating_condition(
+    condition: &RuleCondition,
+    binds: &mut Vec<RuleBind>,
+) -> Result<String, DomainError> {
+    match condition.operator {
+        RuleOperator::IsEmpty => Ok("b.rating IS NULL".into()),
+        RuleOperator::IsNotEmpty => Ok("b.rating IS NOT NULL".into()),
+        _ => numeric_condition("b.rating", condition, binds),
+    }
+}
+
+/// Compares a numeric expression. Reading progress is passed as
+/// `COALESCE(rp.progress_percentage, 0)` so unread books count as `0`.
+fn numeric_condition(
+    column: &str,
+    condition: &RuleCondition,
+    binds: &mut Vec<RuleBind>,
+) -> Result<String, DomainError> {
+    use RuleOperator::*;
+
+    let op = match condition.operator {
+        Equals => "=",
+        NotEquals => "<>",
+        GreaterThan => ">",
+        LessThan => "<",
+        Between => {
+            let (from, to) = range_value(condition, Value::as_f64)?;
+            binds.push(RuleBind::Double(from));
+            binds.push(RuleBind::Double(to));
+            return Ok(format!("{} BETWEEN ? AND ?", column));
+        }
+        _ => return Err(unsupported(condition)),
+    };
+
+    binds.push(RuleBind::Double(number_value(condition)?));
+    Ok(format!("{} {} ?", column, op))
+}
+
+/// Matches favorites when compared with `true`, other books with `false`.
+fn favorite_condition(condition: &RuleCondition) -> Result<String, DomainError> {
+    let wanted = match &condition.value {
+        Value::Bool(b) => *b,
+        _ => {
+            return Err(invalid(&format!(
+                "{:?} {:?} expects true or false",
+                condition.field, condition.operator
+            )));
+        }
+    };
+
+    match condition.operator {
+        RuleOperator::Equals => Ok(format!("b.is_favorite = {}", wanted as i32)),
+        RuleOperator::NotEquals => Ok(format!("b.is_favorite <> {}", wanted as i32)),
+        _ => Err(unsupported(condition)),
+    }
+}
+
+/// Maps `"unread"`, `"reading"` and `"finished"` onto progress ranges.
+fn read_status_condition(condition: &RuleCondition) -> Result<String, DomainError> {
+    let column = "COALESCE(rp.progress_percentage, 0)";
+    let predicate = match text_value(condition)?.to_lowercase().as_str() {
+        "unread" => format!("{} <= 0", column),
+        "reading" => format!("({0} > 0 AND {0} < 100)", column),
+        "finished" => format!("{} >= 100", column),
+        other => return Err(invalid(&format!("unknown read status '{}'", other))),
+    };
+
+    match condition.operator {
+        RuleOperator::Equals => Ok(predicate),
+        RuleOperator::NotEquals => Ok(format!("NOT ({})", predicate)),
+        _ => Err(unsupported(condition)),
+    }
+}
+
+fn text_value(condition: &RuleCondition) -> Result<String, DomainError> {
+    match &condition.value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        _ => Err(invalid(&format!(
+            "{:?} {:?} expects a string value",
+            condition.field, condition.operator
+        ))),
+    }
+}
+
+fn number_value(condition: &RuleCondition) -> Result<f64, DomainError> {
+    let number = match &condition.value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    };
+    number.ok_or_else(|| {
+        invalid(&format!(
+            "{:?} {:?} expects a numeric value",
+            condition.field, condition.operator
+        ))
+    })
+}
+
+fn range_value<'a, T>(
+    condition: &'a RuleCondition,
+    get: fn(&'a Value) -> Option<T>,
+) -> Result<(T, T), DomainError> {
+    if let Value::Array(items) = &condition.value
+        && let [from, to] = items.as_slice()
+        && let (Some(from), Some(to)) = (get(from), get(to))
+    {
+        return Ok((from, to));
+    }
+    Err(invalid(&format!(
+        "{:?} between expects a two-element array",
+        condition.field
+    )))
+}
+
+/// Escapes `%`, `_` and `\` for a `LIKE ... ESCAPE '\'` pattern.
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn unsupported(condition: &RuleCondition) -> DomainError {
+    invalid(&format!(
+        "operator {:?} is not supported for field {:?}",
+        condition.operator, condition.field
+    ))
+}
+
+fn invalid(message: &str) -> DomainError {
+    DomainError::InvalidInput(format!("Invalid smart collection rule: {}", message))
+}
@@ -0,0 +1,176 @@
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use tokio::task::JoinError;
+use walkdir::WalkDir;
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+/// File extensions of the bundles unpacked on import (ZIP and comic book
+/// ZIP).
+pub const ARCHIVE_EXTENSIONS: [&str; 2] = ["zip", "cbz"];
+
+/// Contents of the `mimetype` entry that marks a ZIP file as an EPUB.
+const EPUB_MIMETYPE: &str = "application/epub+zip";
+
+/// Largest entry extracted from a bundle, as a guard against ZIP bombs.
+pub const MAX_ENTRY_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// An entry of a bundle and where it was extracted to.
+#[derive(Debug)]
+pub struct ExtractedEntry {
+    /// The entry's path inside the archive, e.g. `"books/dune.epub"`.
+    pub name: String,
+    /// The extracted file, or why the entry could not be extracted.
+    pub result: io::Result<PathBuf>,
+}
+
+/// Returns `true` if the extension (without the dot) is a bundle format.
+pub fn is_archive_extension(ext: &str) -> bool {
+    ARCHIVE_EXTENSIONS
+        .iter()
+        .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+}
+
+/// Recursively scans a directory for `.zip` and `.cbz` files.
+///
+/// Runs directory traversal on a blocking thread to avoid stalling the async
+/// runtime.
+///
+/// # Arguments
+///
+/// * `dir` - Directory to scan recursively.
+///
+/// # Returns
+///
+/// A vector of absolute paths to matching archives.
+pub async fn scan_archives<P: AsRef<Path> + Send + 'static>(
+    dir: P,
+) -> Result<Vec<PathBuf>, JoinError> {
+    tokio::task::spawn_blocking(move || {
+        WalkDir::new(dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .filter(|p| {
+                p.extension()
+                    .and_then(|s| s.to_str())
+                    .map(is_archive_extension)
+                    .unwrap_or(false)
+            })
+            .collect()
+    })
+    .await
+}
+
+/// Checks whether a ZIP file is really an EPUB, i.e. has a `mimetype` entry
+/// reading `application/epub+zip`, whatever its extension.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or is not a ZIP
+/// archive.
+pub async fn is_epub_container(
+    path: PathBuf,
+) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    tokio::task::spawn_blocking(move || {
+        let mut archive = ZipArchive::new(File::open(&path)?)?;
+        let mut entry = match archive.by_name("mimetype") {
+            Ok(entry) => entry,
+            Err(ZipError::FileNotFound) => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        let mut mimetype = String::new();
+        entry.by_ref().take(64).read_to_string(&mut mimetype)?;
+        Ok(mimetype.trim() == EPUB_MIMETYPE)
+    })
+    .await?
+}
+
+/// Extracts the entries of a ZIP file accepted by `wanted` into a
+/// directory, keeping their folder structure.
+///
+/// Entries whose path would leave `dest` (absolute paths, `..`), folders,
+/// macOS resource forks (`__MACOSX/`, `._*`) and entries rejected by
+/// `wanted` are skipped. Existing files are never overwritten: such entries,
+/// and entries larger than [`MAX_ENTRY_BYTES`], are returned with an error.
+///
+/// # Arguments
+///
+/// * `path` - The archive.
+/// * `dest` - Directory to extract into; created as needed.
+/// * `wanted` - Decides by entry path which entries to extract.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or is not a ZIP
+/// archive. Failures of single entries are reported in their
+/// [`ExtractedEntry`].
+pub async fn extract_entries(
+    path: PathBuf,
+    dest: PathBuf,
+    wanted: fn(&Path) -> bool,
+) -> Result<Vec<ExtractedEntry>, Box<dyn std::error::Error + Send + Sync>> {
+    tokio::task::spawn_blocking(move || {
+        let mut archive = ZipArchive::new(File::open(&path)?)?;
+        let mut extracted = Vec::new();
+
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)?;
+            if entry.is_dir() {
+                continue;
+            }
+            let Some(relative) = entry.enclosed_name() else {
+                continue;
+            };
+            let is_resource_fork = relative.starts_with("__MACOSX")
+                || relative
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with("._"));
+            if is_resource_fork || !wanted(&relative) {
+                continue;
+            }
+
+            let name = entry.name().to_string();
+            let result = if entry.size() > MAX_ENTRY_BYTES {
+                Err(io::Error::other(format!(
+                    "Entry is larger than {} bytes",
+                    MAX_ENTRY_BYTES
+                )))
+            } else {
+                write_entry(&mut entry, &dest.join(&relative))
+            };
+            extracted.push(ExtractedEntry { name, result });
+        }
+
+        Ok(extracted)
+    })
+    .await?
+}
+
+/// Writes an entry's contents to a new file, removing it again when the
+/// entry cannot be read completely.
+fn write_entry(entry: &mut impl Read, target: &Path) -> io::Result<PathBuf> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = File::create_new(target)?;
+    // The declared size may lie; never write more than the limit.
+    let copied = io::copy(&mut entry.take(MAX_ENTRY_BYTES + 1), &mut file);
+    match copied {
+        Ok(bytes) if bytes <= MAX_ENTRY_BYTES => Ok(target.to_path_buf()),
+        Ok(_) => {
+            let _ = fs::remove_file(target);
+            Err(io::Error::other(format!(
+                "Entry is larger than {} bytes",
+                MAX_ENTRY_BYTES
+            )))
+        }
+        Err(e) => {
+            let _ = fs::remove_file(target);
+            Err(e)
+        }
+    }
+}
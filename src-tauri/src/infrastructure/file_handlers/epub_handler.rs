@@ -1,14 +1,32 @@
 use base64::{Engine as _, engine::general_purpose};
+use once_cell::sync::Lazy;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
 use rbook::Epub;
+use rbook::epub::manifest::EpubManifestEntry;
+use rbook::epub::metadata::{DetachedEpubMetaEntry, EpubMetaEntry, EpubMetadata};
+use rbook::epub::toc::EpubTocEntry;
+use rbook::input::Batch;
 use regex::Regex;
-use scraper::{Html, Selector};
+use scraper::{Html, Node, Selector};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
 use std::path::{Path, PathBuf};
 use tokio::task::JoinError;
 use walkdir::WalkDir;
+use zip::ZipArchive;
 
-use crate::infrastructure::database::database::connect_from_pool;
-use crate::infrastructure::file_handlers::BookMetadata;
+use crate::domain::models::contributor::{
+    ROLE_AUTHOR, marc_relator_for_role, role_from_marc_relator,
+};
+use crate::infrastructure::file_handlers::{
+    BookMetadata, BookTextSegment, ParsedContributor, SearchHit, SearchResults, TocEntry,
+    author_names, authors_as_contributors, clean_description, count_words, estimate_page_count,
+    normalize_language_tag, parse_series_index, sort_name_for, split_tag_list,
+};
 use crate::utils::file::compute_checksum;
+use crate::utils::isbn::normalize_isbn;
+use crate::utils::search::{find_matches, html_to_text};
 
 /// Marker struct for EPUB-specific operations.
 pub struct EpubHandler;
@@ -45,10 +63,49 @@ pub async fn scan_epubs<P: AsRef<Path> + Send + 'static>(
     .await
 }
 
+/// Reads the series name and position from EPUB 3 `belongs-to-collection`
+/// metadata (untyped or with `collection-type` "series"), falling back to
+/// Calibre's `calibre:series` / `calibre:series_index` meta tags.
+fn epub_series(metadata: &EpubMetadata<'_>) -> (Option<String>, Option<f32>) {
+    let collection = metadata
+        .by_property("belongs-to-collection")
+        .filter(|entry| !entry.value().trim().is_empty())
+        .find(|entry| {
+            entry
+                .refinements()
+                .by_property("collection-type")
+                .next()
+                .is_none_or(|kind| kind.value().trim() == "series")
+        });
+    if let Some(entry) = collection {
+        let index = entry
+            .refinements()
+            .by_property("group-position")
+            .next()
+            .and_then(|position| parse_series_index(position.value()));
+        return (Some(entry.value().trim().to_string()), index);
+    }
+
+    let name = metadata
+        .by_property("calibre:series")
+        .map(|entry| entry.value().trim().to_string())
+        .find(|name| !name.is_empty());
+    let index = match name {
+        Some(_) => metadata
+            .by_property("calibre:series_index")
+            .next()
+            .and_then(|entry| parse_series_index(entry.value())),
+        None => None,
+    };
+    (name, index)
+}
+
 /// Parses metadata from an EPUB file.
 ///
-/// Extracts title, authors, publishers, publication date, ISBN, cover image,
-/// and computes a SHA-256 checksum. Defaults to "Unknown Author" / "Unknown
+/// Extracts title, contributors, publishers, publication date, ISBN, series,
+/// subjects, description, language and cover image, and computes a SHA-256
+/// checksum. The words of every chapter are counted to estimate a page count
+/// (see [`estimate_page_count`]). Defaults to "Unknown Author" / "Unknown
 /// Publisher" / "Unknown Title" when metadata fields are missing.
 ///
 /// # Arguments
@@ -78,7 +135,41 @@ pub async fn parse_epub_meta(
             .map(|t| t.value().to_string())
             .unwrap_or_else(|| "Unknown Title".to_string());
 
-        let mut authors: Vec<String> = metadata.creators().map(|c| c.value().to_string()).collect();
+        // Creators default to the author role; contributors are only kept
+        // when their role is one we track (this skips e.g. "bkp" entries that
+        // conversion tools add for themselves).
+        let creators = metadata.creators().map(|c| {
+            let role = c.main_role().and_then(|r| role_from_marc_relator(r.code()));
+            (c, Some(role.unwrap_or(ROLE_AUTHOR)))
+        });
+        let others = metadata.contributors().map(|c| {
+            let role = c.main_role().and_then(|r| role_from_marc_relator(r.code()));
+            (c, role)
+        });
+
+        let mut contributors: Vec<ParsedContributor> = Vec::new();
+        for (entry, role) in creators.chain(others) {
+            let (Some(role), name) = (role, entry.value().trim()) else {
+                continue;
+            };
+            if name.is_empty()
+                || contributors
+                    .iter()
+                    .any(|c| c.name == name && c.role == role)
+            {
+                continue;
+            }
+            contributors.push(ParsedContributor {
+                name: name.to_string(),
+                sort_name: entry
+                    .file_as()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .or_else(|| sort_name_for(name)),
+                role: role.to_string(),
+            });
+        }
 
         let mut publishers: Vec<String> = metadata
             .publishers()
@@ -89,16 +180,35 @@ pub async fn parse_epub_meta(
             publishers.push("Unknown Publisher".to_string());
         }
 
-        if authors.is_empty() {
-            authors.push("Unknown Author".to_string());
+        if contributors.is_empty() {
+            contributors = authors_as_contributors(&[]);
         }
+        let authors = author_names(&contributors);
 
         let published_date = metadata.published().map(|d| d.to_string());
 
+        let (series, series_index) = epub_series(&metadata);
+
+        let subjects: Vec<String> = metadata.tags().map(|t| t.value().to_string()).collect();
+        let tags = split_tag_list(&subjects);
+
+        let description = metadata
+            .descriptions()
+            .find_map(|d| clean_description(d.value()));
+        let language = metadata
+            .languages()
+            .find_map(|l| normalize_language_tag(l.value()));
+
+        let word_count: usize = chapter_segments(&book)
+            .iter()
+            .map(|segment| count_words(&segment.text))
+            .sum();
+        let page_count = estimate_page_count(word_count);
+
+        // ISBNs appear as `urn:isbn:` URNs or as plain identifier values.
         let isbn = metadata
             .identifiers()
-            .find(|i| i.value().starts_with("urn:isbn:"))
-            .map(|i| i.value().to_string());
+            .find_map(|i| normalize_isbn(i.value()));
 
         let cover_data = if let Some(cover_image) = book.manifest().cover_image() {
             let mime_type = cover_image.kind().as_str().to_string();
@@ -113,9 +223,17 @@ pub async fn parse_epub_meta(
         Ok(BookMetadata {
             title,
             authors,
+            contributors,
             publishers,
             published_date,
             isbn,
+            series,
+            series_index,
+            tags,
+            description,
+            language,
+            word_count: i32::try_from(word_count).ok(),
+            page_count: i32::try_from(page_count).ok(),
             file_path: path,
             cover_data,
             checksum,
@@ -124,20 +242,335 @@ pub async fn parse_epub_meta(
     .await?
 }
 
+/// The tracked role of a `dc:creator` or `dc:contributor` entry, read from
+/// its EPUB 3 `role` refinement or EPUB 2 `opf:role` attribute. Creators
+/// without a role are authors, as in [`parse_epub_meta`].
+fn credited_role(entry: &EpubMetaEntry<'_>) -> Option<&'static str> {
+    let code = entry
+        .refinements()
+        .by_property("role")
+        .next()
+        .map(|role| role.value())
+        .or_else(|| entry.attributes().get_value("opf:role"));
+    match code {
+        Some(code) => role_from_marc_relator(code),
+        None if entry.property().as_str() == "dc:creator" => Some(ROLE_AUTHOR),
+        None => None,
+    }
+}
+
+/// Whether an entry holds series information that [`epub_series`] reads.
+fn is_series_entry(entry: &EpubMetaEntry<'_>) -> bool {
+    match entry.property().as_str() {
+        "belongs-to-collection" => entry
+            .refinements()
+            .by_property("collection-type")
+            .next()
+            .is_none_or(|kind| kind.value().trim() == "series"),
+        "calibre:series" | "calibre:series_index" => true,
+        _ => false,
+    }
+}
+
+/// Writes metadata into the package document (OPF) of an EPUB file.
+///
+/// The title, contributors with their roles, publisher, publication date,
+/// ISBN, subjects, series, description and language of `metadata` replace
+/// the file's entries; fields without a value leave the file's entries as
+/// they are. Contributors whose role the library does not track (e.g. the
+/// `bkp` credit of conversion tools) are kept. ISBN identifiers are replaced
+/// by a `urn:isbn:` identifier; when the package's unique identifier is an
+/// ISBN, its value is updated in place so its ID stays referenced. Series
+/// are written both as EPUB 3 `belongs-to-collection` metadata and as
+/// Calibre's `calibre:series` tags. `cover_data`, if any, becomes the cover
+/// image; the previous cover stays in the manifest since pages may still
+/// show it.
+///
+/// The new file is written next to the original under a `.part` name,
+/// synced and then renamed over it, so a failed write never leaves a
+/// truncated EPUB behind. Chapters and other resources are copied as they
+/// are; the table of contents is not regenerated.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+/// * `metadata` - The metadata to write. `file_path`, `checksum`,
+///   `word_count` and `page_count` are ignored.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened, is not a valid
+/// EPUB, or the new file cannot be written or moved into place.
+pub async fn embed_epub_metadata(
+    path: String,
+    metadata: BookMetadata,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    tokio::task::spawn_blocking(move || {
+        let partial = Path::new(&path).with_extension("epub.part");
+        let written = (|| -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            let mut book = Epub::open(&path)?;
+            apply_embedded_metadata(&mut book, metadata);
+
+            let file = std::fs::File::create(&partial)?;
+            let writer = book
+                .write()
+                .keep_orphans(true)
+                .generate_toc(false)
+                .write(std::io::BufWriter::new(file))?;
+            writer
+                .into_inner()
+                .map_err(|e| e.into_error())?
+                .sync_all()?;
+            // The archive keeps the original open; close it before replacing
+            // the file, which Windows would refuse otherwise.
+            drop(book);
+            std::fs::rename(&partial, &path)?;
+            Ok(())
+        })();
+
+        if written.is_err() {
+            let _ = std::fs::remove_file(&partial);
+        }
+        written
+    })
+    .await?
+}
+
+/// Replaces the metadata entries of an open EPUB; see
+/// [`embed_epub_metadata`].
+fn apply_embedded_metadata(book: &mut Epub, metadata: BookMetadata) {
+    book.metadata_mut().retain(|entry| {
+        let property = entry.property();
+        let replaced = match property.as_str() {
+            "dc:creator" | "dc:contributor" => {
+                !metadata.contributors.is_empty() && credited_role(&entry).is_some()
+            }
+            _ => metadata.series.is_some() && is_series_entry(&entry),
+        };
+        !replaced
+    });
+
+    let isbn = metadata.isbn.as_deref().and_then(normalize_isbn);
+    let mut isbn_in_unique_identifier = false;
+    if let Some(isbn) = &isbn {
+        let unique_id = book.package().unique_identifier().to_string();
+        book.metadata_mut().retain(|entry| {
+            entry.property().as_str() != "dc:identifier"
+                || entry.id() == Some(unique_id.as_str())
+                || normalize_isbn(entry.value()).is_none()
+        });
+        if let Some(mut unique) = book.metadata_mut().by_id_mut(&unique_id)
+            && normalize_isbn(unique.as_view().value()).is_some()
+        {
+            unique.set_value(format!("urn:isbn:{}", isbn));
+            isbn_in_unique_identifier = true;
+        }
+    }
+
+    let mut editor = book.edit();
+    if !metadata.title.trim().is_empty() {
+        editor = editor
+            .clear_meta("dc:title")
+            .title(metadata.title.trim().to_string());
+    }
+    for contributor in &metadata.contributors {
+        let mut entry = DetachedEpubMetaEntry::creator(contributor.name.clone())
+            .role(marc_relator_for_role(&contributor.role));
+        if let Some(sort_name) = &contributor.sort_name {
+            entry = entry.file_as(sort_name.clone());
+        }
+        editor = if contributor.role == ROLE_AUTHOR {
+            editor.creator(entry)
+        } else {
+            editor.contributor(entry)
+        };
+    }
+    if let Some(publisher) = metadata
+        .publishers
+        .iter()
+        .find(|name| !name.trim().is_empty() && name.as_str() != "Unknown Publisher")
+    {
+        editor = editor
+            .clear_meta("dc:publisher")
+            .publisher(publisher.clone());
+    }
+    if let Some(date) = metadata.published_date {
+        editor = editor.published_date(date);
+    }
+    if let Some(isbn) = isbn
+        && !isbn_in_unique_identifier
+    {
+        editor = editor.identifier(format!("urn:isbn:{}", isbn));
+    }
+    if !metadata.tags.is_empty() {
+        editor = editor
+            .clear_meta("dc:subject")
+            .tag(Batch(metadata.tags.into_iter()));
+    }
+    if let Some(series) = metadata.series {
+        let mut collection = DetachedEpubMetaEntry::meta("belongs-to-collection")
+            .value(series.clone())
+            .refinement(DetachedEpubMetaEntry::meta("collection-type").value("series"));
+        let mut calibre = vec![DetachedEpubMetaEntry::meta_name("calibre:series").value(series)];
+        if let Some(index) = metadata.series_index {
+            collection = collection
+                .refinement(DetachedEpubMetaEntry::meta("group-position").value(index.to_string()));
+            calibre.push(
+                DetachedEpubMetaEntry::meta_name("calibre:series_index").value(index.to_string()),
+            );
+        }
+        editor = editor.meta(collection).meta(Batch(calibre.into_iter()));
+    }
+    if let Some(description) = metadata.description {
+        editor = editor.clear_meta("dc:description").description(description);
+    }
+    if let Some(language) = metadata.language {
+        editor = editor.clear_meta("dc:language").language(language);
+    }
+    if let Some((bytes, mime_type)) = metadata.cover_data {
+        // A fresh name, so replacing the cover twice never reuses an href.
+        let extension = match mime_type.as_str() {
+            "image/png" => "png",
+            "image/gif" => "gif",
+            "image/webp" => "webp",
+            _ => "jpg",
+        };
+        let href = format!("cover-{}.{}", chrono::Utc::now().timestamp(), extension);
+        editor = editor.cover_image((href, bytes));
+    }
+    editor.modified_now();
+}
+
+/// A spine (reading-order) entry of an EPUB, as listed to the frontend.
+#[derive(Serialize, Clone)]
+pub struct EpubSpineItem {
+    /// 0-based position in the spine; used to address chapters.
+    pub index: usize,
+    /// The spine entry's `idref` (manifest item ID).
+    pub idref: String,
+    /// Resolved href of the referenced manifest resource.
+    pub href: String,
+    /// MIME type of the referenced resource.
+    pub media_type: String,
+    /// Whether the entry is part of the primary (linear) reading order.
+    pub linear: bool,
+    /// Uncompressed size of the resource in bytes.
+    pub length: u64,
+}
+
+/// A single rendered EPUB chapter.
+#[derive(Serialize, Clone)]
+pub struct EpubChapter {
+    /// 0-based spine index of the chapter.
+    pub index: usize,
+    /// The spine entry's `idref` (manifest item ID).
+    pub idref: String,
+    /// Resolved href of the chapter resource.
+    pub href: String,
+    /// Sanitized `<body>` inner HTML. Images are linked through the
+    /// [`BOOK_SCHEME`] protocol when a book ID is known, and inlined as base64
+    /// data URIs otherwise. Empty for spine items that are not XHTML documents.
+    pub html: String,
+    /// The chapter's styles: the `<style>` elements of its `<head>` and its
+    /// `<link rel="stylesheet">` elements, pointed at the [`BOOK_SCHEME`]
+    /// protocol when a book ID is known and inlined as `<style>` elements
+    /// otherwise. Empty when the chapter has no styles.
+    pub styles: String,
+}
+
+/// Name of the custom URI scheme that serves EPUB manifest resources to the
+/// webview. Registered in `main.rs`.
+pub const BOOK_SCHEME: &str = "book";
+
+/// Characters percent-encoded in each path segment of a [`book_resource_url`]:
+/// everything but letters, digits and `-._~`.
+const URL_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Builds the URL under which a manifest resource of a library book is served
+/// by the [`BOOK_SCHEME`] protocol.
+///
+/// The book ID is carried in the path rather than the host because Windows and
+/// Android webviews only accept custom schemes as `http://<scheme>.localhost/`.
+/// Keeping the resource's directory layout in the path also lets relative
+/// `url(...)` references inside stylesheets resolve through the same scheme.
+///
+/// # Arguments
+///
+/// * `book_id` - The book's database ID.
+/// * `href` - Absolute manifest href of the resource (e.g. `/EPUB/img/a.png`).
+///
+/// # Returns
+///
+/// `book://localhost/<book_id>/<href>` on macOS and Linux, or
+/// `http://book.localhost/<book_id>/<href>` on Windows and Android.
+pub fn book_resource_url(book_id: i32, href: &str) -> String {
+    // Decoding first keeps hrefs that are already percent-encoded from being
+    // encoded twice.
+    let href = href
+        .trim_start_matches('/')
+        .split('/')
+        .map(|segment| {
+            utf8_percent_encode(
+                &percent_decode_str(segment).decode_utf8_lossy(),
+                URL_SEGMENT,
+            )
+            .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    if cfg!(any(windows, target_os = "android")) {
+        format!("http://{}.localhost/{}/{}", BOOK_SCHEME, book_id, href)
+    } else {
+        format!("{}://localhost/{}/{}", BOOK_SCHEME, book_id, href)
+    }
+}
+
+/// Splits the path of a [`BOOK_SCHEME`] request into the book ID and the
+/// percent-decoded resource href; the inverse of [`book_resource_url`].
+///
+/// Returns `None` unless the path has the form `<book_id>/<href>` with a
+/// numeric ID, a non-empty href and valid UTF-8 escapes.
+pub fn parse_book_resource_path(path: &str) -> Option<(i32, String)> {
+    let (id, href) = path.trim_start_matches('/').split_once('/')?;
+    let book_id = id.parse::<i32>().ok()?;
+    let href = percent_decode_str(href).decode_utf8().ok()?;
+    (!href.is_empty()).then(|| (book_id, href.into_owned()))
+}
+
+// TODO: Avoid using regex
+/// Matches `<img src="...">` attributes for image inlining.
+static IMG_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)(<img[^>]*?src=["'])([^"']+)(["'][^>]*?>)"#).unwrap());
+
+/// Matches SVG `<image href="...">` / `xlink:href` attributes for image inlining.
+static IMAGE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)(<image[^>]*?(?:xlink:)?href=["'])([^"']+)(["'][^>]*?>)"#).unwrap()
+});
+
 /// Reads and concatenates the full HTML content of an EPUB file.
 ///
 /// Iterates through the spine items, extracts `<body>` inner HTML, and
-/// replaces relative image `src` attributes with inline base64 data URIs
-/// so the resulting HTML is self-contained.
+/// rewrites relative image `src` attributes. With a `book_id` they point at
+/// the [`BOOK_SCHEME`] protocol; without one the images are inlined as base64
+/// data URIs so the resulting HTML is self-contained.
+///
+/// Prefer [`get_epub_spine`] and [`get_epub_chapters`] for large books, which
+/// only render the chapters on screen.
 ///
 /// # Arguments
 ///
 /// * `path` - Absolute path to the EPUB file.
+/// * `book_id` - Database ID of the book, used to build `book://` image URLs.
+///   `None` falls back to inline data URIs.
 ///
 /// # Returns
 ///
 /// A single HTML string containing the concatenated body content of all
-/// spine items, with embedded base64 images.
+/// spine items.
 ///
 /// # Errors
 ///
@@ -145,86 +578,18 @@ pub async fn parse_epub_meta(
 /// fails to parse.
 pub async fn get_epub_content(
     path: &str,
+    book_id: Option<i32>,
 ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
     let path_str = path.to_string();
     tokio::task::spawn_blocking(move || {
         let epub = Epub::open(&path_str).map_err(|e| e.to_string())?;
         let mut combined_html = String::new();
-        // TODO: Avoid using regex
-        let img_re = Regex::new(r#"(?i)(<img[^>]*?src=["'])([^"']+)(["'][^>]*?>)"#).unwrap();
-        let image_re =
-            Regex::new(r#"(?i)(<image[^>]*?(?:xlink:)?href=["'])([^"']+)(["'][^>]*?>)"#).unwrap();
-
-        let spine = epub.spine().iter().collect::<Vec<_>>();
 
-        for item_ref in spine {
+        for item_ref in epub.spine().iter() {
             if let Some(resource) = epub.manifest().by_id(item_ref.idref())
-                && resource.kind().as_str() == "application/xhtml+xml"
-                && let Ok(content) = epub.read_resource_str(resource.resource())
+                && let Some(body) = render_chapter_body(&epub, &resource, book_id)
             {
-                let content_img_processed =
-                    img_re.replace_all(&content, |caps: &regex::Captures| {
-                        let prefix = &caps[1];
-                        let src = &caps[2];
-                        let suffix = &caps[3];
-
-                        if src.starts_with("data:") || src.starts_with("http") {
-                            return caps[0].to_string();
-                        }
-
-                        let current_href = resource.href().as_str();
-                        let resolved_href = resolve_path(current_href, src);
-
-                        if let Some(image_resource) =
-                            epub.manifest().by_href(&resolved_href)
-                            && let Ok(image_bytes) = image_resource.read_bytes()
-                        {
-                            let encoded =
-                                general_purpose::STANDARD.encode(&image_bytes);
-                            let kind = image_resource.kind();
-                            let mime_type = kind.as_str();
-                            let data_url =
-                                format!("data:{};base64,{}", mime_type, encoded);
-                            return format!("{}{}{}", prefix, data_url, suffix);
-                        }
-                        caps[0].to_string()
-                    });
-
-                let content_final = image_re.replace_all(
-                    &content_img_processed,
-                    |caps: &regex::Captures| {
-                        let prefix = &caps[1];
-                        let src = &caps[2];
-                        let suffix = &caps[3];
-
-                        if src.starts_with("data:") || src.starts_with("http") {
-                            return caps[0].to_string();
-                        }
-
-                        let current_href = resource.href().as_str();
-                        let resolved_href = resolve_path(current_href, src);
-
-                        if let Some(image_resource) =
-                            epub.manifest().by_href(&resolved_href)
-                            && let Ok(image_bytes) = image_resource.read_bytes()
-                        {
-                            let encoded =
-                                general_purpose::STANDARD.encode(&image_bytes);
-                            let kind = image_resource.kind();
-                            let mime_type = kind.as_str();
-                            let data_url =
-                                format!("data:{};base64,{}", mime_type, encoded);
-                            return format!("{}{}{}", prefix, data_url, suffix);
-                        }
-                        caps[0].to_string()
-                    },
-                );
-
-                let document = Html::parse_document(&content_final);
-                let body_selector = Selector::parse("body").unwrap();
-                if let Some(body_node) = document.select(&body_selector).next() {
-                    combined_html.push_str(&body_node.inner_html());
-                }
+                combined_html.push_str(&body);
             }
         }
         Ok(combined_html)
@@ -232,6 +597,592 @@ pub async fn get_epub_content(
     .await?
 }
 
+/// Lists the spine (reading order) of an EPUB file.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+///
+/// # Returns
+///
+/// One [`EpubSpineItem`] per spine entry, in reading order, including the
+/// uncompressed length of each referenced resource as recorded in the ZIP
+/// central directory, so no chapter is decompressed.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or is not a valid
+/// EPUB.
+pub async fn get_epub_spine(
+    path: &str,
+) -> Result<Vec<EpubSpineItem>, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let epub = Epub::open(&path_str).map_err(|e| e.to_string())?;
+        let sizes = entry_sizes(&path_str)?;
+
+        let items = epub
+            .spine()
+            .iter()
+            .enumerate()
+            .map(|(index, item_ref)| {
+                let resource = epub.manifest().by_id(item_ref.idref());
+                EpubSpineItem {
+                    index,
+                    idref: item_ref.idref().to_string(),
+                    href: resource
+                        .as_ref()
+                        .map(|r| r.href().as_str().to_string())
+                        .unwrap_or_default(),
+                    media_type: resource
+                        .as_ref()
+                        .map(|r| r.media_type().to_string())
+                        .unwrap_or_default(),
+                    linear: item_ref.is_linear(),
+                    length: resource
+                        .and_then(|r| {
+                            let href = r.href();
+                            let name = percent_decode_str(href.as_str().trim_start_matches('/'))
+                                .decode_utf8_lossy();
+                            sizes.get(name.as_ref()).copied()
+                        })
+                        .unwrap_or(0),
+                }
+            })
+            .collect();
+
+        Ok(items)
+    })
+    .await?
+}
+
+/// Reads the uncompressed size of every entry of an EPUB's ZIP container
+/// from its central directory, keyed by entry name.
+fn entry_sizes(
+    path: &str,
+) -> Result<HashMap<String, u64>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let mut sizes = HashMap::new();
+    for index in 0..archive.len() {
+        let entry = archive.by_index_raw(index)?;
+        sizes.insert(entry.name().to_string(), entry.size());
+    }
+    Ok(sizes)
+}
+
+/// Builds the table of contents of an EPUB file.
+///
+/// Uses the EPUB 3 navigation document, falling back to the EPUB 2 NCX when
+/// the book has no nav document. Each entry is mapped onto the spine so the
+/// reader can jump straight to the chapter.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+///
+/// # Returns
+///
+/// The top-level [`TocEntry`] nodes with their children nested below them.
+/// Empty when the EPUB has no table of contents.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or is not a valid
+/// EPUB.
+pub async fn get_epub_toc(
+    path: &str,
+) -> Result<Vec<TocEntry>, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let epub = Epub::open(&path_str).map_err(|e| e.to_string())?;
+
+        let spine_indices: HashMap<String, usize> = epub
+            .spine()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item_ref)| {
+                epub.manifest()
+                    .by_id(item_ref.idref())
+                    .map(|resource| (resource.href().as_str().to_string(), index))
+            })
+            .collect();
+
+        let entries = match epub.toc().contents() {
+            Some(root) => root
+                .iter()
+                .map(|entry| build_epub_toc_entry(&entry, &spine_indices))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Ok(entries)
+    })
+    .await?
+}
+
+/// Converts an rbook TOC entry (and its descendants) into a [`TocEntry`].
+fn build_epub_toc_entry(
+    entry: &EpubTocEntry<'_>,
+    spine_indices: &HashMap<String, usize>,
+) -> TocEntry {
+    let href = entry.href();
+    let resource_href = href.map(|h| h.path().as_str().to_string());
+    let fragment = href
+        .and_then(|h| h.fragment())
+        .filter(|f| !f.is_empty())
+        .map(str::to_string);
+    let spine_index = resource_href
+        .as_ref()
+        .and_then(|h| spine_indices.get(h).copied());
+
+    TocEntry {
+        label: entry.label().trim().to_string(),
+        href: resource_href,
+        fragment,
+        spine_index,
+        page: None,
+        position: None,
+        depth: entry.depth().saturating_sub(1),
+        children: entry
+            .iter()
+            .map(|child| build_epub_toc_entry(&child, spine_indices))
+            .collect(),
+    }
+}
+
+/// Searches the text of every XHTML chapter in an EPUB file.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+/// * `pattern` - Compiled search pattern (see
+///   [`build_search_pattern`](crate::utils::search::build_search_pattern)).
+/// * `max_hits` - Maximum number of hits to return.
+///
+/// # Returns
+///
+/// The first `max_hits` hits in reading order, each with its spine index and
+/// the character offset of the match within the chapter's plain text, and
+/// whether more were found.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or is not a valid
+/// EPUB.
+pub async fn search_epub(
+    path: &str,
+    pattern: Regex,
+    max_hits: usize,
+) -> Result<SearchResults, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let epub = Epub::open(&path_str).map_err(|e| e.to_string())?;
+        let limit = max_hits.saturating_add(1);
+        let mut hits = Vec::new();
+
+        for (index, item_ref) in epub.spine().iter().enumerate() {
+            if hits.len() >= limit {
+                break;
+            }
+            let Some(resource) = epub.manifest().by_id(item_ref.idref()) else {
+                continue;
+            };
+            if resource.kind().as_str() != "application/xhtml+xml" {
+                continue;
+            }
+            let Ok(content) = epub.read_resource_str(resource.resource()) else {
+                continue;
+            };
+
+            let text = html_to_text(&content);
+            let matches = find_matches(&text, &pattern, limit - hits.len());
+            hits.extend(matches.into_iter().map(|m| SearchHit {
+                spine_index: Some(index),
+                page: None,
+                offset: m.offset,
+                length: m.length,
+                matched: m.matched,
+                snippet: m.snippet,
+                bbox: None,
+            }));
+        }
+
+        Ok(SearchResults::capped(hits, max_hits))
+    })
+    .await?
+}
+
+/// Extracts the plain text of every XHTML chapter in an EPUB file.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+///
+/// # Returns
+///
+/// One [`BookTextSegment`] per non-empty chapter, in spine order.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or is not a valid
+/// EPUB.
+pub async fn extract_epub_text(
+    path: &str,
+) -> Result<Vec<BookTextSegment>, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let epub = Epub::open(&path_str).map_err(|e| e.to_string())?;
+        Ok(chapter_segments(&epub))
+    })
+    .await?
+}
+
+/// Plain text of every non-empty XHTML chapter of an open EPUB, in spine
+/// order. Chapters that cannot be read are skipped.
+fn chapter_segments(epub: &Epub) -> Vec<BookTextSegment> {
+    let mut segments = Vec::new();
+
+    for (index, item_ref) in epub.spine().iter().enumerate() {
+        let Some(resource) = epub.manifest().by_id(item_ref.idref()) else {
+            continue;
+        };
+        if resource.kind().as_str() != "application/xhtml+xml" {
+            continue;
+        }
+        let Ok(content) = epub.read_resource_str(resource.resource()) else {
+            continue;
+        };
+
+        let text = html_to_text(&content);
+        if !text.trim().is_empty() {
+            segments.push(BookTextSegment {
+                spine_index: Some(index),
+                page: None,
+                text,
+            });
+        }
+    }
+
+    segments
+}
+
+/// Renders a single EPUB chapter by spine index.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+/// * `index` - 0-based spine index of the chapter.
+/// * `book_id` - Database ID of the book, used to build `book://` image URLs.
+///
+/// # Returns
+///
+/// The rendered [`EpubChapter`].
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or `index` is out of
+/// range.
+pub async fn get_epub_chapter(
+    path: &str,
+    index: usize,
+    book_id: Option<i32>,
+) -> Result<EpubChapter, Box<dyn std::error::Error + Send + Sync>> {
+    get_epub_chapters(path, index, 0, book_id)
+        .await?
+        .into_iter()
+        .find(|chapter| chapter.index == index)
+        .ok_or_else(|| format!("Chapter {} not found", index).into())
+}
+
+/// Renders an EPUB chapter together with its neighbouring chapters.
+///
+/// The EPUB is opened once and chapters `index - prefetch ..= index + prefetch`
+/// (clamped to the spine) are rendered, so the reader can warm up adjacent
+/// chapters in the same round trip.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+/// * `index` - 0-based spine index of the requested chapter.
+/// * `prefetch` - Number of chapters to include on each side of `index`.
+/// * `book_id` - Database ID of the book, used to build `book://` image URLs.
+///
+/// # Returns
+///
+/// The rendered chapters in spine order; the requested chapter is the one
+/// whose [`EpubChapter::index`] equals `index`.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or `index` is out of
+/// range.
+pub async fn get_epub_chapters(
+    path: &str,
+    index: usize,
+    prefetch: usize,
+    book_id: Option<i32>,
+) -> Result<Vec<EpubChapter>, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let epub = Epub::open(&path_str).map_err(|e| e.to_string())?;
+        let spine = epub.spine();
+
+        if index >= spine.len() {
+            return Err(format!(
+                "Chapter {} out of range (EPUB has {} spine items)",
+                index,
+                spine.len()
+            )
+            .into());
+        }
+
+        let start = index.saturating_sub(prefetch);
+        let end = index.saturating_add(prefetch).min(spine.len() - 1);
+
+        let chapters = (start..=end)
+            .filter_map(|i| spine.get(i).map(|item_ref| (i, item_ref)))
+            .map(|(i, item_ref)| {
+                let resource = epub.manifest().by_id(item_ref.idref());
+                EpubChapter {
+                    index: i,
+                    idref: item_ref.idref().to_string(),
+                    href: resource
+                        .as_ref()
+                        .map(|r| r.href().as_str().to_string())
+                        .unwrap_or_default(),
+                    html: resource
+                        .as_ref()
+                        .and_then(|r| render_chapter_body(&epub, r, book_id))
+                        .unwrap_or_default(),
+                    styles: resource
+                        .as_ref()
+                        .and_then(|r| render_chapter_styles(&epub, r, book_id))
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        Ok(chapters)
+    })
+    .await?
+}
+
+/// Renders one XHTML spine resource as sanitized body HTML.
+///
+/// Relative `<img>` and SVG `<image>` references are resolved against the
+/// manifest and pointed at [`book_resource_url`] when `book_id` is given, or
+/// replaced with inline base64 data URIs otherwise. Scripts, inline event
+/// handlers and `javascript:` URLs are then stripped. Returns `None` for
+/// non-XHTML resources or when the resource cannot be read.
+fn render_chapter_body(
+    epub: &Epub,
+    resource: &EpubManifestEntry<'_>,
+    book_id: Option<i32>,
+) -> Option<String> {
+    if resource.kind().as_str() != "application/xhtml+xml" {
+        return None;
+    }
+    let content = epub.read_resource_str(resource.resource()).ok()?;
+    let current_href = resource.href().as_str();
+
+    let link_image = |caps: &regex::Captures| {
+        let prefix = &caps[1];
+        let src = &caps[2];
+        let suffix = &caps[3];
+
+        if src.starts_with("data:") || src.starts_with("http") {
+            return caps[0].to_string();
+        }
+
+        let resolved_href = resolve_path(current_href, src);
+        let Some(image_resource) = epub.manifest().by_href(&resolved_href) else {
+            return caps[0].to_string();
+        };
+
+        if let Some(id) = book_id {
+            return format!("{}{}{}", prefix, book_resource_url(id, &resolved_href), suffix);
+        }
+
+        if let Ok(image_bytes) = image_resource.read_bytes()
+        {
+            let encoded = general_purpose::STANDARD.encode(&image_bytes);
+            let kind = image_resource.kind();
+            let mime_type = kind.as_str();
+            let data_url = format!("data:{};base64,{}", mime_type, encoded);
+            return format!("{}{}{}", prefix, data_url, suffix);
+        }
+        caps[0].to_string()
+    };
+
+    let content_img_processed = IMG_RE.replace_all(&content, link_image);
+    let content_final = IMAGE_RE.replace_all(&content_img_processed, link_image);
+
+    let mut document = Html::parse_document(&content_final);
+    sanitize_document(&mut document);
+    let body_selector = Selector::parse("body").unwrap();
+    Some(document.select(&body_selector).next()?.inner_html())
+}
+
+/// Collects the `<style>` and `<link rel="stylesheet">` elements of an XHTML
+/// spine resource's `<head>`.
+///
+/// Linked stylesheets are resolved against the manifest and pointed at
+/// [`book_resource_url`] when `book_id` is given, so their relative `url(...)`
+/// references resolve through the protocol too; otherwise their contents are
+/// inlined as `<style>` elements. Remote and unknown stylesheets are dropped.
+/// Returns `None` for non-XHTML resources or when the resource cannot be read.
+fn render_chapter_styles(
+    epub: &Epub,
+    resource: &EpubManifestEntry<'_>,
+    book_id: Option<i32>,
+) -> Option<String> {
+    if resource.kind().as_str() != "application/xhtml+xml" {
+        return None;
+    }
+    let content = epub.read_resource_str(resource.resource()).ok()?;
+    let current_href = resource.href().as_str();
+    let document = Html::parse_document(&content);
+    let style_selector = Selector::parse(r#"head style, head link[rel~="stylesheet" i]"#).unwrap();
+
+    let mut styles = String::new();
+    for element in document.select(&style_selector) {
+        if element.value().name() == "style" {
+            styles.push_str(&element.html());
+            continue;
+        }
+        let Some(href) = element.value().attr("href") else {
+            continue;
+        };
+        if href.starts_with("http") || href.starts_with("data:") {
+            continue;
+        }
+        let resolved_href = resolve_path(current_href, href);
+        let Some(stylesheet) = epub.manifest().by_href(&resolved_href) else {
+            continue;
+        };
+        match book_id {
+            Some(id) => styles.push_str(&format!(
+                r#"<link rel="stylesheet" href="{}">"#,
+                book_resource_url(id, &resolved_href)
+            )),
+            None => {
+                if let Ok(css) = epub.read_resource_str(stylesheet.resource()) {
+                    styles.push_str(&format!("<style>{}</style>", css));
+                }
+            }
+        }
+    }
+
+    Some(sanitize_html(&styles))
+}
+
+/// Strips executable content from an HTML fragment before it reaches the
+/// webview. See [`sanitize_document`].
+fn sanitize_html(html: &str) -> String {
+    let mut fragment = Html::parse_fragment(html);
+    sanitize_document(&mut fragment);
+    fragment.root_element().inner_html()
+}
+
+/// Strips executable content from parsed chapter HTML: `<script>` elements,
+/// inline `on*` event handler attributes and `javascript:` URLs in
+/// `href`/`src` attributes.
+///
+/// Works on the DOM rather than the serialized markup, so book text that
+/// merely looks like an attribute is never touched.
+fn sanitize_document(document: &mut Html) {
+    let scripts: Vec<_> = document
+        .tree
+        .nodes()
+        .filter(|node| {
+            node.value()
+                .as_element()
+                .is_some_and(|element| element.name() == "script")
+        })
+        .map(|node| node.id())
+        .collect();
+    for id in scripts {
+        if let Some(mut node) = document.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+
+    for node in document.tree.values_mut() {
+        if let Node::Element(element) = node {
+            element.attrs = std::mem::take(&mut element.attrs)
+                .into_iter()
+                .filter(|(name, value)| !is_executable_attr(&name.local, value))
+                .collect();
+        }
+    }
+}
+
+/// Whether an attribute can run script: an `on*` event handler, or an
+/// `href`/`src` holding a `javascript:` URL.
+fn is_executable_attr(name: &str, value: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    if name.starts_with("on") {
+        return true;
+    }
+    if name != "href" && name != "src" {
+        return false;
+    }
+    // Browsers ignore whitespace and control characters inside the scheme.
+    let scheme: String = value
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .take("javascript:".len())
+        .collect();
+    scheme.eq_ignore_ascii_case("javascript:")
+}
+
+/// Reads a manifest resource (image, font, stylesheet, SVG, ...) straight from
+/// the EPUB archive.
+///
+/// Backs the [`BOOK_SCHEME`] protocol, so chapters can reference resources by
+/// URL instead of carrying them inline.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the EPUB file.
+/// * `href` - Manifest href of the resource, with or without a leading `/`,
+///   percent-encoded or not.
+///
+/// # Returns
+///
+/// `Some((bytes, media_type))` with the resource's MIME type as declared in
+/// the manifest, or `None` if no manifest item has the given href.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or the resource
+/// cannot be read from the archive.
+pub async fn read_epub_resource(
+    path: &str,
+    href: &str,
+) -> Result<Option<(Vec<u8>, String)>, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    let href = format!("/{}", href.trim_start_matches('/'));
+    tokio::task::spawn_blocking(move || {
+        let epub = Epub::open(&path_str).map_err(|e| e.to_string())?;
+
+        // Manifest hrefs are percent-encoded; requests may be either.
+        let wanted = percent_decode_str(&href).decode_utf8_lossy();
+        let resource = epub.manifest().by_href(&href).or_else(|| {
+            epub.manifest().iter().find(|entry| {
+                percent_decode_str(entry.href().as_str()).decode_utf8_lossy() == wanted
+            })
+        });
+        let Some(resource) = resource else {
+            return Ok(None);
+        };
+        let bytes = resource.read_bytes()?;
+
+        Ok(Some((bytes, resource.media_type().to_string())))
+    })
+    .await?
+}
+
 /// Resolves a relative image path against a base EPUB href.
 ///
 /// Normalizes `..` components and converts backslashes to forward slashes
@@ -279,49 +1230,31 @@ fn resolve_path(base_href: &str, relative_path: &str) -> String {
     resolved_path.replace('\\', "/")
 }
 
-/// Retrieves the cover image bytes for a book by its database ID.
-///
-/// Looks up the book's file path in the database, opens the EPUB, and
-/// extracts the manifest's cover image.
+/// Extracts the cover image of an EPUB file.
 ///
 /// # Arguments
 ///
-/// * `book_id` - The book's database ID.
+/// * `path` - Absolute path to the EPUB file.
 ///
 /// # Returns
 ///
-/// Raw cover image bytes. Returns an empty `Vec<u8>` when the EPUB has no
-/// cover image in its manifest.
+/// Raw bytes of the manifest's cover image.
 ///
 /// # Errors
 ///
-/// Returns a boxed error when the book ID is not found, the file cannot be
-/// opened, or the cover image data cannot be read.
-pub async fn get_cover_image_by_book_id(
-    book_id: i32,
+/// Returns a boxed error when the file cannot be opened, declares no cover
+/// image, or the cover image data cannot be read.
+pub async fn get_epub_cover(
+    path: &str,
 ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
-    use diesel::prelude::*;
-    use diesel_async::RunQueryDsl;
-
-    let mut conn = connect_from_pool().await?;
-    let rows = crate::infrastructure::database::models::schema::books::dsl::books
-        .filter(crate::infrastructure::database::models::schema::books::book_id.eq(book_id))
-        .limit(1)
-        .load::<crate::infrastructure::database::models::book::BookRow>(&mut conn)
-        .await
-        .map_err(|e| format!("Book not found: {}", e))?;
-    let book = rows
-        .into_iter()
-        .next()
-        .ok_or_else(|| format!("Book not found: {}", book_id))?;
-
-    let epub = Epub::open(&book.file_path).map_err(|e| e.to_string())?;
-
-    match epub.manifest().cover_image() {
-        Some(cover_image) => {
-            let bytes = cover_image.read_bytes()?;
-            Ok(bytes)
-        }
-        None => Ok(Vec::new()),
-    }
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let epub = Epub::open(&path_str)?;
+        let cover_image = epub
+            .manifest()
+            .cover_image()
+            .ok_or("EPUB file has no cover image")?;
+        Ok(cover_image.read_bytes()?)
+    })
+    .await?
 }
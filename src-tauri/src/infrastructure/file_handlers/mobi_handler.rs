@@ -1,4 +1,940 @@
-/// Placeholder for MOBI file handling (not yet implemented).
-pub fn placeholder() {
-    // TODO: MOBI handler
+use base64::{Engine as _, engine::general_purpose};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{Html, Selector};
+use std::path::{Path, PathBuf};
+use tokio::task::JoinError;
+use walkdir::WalkDir;
+
+use crate::infrastructure::file_handlers::{
+    BookMetadata, BookTextSegment, SearchHit, SearchResults, author_names, authors_as_contributors,
+    clean_description, normalize_language_tag, split_tag_list,
+};
+use crate::utils::file::compute_checksum;
+use crate::utils::isbn::normalize_isbn;
+use crate::utils::search::{find_matches, html_to_text};
+
+/// Marker struct for MOBI/KF8-specific operations.
+pub struct MobiHandler;
+
+/// File extensions handled by the MOBI parser (MOBI, KF8/AZW3, legacy AZW).
+pub const MOBI_EXTENSIONS: [&str; 3] = ["mobi", "azw3", "azw"];
+
+/// Length of the PalmDOC header that precedes the MOBI header in record 0.
+const PALMDOC_HEADER_LEN: usize = 16;
+
+const COMPRESSION_NONE: u16 = 1;
+const COMPRESSION_PALMDOC: u16 = 2;
+const COMPRESSION_HUFF_CDIC: u16 = 17480;
+
+const ENCODING_UTF8: u32 = 65001;
+
+/// Sentinel used by MOBI headers for "no record".
+const NULL_INDEX: u32 = 0xFFFF_FFFF;
+
+const EXTH_AUTHOR: u32 = 100;
+const EXTH_PUBLISHER: u32 = 101;
+const EXTH_DESCRIPTION: u32 = 103;
+const EXTH_ISBN: u32 = 104;
+const EXTH_SUBJECT: u32 = 105;
+const EXTH_PUBLISHED_DATE: u32 = 106;
+const EXTH_ASIN: u32 = 113;
+const EXTH_COVER_OFFSET: u32 = 201;
+const EXTH_THUMB_OFFSET: u32 = 202;
+const EXTH_UPDATED_TITLE: u32 = 503;
+const EXTH_LANGUAGE: u32 = 524;
+
+/// Matches `<mbp:pagebreak/>` markers.
+static PAGEBREAK_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)<mbp:pagebreak\s*/?>").unwrap());
+
+/// Matches MOBI `<img recindex="...">` image references.
+static RECINDEX_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)<img([^>]*?)\s+recindex=["']?(\d+)["']?([^>]*)>"#).unwrap());
+
+/// Matches KF8 `kindle:embed:XXXX?mime=...` image references.
+static EMBED_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"kindle:embed:([0-9A-Va-v]+)(?:\?mime=[^\x22')\s]*)?").unwrap());
+
+/// Returns `true` if the extension (without the dot) is a MOBI-family format.
+pub fn is_mobi_extension(ext: &str) -> bool {
+    MOBI_EXTENSIONS
+        .iter()
+        .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+}
+
+/// Recursively scans a directory for `.mobi`, `.azw3` and `.azw` files.
+///
+/// Runs directory traversal on a blocking thread to avoid stalling the async
+/// runtime.
+///
+/// # Arguments
+///
+/// * `dir` - Directory to scan recursively.
+///
+/// # Returns
+///
+/// A vector of absolute paths to matching MOBI-family files.
+pub async fn scan_mobis<P: AsRef<Path> + Send + 'static>(
+    dir: P,
+) -> Result<Vec<PathBuf>, JoinError> {
+    tokio::task::spawn_blocking(move || {
+        WalkDir::new(dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .filter(|p| {
+                p.extension()
+                    .and_then(|s| s.to_str())
+                    .map(is_mobi_extension)
+                    .unwrap_or(false)
+            })
+            .collect()
+    })
+    .await
+}
+
+/// Parses metadata from a MOBI or KF8 (AZW3) file.
+///
+/// Reads the EXTH header for title, authors, publisher, publication date,
+/// ISBN (falling back to the ASIN), subjects, description, language and the
+/// cover record, and computes a
+/// SHA-256 checksum. Defaults to "Unknown Author" / "Unknown Publisher" /
+/// "Unknown Title" when metadata fields are missing.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the MOBI file.
+///
+/// # Returns
+///
+/// A populated [`BookMetadata`] struct with all extracted fields and the
+/// file's SHA-256 checksum.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened, is not a valid
+/// MOBI container, is DRM-protected, or cannot be checksummed.
+pub async fn parse_mobi_meta(
+    path: String,
+) -> Result<BookMetadata, Box<dyn std::error::Error + Send + Sync>> {
+    let checksum = compute_checksum(&path).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let book = MobiBook::open(&path)?;
+
+        let title = book
+            .exth_strings(EXTH_UPDATED_TITLE)
+            .into_iter()
+            .next()
+            .or_else(|| book.header.full_name.clone())
+            .or_else(|| book.pdb.name())
+            .unwrap_or_else(|| "Unknown Title".to_string());
+
+        let contributors = authors_as_contributors(&book.exth_strings(EXTH_AUTHOR));
+
+        let mut publishers = book.exth_strings(EXTH_PUBLISHER);
+        if publishers.is_empty() {
+            publishers.push("Unknown Publisher".to_string());
+        }
+
+        let published_date = book.exth_strings(EXTH_PUBLISHED_DATE).into_iter().next();
+
+        // Book ASINs are often ISBN-10s; anything else is dropped.
+        let isbn = book
+            .exth_strings(EXTH_ISBN)
+            .into_iter()
+            .chain(book.exth_strings(EXTH_ASIN))
+            .find_map(|value| normalize_isbn(&value));
+
+        let tags = split_tag_list(&book.exth_strings(EXTH_SUBJECT));
+
+        let description = book
+            .exth_strings(EXTH_DESCRIPTION)
+            .iter()
+            .find_map(|d| clean_description(d));
+        let language = book
+            .exth_strings(EXTH_LANGUAGE)
+            .iter()
+            .find_map(|l| normalize_language_tag(l));
+
+        let cover_data = book.cover_image();
+
+        Ok(BookMetadata {
+            title,
+            authors: author_names(&contributors),
+            contributors,
+            publishers,
+            published_date,
+            isbn,
+            series: None,
+            series_index: None,
+            tags,
+            description,
+            language,
+            word_count: None,
+            page_count: None,
+            file_path: path,
+            cover_data,
+            checksum,
+        })
+    })
+    .await?
+}
+
+/// Decodes the text of a MOBI or KF8 file and returns it as HTML.
+///
+/// Text records are decompressed (PalmDOC or HUFF/CDIC), decoded from the
+/// book's declared encoding, and the `<body>` inner HTML is returned. Image
+/// references (`recindex` attributes and `kindle:embed` URLs) are replaced
+/// with inline base64 data URIs so the resulting HTML is self-contained.
+///
+/// For KF8 files only the first flow (the XHTML text) is rendered; skeleton
+/// and fragment chunks are left in storage order, which the HTML parser
+/// folds into a single body.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the MOBI file.
+///
+/// # Returns
+///
+/// A single HTML string containing the book's body content with embedded
+/// base64 images.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened, is DRM-protected,
+/// or uses an unsupported compression scheme.
+pub async fn get_mobi_content(
+    path: &str,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let book = MobiBook::open(&path_str)?;
+        book.render_html()
+    })
+    .await?
+}
+
+/// Searches the decoded text of a MOBI or KF8 file.
+///
+/// MOBI books are read as a single document, so hits carry neither a spine
+/// index nor a page; `offset` is relative to the whole book's text.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the MOBI file.
+/// * `pattern` - Compiled search pattern (see
+///   [`build_search_pattern`](crate::utils::search::build_search_pattern)).
+/// * `max_hits` - Maximum number of hits to return.
+///
+/// # Returns
+///
+/// The first `max_hits` hits in text order, and whether more were found.
+///
+/// # Errors
+///
+/// Returns a boxed error under the same conditions as [`get_mobi_content`].
+pub async fn search_mobi(
+    path: &str,
+    pattern: Regex,
+    max_hits: usize,
+) -> Result<SearchResults, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let book = MobiBook::open(&path_str)?;
+        let text = html_to_text(&book.render_html()?);
+
+        let hits = find_matches(&text, &pattern, max_hits.saturating_add(1))
+            .into_iter()
+            .map(|m| SearchHit {
+                spine_index: None,
+                page: None,
+                offset: m.offset,
+                length: m.length,
+                matched: m.matched,
+                snippet: m.snippet,
+                bbox: None,
+            })
+            .collect();
+        Ok(SearchResults::capped(hits, max_hits))
+    })
+    .await?
+}
+
+/// Extracts the plain text of a MOBI or KF8 file.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the MOBI file.
+///
+/// # Returns
+///
+/// A single [`BookTextSegment`] holding the whole book's text, or none if the
+/// book has no text.
+///
+/// # Errors
+///
+/// Returns a boxed error under the same conditions as [`get_mobi_content`].
+pub async fn extract_mobi_text(
+    path: &str,
+) -> Result<Vec<BookTextSegment>, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let book = MobiBook::open(&path_str)?;
+        let text = html_to_text(&book.render_html()?);
+        if text.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![BookTextSegment {
+            spine_index: None,
+            page: None,
+            text,
+        }])
+    })
+    .await?
+}
+
+/// Extracts the cover image of a MOBI or KF8 file.
+///
+/// Uses the EXTH cover offset, falling back to the thumbnail offset.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the MOBI file.
+///
+/// # Returns
+///
+/// Raw cover image bytes.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or declares no
+/// cover record.
+pub async fn get_mobi_cover(
+    path: &str,
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let book = MobiBook::open(&path_str)?;
+        book.cover_image()
+            .map(|(bytes, _)| bytes)
+            .ok_or_else(|| "MOBI file has no cover image".into())
+    })
+    .await?
+}
+
+/// A Palm database (PDB) container: the raw file bytes plus record offsets.
+struct PalmDatabase {
+    data: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl PalmDatabase {
+    /// Parses the PDB header and record list, requiring a `BOOKMOBI` type.
+    fn parse(data: Vec<u8>) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        if data.get(60..68) != Some(b"BOOKMOBI".as_slice()) {
+            return Err("Not a MOBI file".into());
+        }
+
+        let count = read_u16(&data, 76).ok_or("Truncated PDB header")? as usize;
+        let mut offsets = Vec::with_capacity(count);
+        for i in 0..count {
+            let offset = read_u32(&data, 78 + i * 8).ok_or("Truncated PDB record list")?;
+            offsets.push(offset as usize);
+        }
+
+        Ok(Self { data, offsets })
+    }
+
+    /// Returns the bytes of the record at `index`, if it exists.
+    fn record(&self, index: usize) -> Option<&[u8]> {
+        let start = *self.offsets.get(index)?;
+        let end = self
+            .offsets
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.data.len());
+        self.data.get(start..end)
+    }
+
+    /// Returns the database name from the PDB header.
+    fn name(&self) -> Option<String> {
+        let raw = self.data.get(0..32)?;
+        let end = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+        let name = String::from_utf8_lossy(&raw[..end]).replace('_', " ");
+        let name = name.trim();
+        (!name.is_empty()).then(|| name.to_string())
+    }
+}
+
+/// A single EXTH metadata record.
+struct ExthRecord {
+    kind: u32,
+    data: Vec<u8>,
+}
+
+/// Fields of the PalmDOC and MOBI headers stored in record 0.
+struct MobiHeader {
+    compression: u16,
+    text_length: usize,
+    text_record_count: usize,
+    mobi_version: u32,
+    encoding: u32,
+    full_name: Option<String>,
+    first_image_index: Option<usize>,
+    huff_record_index: usize,
+    huff_record_count: usize,
+    fdst_index: Option<usize>,
+    extra_flags: u16,
+    exth: Vec<ExthRecord>,
+}
+
+impl MobiHeader {
+    /// Parses record 0 of a MOBI file.
+    fn parse(record0: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let compression = read_u16(record0, 0).ok_or("Truncated PalmDOC header")?;
+        let text_length = read_u32(record0, 4).ok_or("Truncated PalmDOC header")? as usize;
+        let text_record_count = read_u16(record0, 8).ok_or("Truncated PalmDOC header")? as usize;
+        let encryption = read_u16(record0, 12).ok_or("Truncated PalmDOC header")?;
+
+        if encryption != 0 {
+            return Err("DRM-protected MOBI files are not supported".into());
+        }
+
+        if record0.get(PALMDOC_HEADER_LEN..PALMDOC_HEADER_LEN + 4) != Some(b"MOBI".as_slice()) {
+            return Err("Missing MOBI header".into());
+        }
+
+        let header_length = read_u32(record0, 20).ok_or("Truncated MOBI header")? as usize;
+        let encoding = read_u32(record0, 28).unwrap_or(1252);
+        let mobi_version = read_u32(record0, 36).unwrap_or(0);
+
+        let full_name = match (read_u32(record0, 84), read_u32(record0, 88)) {
+            (Some(offset), Some(len)) => record0
+                .get(offset as usize..(offset as usize).saturating_add(len as usize))
+                .map(|raw| decode_text(raw, encoding).trim().to_string())
+                .filter(|name| !name.is_empty()),
+            _ => None,
+        };
+
+        let first_image_index = read_u32(record0, 108)
+            .filter(|&idx| idx != NULL_INDEX && idx != 0)
+            .map(|idx| idx as usize);
+
+        let huff_record_index = read_u32(record0, 112).unwrap_or(0) as usize;
+        let huff_record_count = read_u32(record0, 116).unwrap_or(0) as usize;
+
+        let exth_flags = read_u32(record0, 128).unwrap_or(0);
+
+        let fdst_index = if mobi_version >= 8 {
+            read_u32(record0, 192)
+                .filter(|&idx| idx != NULL_INDEX && idx != 0)
+                .map(|idx| idx as usize)
+        } else {
+            None
+        };
+
+        let extra_flags = if header_length >= 0xE4 {
+            read_u16(record0, 0xF2).unwrap_or(0)
+        } else {
+            0
+        };
+
+        let exth = if exth_flags & 0x40 != 0 {
+            parse_exth(record0, PALMDOC_HEADER_LEN + header_length)
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            compression,
+            text_length,
+            text_record_count,
+            mobi_version,
+            encoding,
+            full_name,
+            first_image_index,
+            huff_record_index,
+            huff_record_count,
+            fdst_index,
+            extra_flags,
+            exth,
+        })
+    }
+}
+
+/// Parses the EXTH block starting at `offset` in record 0.
+///
+/// Malformed trailing records are ignored rather than failing the whole
+/// header, since many converters write slightly inconsistent lengths.
+fn parse_exth(record0: &[u8], offset: usize) -> Vec<ExthRecord> {
+    let mut records = Vec::new();
+
+    if record0.get(offset..offset + 4) != Some(b"EXTH".as_slice()) {
+        return records;
+    }
+
+    let count = read_u32(record0, offset + 8).unwrap_or(0);
+    let mut pos = offset + 12;
+
+    for _ in 0..count {
+        let (Some(kind), Some(len)) = (read_u32(record0, pos), read_u32(record0, pos + 4)) else {
+            break;
+        };
+        let len = len as usize;
+        if len < 8 {
+            break;
+        }
+        let Some(data) = record0.get(pos + 8..pos + len) else {
+            break;
+        };
+        records.push(ExthRecord {
+            kind,
+            data: data.to_vec(),
+        });
+        pos += len;
+    }
+
+    records
+}
+
+/// An opened MOBI file: the PDB container and its parsed record 0 header.
+struct MobiBook {
+    pdb: PalmDatabase,
+    header: MobiHeader,
+}
+
+impl MobiBook {
+    /// Reads and parses the MOBI file at `path`.
+    fn open(path: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let data = std::fs::read(path)?;
+        let pdb = PalmDatabase::parse(data)?;
+        let record0 = pdb.record(0).ok_or("MOBI file has no records")?;
+        let header = MobiHeader::parse(record0)?;
+        Ok(Self { pdb, header })
+    }
+
+    /// Returns all EXTH string values of the given record type, decoded.
+    fn exth_strings(&self, kind: u32) -> Vec<String> {
+        self.header
+            .exth
+            .iter()
+            .filter(|r| r.kind == kind)
+            .map(|r| decode_text(&r.data, self.header.encoding).trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    /// Returns the first EXTH value of the given record type as a `u32`.
+    fn exth_u32(&self, kind: u32) -> Option<u32> {
+        self.header
+            .exth
+            .iter()
+            .find(|r| r.kind == kind)
+            .and_then(|r| read_u32(&r.data, 0))
+            .filter(|&v| v != NULL_INDEX)
+    }
+
+    /// Returns the image record for a 1-based `recindex` / `kindle:embed` index.
+    fn image_record(&self, index: usize) -> Option<&[u8]> {
+        let first = self.header.first_image_index?;
+        self.pdb.record(first + index.checked_sub(1)?)
+    }
+
+    /// Returns the cover image bytes and MIME type declared by EXTH.
+    fn cover_image(&self) -> Option<(Vec<u8>, String)> {
+        let first = self.header.first_image_index?;
+        let offset = self
+            .exth_u32(EXTH_COVER_OFFSET)
+            .or_else(|| self.exth_u32(EXTH_THUMB_OFFSET))?;
+        let bytes = self.pdb.record(first + offset as usize)?;
+        let mime = sniff_image_mime(bytes)?;
+        Some((bytes.to_vec(), mime.to_string()))
+    }
+
+    /// Decompresses and concatenates all text records.
+    fn text_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut huff = match self.header.compression {
+            COMPRESSION_NONE | COMPRESSION_PALMDOC => None,
+            COMPRESSION_HUFF_CDIC => Some(HuffCdicReader::new(
+                &self.pdb,
+                self.header.huff_record_index,
+                self.header.huff_record_count,
+            )?),
+            other => {
+                return Err(format!("Unsupported MOBI compression type: {}", other).into());
+            }
+        };
+
+        let mut text = Vec::with_capacity(self.header.text_length);
+        for i in 1..=self.header.text_record_count {
+            let record = self
+                .pdb
+                .record(i)
+                .ok_or_else(|| format!("Missing text record {}", i))?;
+            let record = strip_trailing_entries(record, self.header.extra_flags);
+
+            match huff.as_mut() {
+                Some(reader) => text.extend(reader.unpack(record, 0)?),
+                None if self.header.compression == COMPRESSION_PALMDOC => {
+                    text.extend(palmdoc_decompress(record))
+                }
+                None => text.extend_from_slice(record),
+            }
+        }
+
+        text.truncate(self.header.text_length);
+        Ok(text)
+    }
+
+    /// Narrows KF8 text to its first flow using the FDST section table.
+    fn first_flow<'a>(&self, text: &'a [u8]) -> &'a [u8] {
+        let Some(fdst) = self.header.fdst_index.and_then(|idx| self.pdb.record(idx)) else {
+            return text;
+        };
+        if fdst.get(0..4) != Some(b"FDST".as_slice()) {
+            return text;
+        }
+
+        let table = read_u32(fdst, 4).unwrap_or(12) as usize;
+        match (read_u32(fdst, table), read_u32(fdst, table + 4)) {
+            (Some(start), Some(end)) => text
+                .get(start as usize..(end as usize).min(text.len()))
+                .unwrap_or(text),
+            _ => text,
+        }
+    }
+
+    /// Renders the book text as self-contained body HTML.
+    fn render_html(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let text = self.text_bytes()?;
+        let text = if self.header.mobi_version >= 8 {
+            self.first_flow(&text)
+        } else {
+            &text
+        };
+        let markup = decode_text(text, self.header.encoding);
+
+        let markup =
+            PAGEBREAK_RE.replace_all(&markup, "<div style=\"page-break-after: always\"></div>");
+
+        let markup = RECINDEX_RE.replace_all(&markup, |caps: &regex::Captures| {
+            let src = caps[2]
+                .parse::<usize>()
+                .ok()
+                .and_then(|idx| self.image_data_uri(idx))
+                .map(|uri| format!(" src=\"{}\"", uri))
+                .unwrap_or_default();
+            format!("<img{}{}{}>", &caps[1], src, &caps[3])
+        });
+
+        let markup = EMBED_RE.replace_all(&markup, |caps: &regex::Captures| {
+            usize::from_str_radix(&caps[1], 32)
+                .ok()
+                .and_then(|idx| self.image_data_uri(idx))
+                .unwrap_or_else(|| caps[0].to_string())
+        });
+
+        let document = Html::parse_document(&markup);
+        let body_selector = Selector::parse("body").unwrap();
+        Ok(document
+            .select(&body_selector)
+            .next()
+            .map(|body| body.inner_html())
+            .unwrap_or_else(|| markup.to_string()))
+    }
+
+    /// Returns a base64 data URI for the image at the 1-based index.
+    fn image_data_uri(&self, index: usize) -> Option<String> {
+        let bytes = self.image_record(index)?;
+        let mime = sniff_image_mime(bytes)?;
+        Some(format!(
+            "data:{};base64,{}",
+            mime,
+            general_purpose::STANDARD.encode(bytes)
+        ))
+    }
+}
+
+/// Decoder state for HUFF/CDIC-compressed text records.
+///
+/// Follows the layout used by Mobipocket Creator and KindleGen: one `HUFF`
+/// record with the code tables, followed by one or more `CDIC` phrase
+/// dictionaries whose entries may themselves be compressed.
+struct HuffCdicReader {
+    /// Per-leading-byte lookup: `(code length, terminal, max code)`.
+    dict1: Vec<(u32, bool, u64)>,
+    mincode: [u64; 33],
+    maxcode: [u64; 33],
+    /// Phrase dictionary; the flag marks entries that are already expanded.
+    dictionary: Vec<(Vec<u8>, bool)>,
+}
+
+impl HuffCdicReader {
+    /// Loads the `HUFF` record at `huff_index` and its following `CDIC` records.
+    fn new(
+        pdb: &PalmDatabase,
+        huff_index: usize,
+        huff_count: usize,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let huff = pdb.record(huff_index).ok_or("Missing HUFF record")?;
+        if huff.get(0..8) != Some(b"HUFF\x00\x00\x00\x18".as_slice()) {
+            return Err("Invalid HUFF record".into());
+        }
+
+        let dict1_offset = read_u32(huff, 8).ok_or("Truncated HUFF record")? as usize;
+        let dict2_offset = read_u32(huff, 12).ok_or("Truncated HUFF record")? as usize;
+
+        let mut dict1 = Vec::with_capacity(256);
+        for i in 0..256 {
+            let v = read_u32(huff, dict1_offset + i * 4).ok_or("Truncated HUFF table")?;
+            let codelen = v & 0x1F;
+            if codelen == 0 {
+                return Err("Invalid HUFF code length".into());
+            }
+            let maxcode = ((((v >> 8) as u64) + 1) << (32 - codelen)) - 1;
+            dict1.push((codelen, v & 0x80 != 0, maxcode));
+        }
+
+        let mut mincode = [0u64; 33];
+        let mut maxcode = [0u64; 33];
+        maxcode[0] = (1u64 << 32) - 1;
+        for codelen in 1..=32usize {
+            let base = dict2_offset + (codelen - 1) * 8;
+            let min = read_u32(huff, base).ok_or("Truncated HUFF table")? as u64;
+            let max = read_u32(huff, base + 4).ok_or("Truncated HUFF table")? as u64;
+            mincode[codelen] = min << (32 - codelen);
+            maxcode[codelen] = ((max + 1) << (32 - codelen)) - 1;
+        }
+
+        let mut dictionary = Vec::new();
+        for i in 1..huff_count {
+            let cdic = pdb.record(huff_index + i).ok_or("Missing CDIC record")?;
+            if cdic.get(0..8) != Some(b"CDIC\x00\x00\x00\x10".as_slice()) {
+                return Err("Invalid CDIC record".into());
+            }
+
+            let phrases = read_u32(cdic, 8).ok_or("Truncated CDIC record")? as usize;
+            let bits = read_u32(cdic, 12).ok_or("Truncated CDIC record")?;
+            let count = (1usize << bits.min(31)).min(phrases.saturating_sub(dictionary.len()));
+
+            for j in 0..count {
+                let offset = read_u16(cdic, 16 + j * 2).ok_or("Truncated CDIC record")? as usize;
+                let blen = read_u16(cdic, 16 + offset).ok_or("Truncated CDIC record")? as usize;
+                let start = 18 + offset;
+                let phrase = cdic
+                    .get(start..start + (blen & 0x7FFF))
+                    .ok_or("Truncated CDIC phrase")?;
+                dictionary.push((phrase.to_vec(), blen & 0x8000 != 0));
+            }
+        }
+
+        Ok(Self {
+            dict1,
+            mincode,
+            maxcode,
+            dictionary,
+        })
+    }
+
+    /// Decompresses one HUFF/CDIC-encoded record.
+    ///
+    /// Dictionary phrases that are themselves compressed are expanded
+    /// recursively and memoized in place.
+    fn unpack(
+        &mut self,
+        data: &[u8],
+        depth: usize,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        if depth > 32 {
+            return Err("HUFF/CDIC dictionary nesting too deep".into());
+        }
+
+        let mut out = Vec::new();
+        let mut bits_left = data.len() as i64 * 8;
+        let mut pos = 0usize;
+        let mut x = read_u64_padded(data, pos);
+        let mut n: i64 = 32;
+
+        loop {
+            if n <= 0 {
+                pos += 4;
+                x = read_u64_padded(data, pos);
+                n += 32;
+            }
+            let code = (x >> n as u32) & 0xFFFF_FFFF;
+
+            let (mut codelen, term, mut maxcode) = self.dict1[(code >> 24) as usize];
+            if !term {
+                while codelen < 32 && code < self.mincode[codelen as usize] {
+                    codelen += 1;
+                }
+                maxcode = self.maxcode[codelen as usize];
+            }
+
+            n -= codelen as i64;
+            bits_left -= codelen as i64;
+            if bits_left < 0 {
+                break;
+            }
+
+            let index = (maxcode
+                .checked_sub(code)
+                .ok_or("Corrupt HUFF/CDIC code")?
+                >> (32 - codelen)) as usize;
+            let (phrase, expanded) = self
+                .dictionary
+                .get(index)
+                .cloned()
+                .ok_or("HUFF/CDIC phrase index out of range")?;
+
+            if expanded {
+                out.extend_from_slice(&phrase);
+            } else {
+                let phrase = self.unpack(&phrase, depth + 1)?;
+                out.extend_from_slice(&phrase);
+                self.dictionary[index] = (phrase, true);
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+/// Decompresses a PalmDOC (LZ77 variant) text record.
+fn palmdoc_decompress(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() * 2);
+    let mut i = 0;
+
+    while i < data.len() {
+        let c = data[i];
+        i += 1;
+
+        match c {
+            0x01..=0x08 => {
+                let end = (i + c as usize).min(data.len());
+                out.extend_from_slice(&data[i..end]);
+                i = end;
+            }
+            0x00 | 0x09..=0x7F => out.push(c),
+            0x80..=0xBF => {
+                let Some(&next) = data.get(i) else {
+                    break;
+                };
+                i += 1;
+                let pair = (((c as usize) << 8) | next as usize) & 0x3FFF;
+                let distance = pair >> 3;
+                let length = (pair & 0x07) + 3;
+                if distance == 0 || distance > out.len() {
+                    continue;
+                }
+                for _ in 0..length {
+                    let byte = out[out.len() - distance];
+                    out.push(byte);
+                }
+            }
+            0xC0..=0xFF => {
+                out.push(b' ');
+                out.push(c ^ 0x80);
+            }
+        }
+    }
+
+    out
+}
+
+/// Strips the trailing entries declared by the MOBI extra-data flags.
+fn strip_trailing_entries(record: &[u8], extra_flags: u16) -> &[u8] {
+    let mut size = record.len();
+    let mut flags = extra_flags >> 1;
+
+    while flags != 0 {
+        if flags & 1 != 0 {
+            size = size.saturating_sub(trailing_entry_size(&record[..size]));
+        }
+        flags >>= 1;
+    }
+
+    if extra_flags & 1 != 0 && size > 0 {
+        let multibyte = (record[size - 1] & 0x03) as usize + 1;
+        size = size.saturating_sub(multibyte);
+    }
+
+    &record[..size]
+}
+
+/// Reads a backward-encoded variable-width integer from the end of `data`.
+fn trailing_entry_size(data: &[u8]) -> usize {
+    let mut result = 0usize;
+    let mut shift = 0;
+
+    for &byte in data.iter().rev() {
+        result |= ((byte & 0x7F) as usize) << shift;
+        shift += 7;
+        if byte & 0x80 != 0 || shift >= 28 {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Decodes text in the MOBI header's declared encoding (UTF-8 or CP1252).
+fn decode_text(bytes: &[u8], encoding: u32) -> String {
+    if encoding == ENCODING_UTF8 {
+        return String::from_utf8_lossy(bytes).into_owned();
+    }
+
+    const CP1252_HIGH: [char; 32] = [
+        '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}',
+        '\u{2021}', '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}',
+        '\u{017D}', '\u{008F}', '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}',
+        '\u{2022}', '\u{2013}', '\u{2014}', '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}',
+        '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+    ];
+
+    bytes
+        .iter()
+        .map(|&b| match b {
+            0x80..=0x9F => CP1252_HIGH[(b - 0x80) as usize],
+            _ => b as char,
+        })
+        .collect()
+}
+
+/// Detects the MIME type of an image record from its magic bytes.
+fn sniff_image_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"\x89PNG") {
+        Some("image/png")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("image/gif")
+    } else if bytes.starts_with(b"BM") {
+        Some("image/bmp")
+    } else {
+        None
+    }
+}
+
+/// Reads a big-endian `u16` at `offset`, if in bounds.
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+/// Reads a big-endian `u32` at `offset`, if in bounds.
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Reads a big-endian `u64`, treating bytes past the end of `data` as zero.
+fn read_u64_padded(data: &[u8], offset: usize) -> u64 {
+    (0..8).fold(0u64, |acc, i| {
+        (acc << 8) | data.get(offset + i).copied().unwrap_or(0) as u64
+    })
 }
@@ -1,25 +1,51 @@
+pub mod archive_handler;
 pub mod epub_handler;
 pub mod mobi_handler;
 pub mod pdf_handler;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::contributor::ROLE_AUTHOR;
+use crate::domain::models::tag::normalize_tag_name;
+use crate::utils::search::html_to_text;
+
+/// Words per printed page used to estimate page counts from word counts.
+pub const WORDS_PER_PAGE: usize = 250;
 
 /// Metadata extracted from an ebook file.
 ///
 /// Used by the import pipeline and the metadata service to populate book
-/// records and present metadata to the frontend.
-#[derive(Serialize, Clone)]
+/// records and present metadata to the frontend. Candidates from a
+/// [`MetadataProvider`](crate::application::provider::MetadataProvider) use
+/// the same shape, with an empty `file_path` and `checksum`.
+#[derive(Serialize, Deserialize, Clone)]
 pub struct BookMetadata {
     /// Book title from the file's metadata.
     pub title: String,
-    /// List of author names from the file's metadata.
+    /// Names of the contributors with the author role, in credit order.
     pub authors: Vec<String>,
+    /// Everyone credited in the file's metadata, in credit order.
+    pub contributors: Vec<ParsedContributor>,
     /// Publication date string, if present.
     pub published_date: Option<String>,
     /// List of publisher names from the file's metadata.
     pub publishers: Vec<String>,
     /// ISBN, if present in the file's metadata.
     pub isbn: Option<String>,
+    /// Name of the series the book belongs to, if any.
+    pub series: Option<String>,
+    /// Position within the series, if given.
+    pub series_index: Option<f32>,
+    /// Subject keywords, normalized and deduplicated.
+    pub tags: Vec<String>,
+    /// Plain-text synopsis, sanitized from the (often HTML) description.
+    pub description: Option<String>,
+    /// BCP-47 language tag, e.g. `"en"` or `"pt-BR"`.
+    pub language: Option<String>,
+    /// Number of words in the text, when it was counted.
+    pub word_count: Option<i32>,
+    /// Real page count for PDFs, estimated from `word_count` otherwise.
+    pub page_count: Option<i32>,
     /// Absolute path to the ebook file on disk.
     pub file_path: String,
     /// Raw cover image bytes and MIME type, if extracted.
@@ -27,3 +53,314 @@ pub struct BookMetadata {
     /// SHA-256 checksum of the file.
     pub checksum: String,
 }
+
+/// A person credited in an ebook file's metadata.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ParsedContributor {
+    /// Display name, e.g. `"Ursula K. Le Guin"`.
+    pub name: String,
+    /// Sort key from the file (EPUB `file-as`) or derived from the name.
+    pub sort_name: Option<String>,
+    /// One of the role constants in [`crate::domain::models::contributor`].
+    pub role: String,
+}
+
+impl ParsedContributor {
+    /// Creates a contributor whose sort name is derived with [`sort_name_for`].
+    pub fn new(name: &str, role: &str) -> Self {
+        Self {
+            name: name.trim().to_string(),
+            sort_name: sort_name_for(name),
+            role: role.to_string(),
+        }
+    }
+}
+
+/// Name suffixes kept after the given names by [`sort_name_for`].
+const NAME_SUFFIXES: &[&str] = &["jr", "jr.", "sr", "sr.", "ii", "iii", "iv"];
+
+/// Surname particles that stay attached to the surname, as in "Le Guin".
+const SURNAME_PARTICLES: &[&str] = &[
+    "da", "de", "del", "della", "der", "di", "du", "la", "le", "van", "von",
+];
+
+/// Derives a `"Last, First"` sort key from a display name.
+///
+/// Names that already contain a comma or consist of a single word are
+/// returned unchanged; blank names yield `None`. Surname particles such as
+/// "Le" or "van" and suffixes such as "Jr." are recognised:
+/// `"Ursula K. Le Guin"` becomes `"Le Guin, Ursula K."` and
+/// `"Martin Luther King Jr."` becomes `"King, Martin Luther, Jr."`.
+pub fn sort_name_for(name: &str) -> Option<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut words: Vec<&str> = name.split_whitespace().collect();
+    if name.contains(',') || words.len() < 2 {
+        return Some(name.to_string());
+    }
+
+    let suffix = match words.last() {
+        Some(last) if NAME_SUFFIXES.contains(&last.to_lowercase().as_str()) => words.pop(),
+        _ => None,
+    };
+    if words.len() < 2 {
+        return Some(name.to_string());
+    }
+
+    let mut surname_start = words.len() - 1;
+    while surname_start > 1
+        && SURNAME_PARTICLES.contains(&words[surname_start - 1].to_lowercase().as_str())
+    {
+        surname_start -= 1;
+    }
+
+    let mut sort_name = format!(
+        "{}, {}",
+        words[surname_start..].join(" "),
+        words[..surname_start].join(" ")
+    );
+    if let Some(suffix) = suffix {
+        sort_name.push_str(", ");
+        sort_name.push_str(suffix);
+    }
+    Some(sort_name)
+}
+
+/// Splits a single metadata string that names several people, such as
+/// `"Terry Pratchett & Neil Gaiman"` or `"A. Smith; B. Jones"`.
+///
+/// Separators are `;`, `&` and the word "and". Commas are left alone since
+/// they usually separate a surname from the given names.
+pub fn split_author_list(raw: &str) -> Vec<String> {
+    raw.split([';', '&'])
+        .flat_map(|part| part.split(" and "))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Wraps plain author names as [`ParsedContributor`]s with the author role,
+/// dropping blanks and duplicates. Falls back to a single "Unknown Author".
+pub fn authors_as_contributors(names: &[String]) -> Vec<ParsedContributor> {
+    let mut contributors: Vec<ParsedContributor> = Vec::new();
+    for name in names {
+        let contributor = ParsedContributor::new(name, ROLE_AUTHOR);
+        if !contributor.name.is_empty() && !contributors.contains(&contributor) {
+            contributors.push(contributor);
+        }
+    }
+    if contributors.is_empty() {
+        contributors.push(ParsedContributor {
+            name: "Unknown Author".to_string(),
+            sort_name: None,
+            role: ROLE_AUTHOR.to_string(),
+        });
+    }
+    contributors
+}
+
+/// Parses a series position such as `"2"`, `"2.5"` or `" 3.0 "`.
+///
+/// Returns `None` for text, negative or non-finite values.
+pub fn parse_series_index(raw: &str) -> Option<f32> {
+    raw.trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|index| index.is_finite() && *index >= 0.0)
+}
+
+/// Turns subject and keyword strings into tag names.
+///
+/// Each value may hold several keywords separated by `,` or `;`. Names are
+/// normalized with [`normalize_tag_name`] and deduplicated case-insensitively,
+/// keeping the first spelling.
+pub fn split_tag_list<S: AsRef<str>>(values: &[S]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for name in values
+        .iter()
+        .flat_map(|value| value.as_ref().split([',', ';']))
+        .filter_map(normalize_tag_name)
+    {
+        if !tags.iter().any(|tag| tag.eq_ignore_ascii_case(&name)) {
+            tags.push(name);
+        }
+    }
+    tags
+}
+
+/// Turns a description that may contain HTML into plain text.
+///
+/// Tags are stripped and entities decoded; each paragraph becomes one line
+/// with its whitespace collapsed. Line breaks in plain-text descriptions are
+/// kept, while in HTML only block elements start a new line. Returns `None`
+/// when nothing is left.
+pub fn clean_description(raw: &str) -> Option<String> {
+    let html = if raw.contains('<') {
+        raw.split_whitespace().collect::<Vec<_>>().join(" ")
+    } else {
+        raw.to_string()
+    };
+    let text = html_to_text(&html)
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Normalizes a language tag to BCP-47 casing, e.g. `"EN_us"` → `"en-US"`.
+///
+/// Underscores are accepted as separators. The primary language subtag is
+/// lowercased, four-letter script subtags are title-cased and two-letter
+/// region subtags uppercased. Returns `None` for values that are not
+/// well-formed tags, such as `"English"` or `"und"`.
+pub fn normalize_language_tag(raw: &str) -> Option<String> {
+    let mut subtags = raw.trim().split(['-', '_']);
+    let primary = subtags.next()?.to_ascii_lowercase();
+    if !(2..=3).contains(&primary.len())
+        || !primary.chars().all(|c| c.is_ascii_alphabetic())
+        || primary == "und"
+    {
+        return None;
+    }
+
+    let mut tag = primary;
+    for subtag in subtags {
+        if subtag.is_empty()
+            || subtag.len() > 8
+            || !subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return None;
+        }
+        let subtag = match subtag.len() {
+            2 if subtag.chars().all(|c| c.is_ascii_alphabetic()) => subtag.to_ascii_uppercase(),
+            4 if subtag.chars().all(|c| c.is_ascii_alphabetic()) => {
+                let lower = subtag.to_ascii_lowercase();
+                lower[..1].to_ascii_uppercase() + &lower[1..]
+            }
+            _ => subtag.to_ascii_lowercase(),
+        };
+        tag.push('-');
+        tag.push_str(&subtag);
+    }
+    Some(tag)
+}
+
+/// Counts the words of a plain text. Tokens without a letter or digit, such
+/// as dashes and ornaments, are not counted.
+pub fn count_words(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|token| token.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+/// Estimates a printed page count from a word count using
+/// [`WORDS_PER_PAGE`], rounding up. Returns `0` only for an empty text.
+pub fn estimate_page_count(word_count: usize) -> usize {
+    word_count.div_ceil(WORDS_PER_PAGE)
+}
+
+/// Names of the contributors with the author role, in credit order.
+pub fn author_names(contributors: &[ParsedContributor]) -> Vec<String> {
+    contributors
+        .iter()
+        .filter(|c| c.role == ROLE_AUTHOR)
+        .map(|c| c.name.clone())
+        .collect()
+}
+
+/// A node of a book's table of contents.
+///
+/// Built from the EPUB 3 nav document / EPUB 2 NCX for EPUBs and from the
+/// outline (bookmarks) dictionary for PDFs.
+#[derive(Serialize, Clone, Debug)]
+pub struct TocEntry {
+    /// Human-readable label of the entry.
+    pub label: String,
+    /// Resolved EPUB resource href (without fragment) the entry points to.
+    pub href: Option<String>,
+    /// EPUB fragment identifier, or the name of an unresolved PDF named
+    /// destination.
+    pub fragment: Option<String>,
+    /// 0-based EPUB spine index of the target chapter, if it is in the spine.
+    pub spine_index: Option<usize>,
+    /// 0-based PDF page index of the target.
+    pub page: Option<u32>,
+    /// Target expressed as a `reading_progress.current_position` value: the
+    /// 1-based page number for PDFs. `None` for EPUBs, whose reader saves a
+    /// layout-dependent scroll offset; those are reached through `fragment`.
+    pub position: Option<String>,
+    /// Nesting level; top-level entries have depth `0`.
+    pub depth: usize,
+    /// Nested child entries, in document order.
+    pub children: Vec<TocEntry>,
+}
+
+/// A rectangle in PDF page coordinates, as reported by `extract_spans`.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    /// X coordinate of the box origin.
+    pub x: f32,
+    /// Y coordinate of the box origin.
+    pub y: f32,
+    /// Width of the box.
+    pub width: f32,
+    /// Height of the box.
+    pub height: f32,
+}
+
+/// A match of an in-book search.
+#[derive(Serialize, Clone, Debug)]
+pub struct SearchHit {
+    /// 0-based EPUB spine index of the chapter containing the match.
+    pub spine_index: Option<usize>,
+    /// 0-based PDF page index containing the match.
+    pub page: Option<u32>,
+    /// Character offset of the match within the chapter's (or page's) text.
+    pub offset: usize,
+    /// Length of the match in characters.
+    pub length: usize,
+    /// The matched text.
+    pub matched: String,
+    /// The match with surrounding context.
+    pub snippet: String,
+    /// Area of the PDF page covered by the match, for highlighting.
+    pub bbox: Option<BoundingBox>,
+}
+
+/// The hits of an in-book search, capped at a maximum count.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct SearchResults {
+    /// The first hits in reading order.
+    pub hits: Vec<SearchHit>,
+    /// Whether the book has more hits than were returned.
+    pub truncated: bool,
+}
+
+impl SearchResults {
+    /// Caps hits, collected with room for one more than `max_hits`, at
+    /// `max_hits`, flagging the results as truncated when the extra hit was
+    /// found.
+    pub fn capped(mut hits: Vec<SearchHit>, max_hits: usize) -> Self {
+        let truncated = hits.len() > max_hits;
+        hits.truncate(max_hits);
+        Self { hits, truncated }
+    }
+}
+
+/// Plain text of one chapter or page, as stored in the library full-text
+/// index.
+#[derive(Serialize, Clone, Debug)]
+pub struct BookTextSegment {
+    /// 0-based EPUB spine index of the chapter the text comes from.
+    pub spine_index: Option<usize>,
+    /// 0-based PDF page index the text comes from.
+    pub page: Option<u32>,
+    /// The flattened text.
+    pub text: String,
+}
@@ -1,14 +1,26 @@
 use base64::{Engine as _, engine::general_purpose};
-use pdf_oxide::PdfDocument;
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use pdf_oxide::{Destination, OutlineItem, PdfDocument};
 use pdf_oxide::extractors::xmp::XmpExtractor;
+use pdf_oxide::object::Object;
+use pdf_oxide::optional_content::decode_pdf_text_string;
 use pdf_oxide::rendering::{RenderOptions, render_page};
+use regex::Regex;
 use serde::Serialize;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use tokio::task::JoinError;
 use walkdir::WalkDir;
 
-use crate::infrastructure::file_handlers::BookMetadata;
+use crate::infrastructure::file_handlers::{
+    BookMetadata, BookTextSegment, BoundingBox, SearchHit, SearchResults, TocEntry, author_names,
+    authors_as_contributors, clean_description, normalize_language_tag, split_author_list,
+    split_tag_list,
+};
 use crate::utils::file::compute_checksum;
+use crate::utils::isbn::normalize_isbn;
+use crate::utils::search::find_matches;
 
 /// A rendered PDF page returned to the frontend.
 #[derive(Serialize, Clone)]
@@ -72,10 +84,131 @@ pub async fn scan_pdfs<P: AsRef<Path> + Send + 'static>(
     .await
 }
 
-/// Parses metadata from a PDF file using XMP metadata extraction.
+/// Number of leading pages searched for an ISBN by [`parse_pdf_meta`].
+pub const ISBN_SCAN_PAGES: usize = 5;
+
+/// Matches an `ISBN`-labelled ISBN-10 or ISBN-13, with optional hyphens or
+/// spaces between digit groups.
+static ISBN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bISBN(?:-?1[03])?[ :]*((?:97[89][ -]?)?\d(?:[ -]?\d){8}[ -]?[\dX])\b")
+        .unwrap()
+});
+
+/// Text entries of a PDF's document information dictionary (`/Info`).
+#[derive(Default)]
+struct InfoDict {
+    title: Option<String>,
+    author: Option<String>,
+    subject: Option<String>,
+    keywords: Option<String>,
+    creation_date: Option<String>,
+}
+
+impl InfoDict {
+    /// Reads the trailer's `/Info` dictionary, resolving an indirect
+    /// reference. Missing or malformed dictionaries yield empty fields.
+    fn read(doc: &PdfDocument) -> Self {
+        let info = doc
+            .trailer()
+            .as_dict()
+            .and_then(|trailer| trailer.get("Info"))
+            .and_then(|obj| match obj.as_reference() {
+                Some(reference) => doc.load_object(reference).ok(),
+                None => Some(obj.clone()),
+            });
+        let Some(dict) = info.as_ref().and_then(Object::as_dict) else {
+            return Self::default();
+        };
+
+        let text = |key: &str| {
+            dict.get(key)
+                .and_then(Object::as_string)
+                .map(|bytes| decode_pdf_text_string(bytes).trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            title: text("Title"),
+            author: text("Author"),
+            subject: text("Subject"),
+            keywords: text("Keywords"),
+            creation_date: text("CreationDate"),
+        }
+    }
+}
+
+/// Converts a PDF date string (`D:YYYYMMDDHHmmSSOHH'mm'`) or an XMP
+/// ISO 8601 timestamp to an ISO date.
+///
+/// Only the date part is kept. Trailing components may be omitted, so the
+/// result is `YYYY-MM-DD`, `YYYY-MM` or `YYYY` depending on the input.
+///
+/// # Returns
+///
+/// `None` when the string does not start with a valid date.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// assert_eq!(parse_pdf_date("D:20230115103000+01'00'").as_deref(), Some("2023-01-15"));
+/// assert_eq!(parse_pdf_date("2019-07-01T12:00:00Z").as_deref(), Some("2019-07-01"));
+/// ```
+pub fn parse_pdf_date(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let raw = raw.strip_prefix("D:").unwrap_or(raw);
+    let date = raw.split(['T', ' ']).next().unwrap_or_default();
+
+    // XMP uses `YYYY-MM-DD`; the Info dictionary packs the digits together.
+    let parts: Vec<&str> = if date.get(4..5) == Some("-") {
+        date.split('-').take(3).collect()
+    } else {
+        let digits = &date[..date.bytes().take_while(u8::is_ascii_digit).count()];
+        [0..4, 4..6, 6..8]
+            .into_iter()
+            .filter_map(|range| digits.get(range))
+            .collect()
+    };
+
+    let year = parts
+        .first()
+        .filter(|y| y.len() == 4)
+        .and_then(|y| y.parse::<i32>().ok())
+        .filter(|y| *y > 0)?;
+    let month = parts.get(1).and_then(|m| m.parse::<u32>().ok());
+    let day = parts.get(2).and_then(|d| d.parse::<u32>().ok());
+
+    match (month, day) {
+        (Some(month), Some(day)) => NaiveDate::from_ymd_opt(year, month, day)
+            .map(|date| date.format("%Y-%m-%d").to_string()),
+        (Some(month), None) if (1..=12).contains(&month) => {
+            Some(format!("{:04}-{:02}", year, month))
+        }
+        (None, _) if parts.len() == 1 => Some(format!("{:04}", year)),
+        _ => None,
+    }
+}
+
+/// Finds the first valid `ISBN`-labelled number in a block of text.
+///
+/// # Returns
 ///
-/// Falls back to defaults ("Unknown Author", "Unknown Title", etc.) when XMP
-/// data is missing. ISBN is not extracted from PDFs.
+/// The ISBN normalized to ISBN-13 with
+/// [`normalize_isbn`](crate::utils::isbn::normalize_isbn), or `None` when the
+/// text has no labelled ISBN with a valid check digit.
+pub fn find_isbn(text: &str) -> Option<String> {
+    ISBN_RE
+        .captures_iter(text)
+        .find_map(|caps| normalize_isbn(&caps[1]))
+}
+
+/// Parses metadata from a PDF file.
+///
+/// XMP metadata is preferred; each field it lacks falls back to the
+/// document information dictionary (`/Title`, `/Author`, `/Subject`,
+/// `/Keywords`, `/CreationDate`). Dates are converted to ISO dates with
+/// [`parse_pdf_date`]. The ISBN is taken from the text of the first
+/// [`ISBN_SCAN_PAGES`] pages. Missing values default to "Unknown Title",
+/// "Unknown Author" and "Unknown Publisher"; the producing application is
+/// never used as the publisher.
 ///
 /// # Arguments
 ///
@@ -84,7 +217,8 @@ pub async fn scan_pdfs<P: AsRef<Path> + Send + 'static>(
 /// # Returns
 ///
 /// A populated [`BookMetadata`] struct with extracted title, authors,
-/// publisher, creation date, and the file's SHA-256 checksum.
+/// creation date, subjects, description, language, ISBN, the page count,
+/// and the file's SHA-256 checksum.
 ///
 /// # Errors
 ///
@@ -97,40 +231,76 @@ pub async fn parse_pdf_meta(
 
     tokio::task::spawn_blocking(move || {
         let doc = PdfDocument::open(&path)?;
+        let xmp = XmpExtractor::extract(&doc)
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        let info = InfoDict::read(&doc);
 
-        let mut title = "Unknown Title".to_string();
-        let mut authors = vec!["Unknown Author".to_string()];
-        let mut publishers = vec!["Unknown Publisher".to_string()];
-        let mut published_date: Option<String> = None;
-
-        if let Ok(Some(xmp)) = XmpExtractor::extract(&doc) {
-            if let Some(t) = &xmp.dc_title
-                && !t.is_empty()
-            {
-                title = t.clone();
-            }
+        let title = xmp
+            .dc_title
+            .clone()
+            .filter(|t| !t.trim().is_empty())
+            .or(info.title)
+            .unwrap_or_else(|| "Unknown Title".to_string());
 
-            if !xmp.dc_creator.is_empty() {
-                authors = xmp.dc_creator.clone();
-            }
+        // A single dc:creator entry often packs several names together, as
+        // does the Info dictionary's /Author.
+        let mut author_list: Vec<String> = xmp
+            .dc_creator
+            .iter()
+            .flat_map(|creator| split_author_list(creator))
+            .collect();
+        if author_list.is_empty()
+            && let Some(author) = &info.author
+        {
+            author_list = split_author_list(author);
+        }
 
-            if let Some(tool) = &xmp.xmp_creator_tool
-                && !tool.is_empty()
-            {
-                publishers = vec![tool.clone()];
-            }
+        let published_date = xmp
+            .xmp_create_date
+            .as_deref()
+            .and_then(parse_pdf_date)
+            .or_else(|| info.creation_date.as_deref().and_then(parse_pdf_date));
 
-            if let Some(date) = &xmp.xmp_create_date {
-                published_date = Some(date.clone());
-            }
+        let mut subjects: Vec<&str> = xmp
+            .dc_subject
+            .iter()
+            .map(String::as_str)
+            .chain(xmp.pdf_keywords.as_deref())
+            .collect();
+        if subjects.is_empty() {
+            subjects.extend(info.keywords.as_deref());
         }
+        let tags = split_tag_list(&subjects);
+
+        let description = xmp
+            .dc_description
+            .as_deref()
+            .and_then(clean_description)
+            .or_else(|| info.subject.as_deref().and_then(clean_description));
+        let language = xmp.dc_language.as_deref().and_then(normalize_language_tag);
 
+        let page_count = doc.page_count().ok();
+        let isbn = (0..page_count.unwrap_or(0).min(ISBN_SCAN_PAGES))
+            .filter_map(|page| doc.extract_text(page).ok())
+            .find_map(|text| find_isbn(&text));
+
+        let contributors = authors_as_contributors(&author_list);
         Ok(BookMetadata {
             title,
-            authors,
+            authors: author_names(&contributors),
+            contributors,
             published_date,
-            publishers,
-            isbn: None,
+            publishers: vec!["Unknown Publisher".to_string()],
+            isbn,
+            series: None,
+            series_index: None,
+            tags,
+            description,
+            language,
+            word_count: None,
+            page_count: page_count.and_then(|n| i32::try_from(n).ok()),
             file_path: path,
             cover_data: None,
             checksum,
@@ -193,6 +363,63 @@ pub async fn get_pdf_page_count(
     .await?
 }
 
+/// Reads the outline (bookmarks) of a PDF file as a table of contents.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the PDF file.
+///
+/// # Returns
+///
+/// The top-level [`TocEntry`] nodes with their children nested below them.
+/// Entries pointing at a page carry its 0-based index in `page`; named
+/// destinations that cannot be resolved keep their name in `fragment`.
+/// Empty when the PDF has no outline.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or the outline
+/// cannot be parsed.
+pub async fn get_pdf_outline(
+    path: &str,
+) -> Result<Vec<TocEntry>, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let doc = PdfDocument::open(&path_str)?;
+        let outline = doc.get_outline()?.unwrap_or_default();
+
+        Ok(outline
+            .iter()
+            .map(|item| build_pdf_toc_entry(item, 0))
+            .collect())
+    })
+    .await?
+}
+
+/// Converts a PDF outline item (and its descendants) into a [`TocEntry`].
+fn build_pdf_toc_entry(item: &OutlineItem, depth: usize) -> TocEntry {
+    let (page, fragment) = match &item.dest {
+        Some(Destination::PageIndex(index)) => (Some(*index as u32), None),
+        Some(Destination::Named(name)) => (None, Some(name.clone())),
+        None => (None, None),
+    };
+
+    TocEntry {
+        label: item.title.trim().to_string(),
+        href: None,
+        fragment,
+        spine_index: None,
+        page,
+        position: page.map(|p| (p + 1).to_string()),
+        depth,
+        children: item
+            .children
+            .iter()
+            .map(|child| build_pdf_toc_entry(child, depth + 1))
+            .collect(),
+    }
+}
+
 /// Renders a specific page of a PDF and extracts its text spans.
 ///
 /// The page is rendered at 150 DPI. Returns the page as a base64-encoded
@@ -234,17 +461,7 @@ pub async fn read_pdf_page(
         let image = render_page(&doc, idx, &render_opts)?;
         let image_data = general_purpose::STANDARD.encode(&image.data);
 
-        let spans = doc.extract_spans(idx).unwrap_or_default();
-        let text_spans: Vec<PdfTextSpan> = spans
-            .into_iter()
-            .map(|s| PdfTextSpan {
-                text: s.text,
-                x: s.bbox.x,
-                y: s.bbox.y,
-                width: s.bbox.width,
-                height: s.bbox.height,
-            })
-            .collect();
+        let text_spans = extract_text_spans(&doc, idx);
 
         Ok(PdfPage {
             page_number,
@@ -256,3 +473,182 @@ pub async fn read_pdf_page(
     })
     .await?
 }
+
+/// Searches the extracted text of every page of a PDF file.
+///
+/// Page text is assembled from the same `extract_spans` output that
+/// [`read_pdf_page`] returns, so each hit carries a bounding box in the
+/// coordinate space of [`PdfPage::text_spans`].
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the PDF file.
+/// * `pattern` - Compiled search pattern (see
+///   [`build_search_pattern`](crate::utils::search::build_search_pattern)).
+/// * `max_hits` - Maximum number of hits to return.
+///
+/// # Returns
+///
+/// The first `max_hits` hits in page order, each with its 0-based page index,
+/// the character offset within the page's text and the area covered by the
+/// match, and whether more were found.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or is not a valid PDF.
+pub async fn search_pdf(
+    path: &str,
+    pattern: Regex,
+    max_hits: usize,
+) -> Result<SearchResults, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let doc = PdfDocument::open(&path_str)?;
+        let page_count = doc.page_count().unwrap_or(0);
+        let limit = max_hits.saturating_add(1);
+        let mut hits = Vec::new();
+
+        for idx in 0..page_count {
+            if hits.len() >= limit {
+                break;
+            }
+            let spans = extract_text_spans(&doc, idx);
+            let (text, ranges) = join_spans(&spans);
+
+            let matches = find_matches(&text, &pattern, limit - hits.len());
+            hits.extend(matches.into_iter().map(|m| SearchHit {
+                spine_index: None,
+                page: Some(idx as u32),
+                offset: m.offset,
+                length: m.length,
+                bbox: match_bbox(&spans, &ranges, &m.byte_range),
+                matched: m.matched,
+                snippet: m.snippet,
+            }));
+        }
+
+        Ok(SearchResults::capped(hits, max_hits))
+    })
+    .await?
+}
+
+/// Extracts the plain text of every page of a PDF file.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the PDF file.
+///
+/// # Returns
+///
+/// One [`BookTextSegment`] per page that has text, in page order.
+///
+/// # Errors
+///
+/// Returns a boxed error when the file cannot be opened or is not a valid PDF.
+pub async fn extract_pdf_text(
+    path: &str,
+) -> Result<Vec<BookTextSegment>, Box<dyn std::error::Error + Send + Sync>> {
+    let path_str = path.to_string();
+    tokio::task::spawn_blocking(move || {
+        let doc = PdfDocument::open(&path_str)?;
+        let page_count = doc.page_count().unwrap_or(0);
+        let mut segments = Vec::new();
+
+        for idx in 0..page_count {
+            let (text, _) = join_spans(&extract_text_spans(&doc, idx));
+            if !text.trim().is_empty() {
+                segments.push(BookTextSegment {
+                    spine_index: None,
+                    page: Some(idx as u32),
+                    text,
+                });
+            }
+        }
+
+        Ok(segments)
+    })
+    .await?
+}
+
+/// Extracts the text spans of a page with their bounding boxes.
+fn extract_text_spans(doc: &PdfDocument, idx: usize) -> Vec<PdfTextSpan> {
+    doc.extract_spans(idx)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|s| PdfTextSpan {
+            text: s.text,
+            x: s.bbox.x,
+            y: s.bbox.y,
+            width: s.bbox.width,
+            height: s.bbox.height,
+        })
+        .collect()
+}
+
+/// Joins span texts into a page's text, separating spans with a space where
+/// neither side already has whitespace. Returns the byte range of each span
+/// within the joined text.
+fn join_spans(spans: &[PdfTextSpan]) -> (String, Vec<Range<usize>>) {
+    let mut text = String::new();
+    let mut ranges = Vec::with_capacity(spans.len());
+
+    for span in spans {
+        if !text.is_empty()
+            && !text.ends_with(char::is_whitespace)
+            && !span.text.starts_with(char::is_whitespace)
+        {
+            text.push(' ');
+        }
+        let start = text.len();
+        text.push_str(&span.text);
+        ranges.push(start..text.len());
+    }
+
+    (text, ranges)
+}
+
+/// Computes the box covering a match in the joined page text.
+///
+/// The part of each overlapped span is interpolated horizontally from
+/// character positions, and the parts are merged into one enclosing box.
+fn match_bbox(
+    spans: &[PdfTextSpan],
+    ranges: &[Range<usize>],
+    matched: &Range<usize>,
+) -> Option<BoundingBox> {
+    let mut bbox: Option<BoundingBox> = None;
+
+    for (span, range) in spans.iter().zip(ranges) {
+        let start = matched.start.max(range.start);
+        let end = matched.end.min(range.end);
+        if start >= end {
+            continue;
+        }
+
+        let span_chars = span.text.chars().count().max(1) as f32;
+        let before = span.text[..start - range.start].chars().count() as f32;
+        let inside = span.text[start - range.start..end - range.start].chars().count() as f32;
+        let part = BoundingBox {
+            x: span.x + span.width * before / span_chars,
+            y: span.y,
+            width: span.width * inside / span_chars,
+            height: span.height,
+        };
+
+        bbox = Some(match bbox {
+            None => part,
+            Some(b) => {
+                let x = b.x.min(part.x);
+                let y = b.y.min(part.y);
+                BoundingBox {
+                    x,
+                    y,
+                    width: (b.x + b.width).max(part.x + part.width) - x,
+                    height: (b.y + b.height).max(part.y + part.height) - y,
+                }
+            }
+        });
+    }
+
+    bbox
+}
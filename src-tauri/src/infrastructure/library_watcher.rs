@@ -0,0 +1,83 @@
+//! Filesystem watcher for the library folders.
+//!
+//! Wraps the platform's native watcher (inotify, FSEvents or
+//! ReadDirectoryChangesW). Events are reduced to the paths they touch and
+//! forwarded to a channel; whether a path appeared or disappeared is decided
+//! by the receiver when it handles the path, which also covers renames and
+//! moves reported as a pair of paths.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Watches the library folders recursively and reports changed paths.
+#[derive(Default)]
+pub struct LibraryWatcher {
+    watcher: Mutex<Option<RecommendedWatcher>>,
+}
+
+impl LibraryWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts the native watcher. Paths of created, modified, renamed and
+    /// deleted entries are sent to `changes`; access events are ignored.
+    ///
+    /// Calling it again replaces the previous watcher, dropping every watched
+    /// folder.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`notify::Error`] when the platform watcher cannot be created.
+    pub fn start(&self, changes: UnboundedSender<PathBuf>) -> notify::Result<()> {
+        let watcher =
+            notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+                Ok(event) if is_content_change(&event.kind) => {
+                    for path in event.paths {
+                        let _ = changes.send(path);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Library watcher error: {}", e),
+            })?;
+        *self.watcher.lock().unwrap() = Some(watcher);
+        Ok(())
+    }
+
+    /// Starts watching a folder and everything below it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`notify::Error`] when the watcher was not started or the
+    /// folder cannot be watched.
+    pub fn watch(&self, root: &Path) -> notify::Result<()> {
+        match self.watcher.lock().unwrap().as_mut() {
+            Some(watcher) => watcher.watch(root, RecursiveMode::Recursive),
+            None => Err(notify::Error::generic("Library watcher is not running")),
+        }
+    }
+
+    /// Stops watching a folder. Does nothing when the watcher was not
+    /// started.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`notify::Error`] when the folder was not being watched.
+    pub fn unwatch(&self, root: &Path) -> notify::Result<()> {
+        match self.watcher.lock().unwrap().as_mut() {
+            Some(watcher) => watcher.unwatch(root),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Whether an event can add, change or remove a file.
+fn is_content_change(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) | EventKind::Any
+    )
+}
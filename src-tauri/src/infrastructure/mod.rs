@@ -1,4 +1,7 @@
-//! Infrastructure layer — database connectivity and ebook file parsing.
+//! Infrastructure layer — database connectivity, ebook file parsing, the
+//! cover thumbnail cache and the library folder watcher.
 
+pub mod cover_cache;
 pub mod database;
 pub mod file_handlers;
+pub mod library_watcher;
@@ -11,7 +11,7 @@
 //!   schema, and ebook file parsers (EPUB, PDF, MOBI).
 //! - **`api`** — Tauri IPC command handlers that bridge the frontend to the
 //!   application layer.
-//! - **`utils`** — Shared helpers for checksums and serialization.
+//! - **`utils`** — Shared helpers for checksums, text search and serialization.
 
 pub mod api;
 pub mod application;
@@ -4,12 +4,14 @@
 //!
 //! Initializes the SQLite database, runs pending migrations, wires up the
 //! application state with all repository implementations, and launches the
-//! Tauri window with registered IPC commands.
+//! Tauri window with registered IPC commands and the `book://` resource
+//! protocol.
 
 use std::sync::Arc;
 
 use diesel::Connection;
 use diesel_migrations::{EmbeddedMigrations, MigrationHarness, embed_migrations};
+use tauri::Manager;
 
 /// Embedded SQL migrations applied on startup.
 pub const MIGRATIONS: EmbeddedMigrations =
@@ -21,6 +23,9 @@ use stellaron_lib::infrastructure::database::database::create_pool;
 #[tokio::main]
 async fn main() {
     dotenvy::dotenv().ok();
+    // Background work (covers, imports, watchers) reports failures it cannot
+    // return through `log`; `RUST_LOG` overrides the default level.
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
 
     let database_url =
         std::env::var("DATABASE_URL").unwrap_or_else(|_| "./database.db".to_string());
@@ -42,36 +47,128 @@ async fn main() {
         bookmark_repo: Arc::new(stellaron_lib::application::repository::bookmark_repo::BookmarkRepoImpl::new()),
         annotation_repo: Arc::new(stellaron_lib::application::repository::annotation_repo::AnnotationRepoImpl::new()),
         reading_progress_repo: Arc::new(stellaron_lib::application::repository::reading_progress_repo::ReadingProgressRepoImpl::new()),
+        search_index_repo: Arc::new(stellaron_lib::application::repository::search_index_repo::SearchIndexRepoImpl::new()),
+        collection_repo: Arc::new(stellaron_lib::application::repository::collection_repo::CollectionRepoImpl::new()),
+        series_repo: Arc::new(stellaron_lib::application::repository::series_repo::SeriesRepoImpl::new()),
+        tag_repo: Arc::new(stellaron_lib::application::repository::tag_repo::TagRepoImpl::new()),
+        library_root_repo: Arc::new(stellaron_lib::application::repository::library_root_repo::LibraryRootRepoImpl::new()),
+        library_settings_repo: Arc::new(stellaron_lib::application::repository::library_settings_repo::LibrarySettingsRepoImpl::new()),
+        metadata_provider: Arc::new(stellaron_lib::application::provider::open_library::OpenLibraryProvider::new()),
+        scan_jobs: Arc::new(stellaron_lib::application::library_scan::ScanJobs::new()),
+        library_watcher: Arc::new(stellaron_lib::infrastructure::library_watcher::LibraryWatcher::new()),
     };
 
+    if let Err(e) = stellaron_lib::application::managed_library::load_library_settings(
+        &app_state.library_settings_repo,
+    )
+    .await
+    {
+        log::error!("Failed to load the library settings: {}", e);
+    }
+
+    if let Err(e) = stellaron_lib::application::search_index::resume_interrupted_indexing(
+        app_state.book_repo.clone(),
+        app_state.search_index_repo.clone(),
+    )
+    .await
+    {
+        log::error!("Failed to resume interrupted indexing: {}", e);
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_fs::init())
         .manage(app_state)
+        .setup(|app| {
+            stellaron_lib::infrastructure::cover_cache::init_cover_cache(
+                app.path().app_cache_dir()?.join("covers"),
+            );
+            stellaron_lib::application::bundle_import::init_import_dir(
+                app.path().app_data_dir()?.join("imports"),
+            );
+            let app_handle = app.handle().clone();
+            tokio::spawn(async move {
+                let state = app_handle.state::<AppState>();
+                if let Err(e) = stellaron_lib::api::handlers::library_handler::start_library_watch(
+                    app_handle.clone(),
+                    &state,
+                )
+                .await
+                {
+                    log::error!("Failed to start the library watcher: {}", e);
+                }
+            });
+            Ok(())
+        })
+        .register_asynchronous_uri_scheme_protocol(
+            stellaron_lib::infrastructure::file_handlers::epub_handler::BOOK_SCHEME,
+            stellaron_lib::api::protocol::book_protocol,
+        )
         .invoke_handler(tauri::generate_handler![
             stellaron_lib::api::commands::book_commands::import_book,
             stellaron_lib::api::commands::book_commands::read_epub,
             stellaron_lib::api::commands::book_commands::read_book,
+            stellaron_lib::api::commands::book_commands::get_epub_spine,
+            stellaron_lib::api::commands::book_commands::read_epub_chapter,
+            stellaron_lib::api::commands::book_commands::get_table_of_contents,
+            stellaron_lib::api::commands::book_commands::search_in_book,
             stellaron_lib::api::commands::book_commands::get_pdf_page_count,
             stellaron_lib::api::commands::book_commands::read_pdf_page,
             stellaron_lib::api::commands::book_commands::list_books,
+            stellaron_lib::api::commands::book_commands::list_books_page,
             stellaron_lib::api::commands::book_commands::get_book_details,
             stellaron_lib::api::commands::book_commands::get_cover_img,
             stellaron_lib::api::commands::book_commands::remove_book,
+            stellaron_lib::api::commands::book_commands::set_book_favorite,
+            stellaron_lib::api::commands::book_commands::set_book_rating,
+            stellaron_lib::api::commands::book_commands::set_book_review,
             stellaron_lib::api::commands::bookmark_commands::add_bookmark,
             stellaron_lib::api::commands::bookmark_commands::get_bookmarks,
             stellaron_lib::api::commands::bookmark_commands::delete_bookmark,
             stellaron_lib::api::commands::annotation_commands::add_annotation,
             stellaron_lib::api::commands::annotation_commands::get_annotations,
             stellaron_lib::api::commands::annotation_commands::delete_annotation,
+            stellaron_lib::api::commands::collection_commands::create_collection,
+            stellaron_lib::api::commands::collection_commands::rename_collection,
+            stellaron_lib::api::commands::collection_commands::delete_collection,
+            stellaron_lib::api::commands::collection_commands::list_collections,
+            stellaron_lib::api::commands::collection_commands::add_book_to_collection,
+            stellaron_lib::api::commands::collection_commands::remove_book_from_collection,
+            stellaron_lib::api::commands::collection_commands::reorder_collection_books,
+            stellaron_lib::api::commands::collection_commands::get_collection_books,
+            stellaron_lib::api::commands::collection_commands::create_smart_collection,
+            stellaron_lib::api::commands::collection_commands::update_smart_collection_rules,
+            stellaron_lib::api::commands::collection_commands::preview_smart_collection,
             stellaron_lib::api::commands::library_commands::scan_books_directory,
+            stellaron_lib::api::commands::library_commands::cancel_library_scan,
+            stellaron_lib::api::commands::library_commands::import_paths,
+            stellaron_lib::api::commands::library_commands::list_library_roots,
+            stellaron_lib::api::commands::library_commands::add_library_root,
+            stellaron_lib::api::commands::library_commands::remove_library_root,
+            stellaron_lib::api::commands::library_commands::get_library_settings,
+            stellaron_lib::api::commands::library_commands::update_library_settings,
+            stellaron_lib::api::commands::library_commands::verify_library,
+            stellaron_lib::api::commands::library_commands::repair_book_file,
+            stellaron_lib::api::commands::search_commands::search_library,
+            stellaron_lib::api::commands::search_commands::rebuild_library_index,
+            stellaron_lib::api::commands::search_commands::get_index_status,
+            stellaron_lib::api::commands::series_commands::get_series_books,
+            stellaron_lib::api::commands::tag_commands::list_tags,
+            stellaron_lib::api::commands::tag_commands::get_tag_books,
+            stellaron_lib::api::commands::tag_commands::add_tag_to_book,
+            stellaron_lib::api::commands::tag_commands::remove_tag_from_book,
+            stellaron_lib::api::commands::tag_commands::rename_tag,
+            stellaron_lib::api::commands::tag_commands::merge_tags,
             stellaron_lib::api::commands::reading_progress_commands::update_reading_progress,
             stellaron_lib::api::commands::reading_progress_commands::get_reading_progress,
             stellaron_lib::api::commands::metadata_commands::fetch_metadata,
             stellaron_lib::api::commands::metadata_commands::list_metadata,
             stellaron_lib::api::commands::metadata_commands::update_metadata,
+            stellaron_lib::api::commands::metadata_commands::find_metadata_candidates,
+            stellaron_lib::api::commands::metadata_commands::apply_metadata_candidate,
+            stellaron_lib::api::commands::metadata_commands::embed_metadata,
             exit_app,
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,126 @@
+//! ISBN normalization and check-digit validation.
+//!
+//! Every ISBN stored in `books.isbn` is an ISBN-13 of bare digits, as
+//! returned by [`normalize_isbn`]. No network lookups are involved.
+
+/// URN prefix used by EPUB `dc:identifier` values, e.g. `urn:isbn:9780306406157`.
+const URN_PREFIX: &str = "urn:isbn:";
+
+/// Strips a `urn:isbn:` or `ISBN` label and every hyphen and space.
+///
+/// The result is upper-cased so an ISBN-10 check digit reads `X`. No
+/// validation is done.
+pub fn strip_isbn(raw: &str) -> String {
+    let value = raw.trim();
+    let value = if let Some(rest) = strip_prefix_ignore_case(value, URN_PREFIX) {
+        rest
+    } else if let Some(rest) = strip_prefix_ignore_case(value, "isbn") {
+        // `ISBN-13: ...`, `ISBN10 ...` or just `ISBN ...`.
+        let rest = rest.strip_prefix('-').unwrap_or(rest);
+        let rest = ["10", "13"]
+            .into_iter()
+            .find_map(|kind| {
+                rest.strip_prefix(kind)
+                    .filter(|r| !r.starts_with(|c: char| c.is_ascii_digit()))
+            })
+            .unwrap_or(rest);
+        rest.trim_start_matches(|c: char| c == ':' || c.is_whitespace())
+    } else {
+        value
+    };
+
+    value
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Whether a string is ten characters — nine digits and a digit or `X` —
+/// with a valid ISBN-10 check digit.
+pub fn is_valid_isbn10(isbn: &str) -> bool {
+    let bytes = isbn.as_bytes();
+    if bytes.len() != 10 || !bytes[..9].iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+    let check = match bytes[9] {
+        b'X' => 10,
+        digit @ b'0'..=b'9' => u32::from(digit - b'0'),
+        _ => return false,
+    };
+
+    let sum: u32 = bytes[..9]
+        .iter()
+        .enumerate()
+        .map(|(i, digit)| (10 - i as u32) * u32::from(digit - b'0'))
+        .sum();
+    (sum + check).is_multiple_of(11)
+}
+
+/// Whether a string is thirteen digits starting with `978` or `979` with a
+/// valid ISBN-13 check digit.
+pub fn is_valid_isbn13(isbn: &str) -> bool {
+    let bytes = isbn.as_bytes();
+    if bytes.len() != 13
+        || !bytes.iter().all(u8::is_ascii_digit)
+        || !(isbn.starts_with("978") || isbn.starts_with("979"))
+    {
+        return false;
+    }
+    isbn13_check_digit(&bytes[..12]) == bytes[12] - b'0'
+}
+
+/// Converts a valid ISBN-10 to its ISBN-13 form (prefix `978`, new check
+/// digit). Returns `None` when the input is not a valid ISBN-10.
+pub fn isbn10_to_isbn13(isbn10: &str) -> Option<String> {
+    if !is_valid_isbn10(isbn10) {
+        return None;
+    }
+    let mut isbn13 = format!("978{}", &isbn10[..9]);
+    let check = isbn13_check_digit(isbn13.as_bytes());
+    isbn13.push(char::from(b'0' + check));
+    Some(isbn13)
+}
+
+/// Normalizes an ISBN in any common spelling to a bare ISBN-13.
+///
+/// Accepts `urn:isbn:` URNs, `ISBN`-labelled values and plain ISBN-10 or
+/// ISBN-13 numbers with or without hyphens and spaces.
+///
+/// # Returns
+///
+/// The ISBN-13 digits, or `None` when the value is not a valid ISBN.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// assert_eq!(normalize_isbn("urn:isbn:978-0-306-40615-7").as_deref(), Some("9780306406157"));
+/// assert_eq!(normalize_isbn("0-306-40615-2").as_deref(), Some("9780306406157"));
+/// assert_eq!(normalize_isbn("0-306-40615-3"), None);
+/// ```
+pub fn normalize_isbn(raw: &str) -> Option<String> {
+    let isbn = strip_isbn(raw);
+    match isbn.len() {
+        10 => isbn10_to_isbn13(&isbn),
+        13 if is_valid_isbn13(&isbn) => Some(isbn),
+        _ => None,
+    }
+}
+
+/// ISBN-13 check digit of twelve ASCII digits (weights 1 and 3).
+fn isbn13_check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, digit)| u32::from(digit - b'0') * if i % 2 == 0 { 1 } else { 3 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// `str::strip_prefix` with an ASCII case-insensitive prefix.
+fn strip_prefix_ignore_case<'a>(value: &'a str, prefix: &str) -> Option<&'a str> {
+    value
+        .get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .map(|_| &value[prefix.len()..])
+}
@@ -1,5 +1,8 @@
-//! Shared utilities for checksums and custom serde serialization.
+//! Shared utilities for checksums, ISBNs, text search and custom serde
+//! serialization.
 
 pub mod deserializers;
 pub mod file;
+pub mod isbn;
+pub mod search;
 pub mod serializers;
@@ -0,0 +1,184 @@
+use regex::{Regex, RegexBuilder};
+use scraper::{Html, Node};
+
+/// Number of characters of context kept on each side of a match in snippets.
+const SNIPPET_CONTEXT: usize = 40;
+
+/// Default cap on the hits returned by an in-book search, so a query like
+/// `e` cannot build an unbounded result list.
+pub const MAX_SEARCH_HITS: usize = 1000;
+
+/// Elements that start a new line when HTML is flattened to text, so words in
+/// adjacent blocks are not glued together.
+const BLOCK_ELEMENTS: [&str; 24] = [
+    "address", "article", "aside", "blockquote", "br", "dd", "div", "dl", "dt", "figcaption",
+    "figure", "footer", "h1", "h2", "h3", "h4", "h5", "h6", "header", "hr", "li", "p", "pre",
+    "tr",
+];
+
+/// Options controlling how an in-book search query is matched.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearchOptions {
+    /// Match regardless of letter case.
+    pub case_insensitive: bool,
+    /// Only match whole words (the query is wrapped in `\b` anchors).
+    pub whole_word: bool,
+    /// Treat the query as a regular expression instead of literal text.
+    pub regex: bool,
+}
+
+/// A single match of a search pattern inside a block of text.
+#[derive(Debug, Clone)]
+pub struct TextMatch {
+    /// Character (not byte) offset of the match within the text.
+    pub offset: usize,
+    /// Length of the match in characters.
+    pub length: usize,
+    /// Byte range of the match within the text.
+    pub byte_range: std::ops::Range<usize>,
+    /// The matched text.
+    pub matched: String,
+    /// The match with surrounding context, whitespace collapsed.
+    pub snippet: String,
+}
+
+/// Flattens an HTML document or fragment into plain text.
+///
+/// Block-level elements are separated by newlines; the contents of `<head>`,
+/// `<script>` and `<style>` are skipped.
+///
+/// # Arguments
+///
+/// * `html` - The HTML to flatten.
+///
+/// # Returns
+///
+/// The visible text of the document.
+pub fn html_to_text(html: &str) -> String {
+    let document = Html::parse_document(html);
+    let mut text = String::new();
+
+    for node in document.root_element().descendants() {
+        match node.value() {
+            Node::Element(element)
+                if BLOCK_ELEMENTS.contains(&element.name())
+                    && !text.is_empty()
+                    && !text.ends_with('\n') =>
+            {
+                text.push('\n');
+            }
+            Node::Text(content) => {
+                let hidden = node.ancestors().any(|ancestor| {
+                    ancestor
+                        .value()
+                        .as_element()
+                        .map(|e| matches!(e.name(), "head" | "script" | "style"))
+                        .unwrap_or(false)
+                });
+                if !hidden {
+                    text.push_str(content);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// Compiles a search query into a [`Regex`] according to `options`.
+///
+/// Literal queries are escaped unless [`SearchOptions::regex`] is set.
+///
+/// # Arguments
+///
+/// * `query` - The user's search query.
+/// * `options` - Matching options.
+///
+/// # Returns
+///
+/// The compiled pattern.
+///
+/// # Errors
+///
+/// Returns [`regex::Error`] when `options.regex` is set and `query` is not a
+/// valid regular expression.
+pub fn build_search_pattern(query: &str, options: &SearchOptions) -> Result<Regex, regex::Error> {
+    let pattern = if options.regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    let pattern = if options.whole_word {
+        format!(r"\b(?:{})\b", pattern)
+    } else {
+        pattern
+    };
+
+    RegexBuilder::new(&pattern)
+        .case_insensitive(options.case_insensitive)
+        .build()
+}
+
+/// Finds the first `max_hits` non-empty matches of `pattern` in `text`.
+///
+/// # Arguments
+///
+/// * `text` - The text to search.
+/// * `pattern` - A pattern built with [`build_search_pattern`].
+/// * `max_hits` - Maximum number of matches to return; the search stops there.
+///
+/// # Returns
+///
+/// The matches in text order, each with character offsets and a snippet.
+pub fn find_matches(text: &str, pattern: &Regex, max_hits: usize) -> Vec<TextMatch> {
+    let mut matches = Vec::new();
+    let mut byte_pos = 0;
+    let mut char_pos = 0;
+
+    for m in pattern
+        .find_iter(text)
+        .filter(|m| !m.is_empty())
+        .take(max_hits)
+    {
+        char_pos += text[byte_pos..m.start()].chars().count();
+        byte_pos = m.start();
+
+        let length = m.as_str().chars().count();
+        matches.push(TextMatch {
+            offset: char_pos,
+            length,
+            byte_range: m.range(),
+            matched: m.as_str().to_string(),
+            snippet: make_snippet(text, m.start(), m.end()),
+        });
+    }
+
+    matches
+}
+
+/// Cuts a snippet of up to [`SNIPPET_CONTEXT`] characters around a byte range,
+/// collapsing whitespace and marking truncation with an ellipsis.
+fn make_snippet(text: &str, start: usize, end: usize) -> String {
+    let from = text[..start]
+        .char_indices()
+        .rev()
+        .take(SNIPPET_CONTEXT)
+        .last()
+        .map(|(i, _)| i)
+        .unwrap_or(start);
+    let to = text[end..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT)
+        .map(|(i, _)| end + i)
+        .unwrap_or(text.len());
+
+    let mut snippet = text[from..to].split_whitespace().collect::<Vec<_>>().join(" ");
+    if from > 0 {
+        snippet.insert(0, '…');
+    }
+    if to < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
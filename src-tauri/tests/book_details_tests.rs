@@ -0,0 +1,57 @@
+use stellaron_lib::infrastructure::file_handlers::{
+    WORDS_PER_PAGE, clean_description, count_words, estimate_page_count, normalize_language_tag,
+};
+
+#[test]
+fn test_clean_description_strips_html() {
+    let raw = "<p>A <b>thrilling</b> tale &amp; more.</p>\n<p>  Second\n   paragraph. </p>";
+
+    assert_eq!(
+        clean_description(raw).as_deref(),
+        Some("A thrilling tale & more.\nSecond paragraph.")
+    );
+}
+
+#[test]
+fn test_clean_description_plain_text_and_blank() {
+    assert_eq!(
+        clean_description("Just a   synopsis.").as_deref(),
+        Some("Just a synopsis.")
+    );
+    assert_eq!(clean_description("<div> </div>"), None);
+    assert_eq!(clean_description(""), None);
+}
+
+#[test]
+fn test_normalize_language_tag() {
+    let cases = [
+        ("en", Some("en")),
+        ("EN_us", Some("en-US")),
+        (" pt-br ", Some("pt-BR")),
+        ("zh-hant-tw", Some("zh-Hant-TW")),
+        ("es-419", Some("es-419")),
+        ("fra", Some("fra")),
+        ("English", None),
+        ("und", None),
+        ("en--US", None),
+        ("", None),
+    ];
+
+    for (raw, expected) in cases {
+        assert_eq!(normalize_language_tag(raw).as_deref(), expected, "{}", raw);
+    }
+}
+
+#[test]
+fn test_count_words_skips_punctuation_tokens() {
+    assert_eq!(count_words("It was a dark — and stormy — night."), 7);
+    assert_eq!(count_words("  \n * * * \n "), 0);
+}
+
+#[test]
+fn test_estimate_page_count_rounds_up() {
+    assert_eq!(estimate_page_count(0), 0);
+    assert_eq!(estimate_page_count(1), 1);
+    assert_eq!(estimate_page_count(WORDS_PER_PAGE), 1);
+    assert_eq!(estimate_page_count(WORDS_PER_PAGE + 1), 2);
+}
@@ -0,0 +1,119 @@
+use stellaron_lib::domain::dto::book_dto::BookDto;
+use stellaron_lib::domain::models::book::{Book, HydratedBook};
+use stellaron_lib::domain::models::contributor::{Contributor, ROLE_AUTHOR, ROLE_EDITOR};
+use stellaron_lib::domain::repository::BookSort;
+
+fn book(id: i32, title: &str) -> Book {
+    Book {
+        id,
+        title: title.to_string(),
+        published_date: None,
+        publisher_id: Some(7),
+        isbn: None,
+        file_type: Some("epub".into()),
+        file_path: Some(format!("/books/{}.epub", id)),
+        cover_image_path: None,
+        checksum: None,
+        added_at: Some("2024-06-01 12:00:00".into()),
+        is_favorite: false,
+        rating: None,
+        review: None,
+        series_id: None,
+        series_index: None,
+        description: None,
+        language: None,
+        word_count: None,
+        page_count: None,
+        file_status: "ok".into(),
+    }
+}
+
+fn contributor(author_id: i32, name: &str, role: &str, position: i32) -> Contributor {
+    Contributor {
+        author_id,
+        name: name.to_string(),
+        sort_name: None,
+        role: role.to_string(),
+        position,
+    }
+}
+
+#[test]
+fn test_book_dto_from_hydrated_keeps_all_authors_and_progress() {
+    let dto = BookDto::from(HydratedBook {
+        book: book(1, "Good Omens"),
+        contributors: vec![
+            contributor(3, "Terry Pratchett", ROLE_AUTHOR, 0),
+            contributor(4, "Neil Gaiman", ROLE_AUTHOR, 1),
+        ],
+        publisher: Some("Gollancz".into()),
+        series: None,
+        tags: Vec::new(),
+        progress_percentage: Some(42.5),
+        last_read_at: Some("2024-07-01 08:00:00".into()),
+    });
+
+    assert_eq!(dto.id, 1);
+    assert_eq!(dto.author.as_deref(), Some("Terry Pratchett"));
+    assert_eq!(dto.authors, vec!["Terry Pratchett", "Neil Gaiman"]);
+    assert_eq!(dto.contributors.len(), 2);
+    assert_eq!(dto.publisher.as_deref(), Some("Gollancz"));
+    assert_eq!(dto.progress_percentage, Some(42.5));
+    assert_eq!(dto.last_read_at.as_deref(), Some("2024-07-01 08:00:00"));
+}
+
+#[test]
+fn test_book_dto_from_hydrated_without_authors() {
+    let dto = BookDto::from(HydratedBook {
+        book: book(2, "Anonymous"),
+        contributors: Vec::new(),
+        publisher: None,
+        series: None,
+        tags: Vec::new(),
+        progress_percentage: None,
+        last_read_at: None,
+    });
+
+    assert!(dto.author.is_none());
+    assert!(dto.authors.is_empty());
+}
+
+#[test]
+fn test_book_dto_from_hydrated_anthology_falls_back_to_editor() {
+    let dto = BookDto::from(HydratedBook {
+        book: book(3, "The Big Book of Science Fiction"),
+        contributors: vec![
+            contributor(5, "Ann VanderMeer", ROLE_EDITOR, 0),
+            contributor(6, "Jeff VanderMeer", ROLE_EDITOR, 1),
+        ],
+        publisher: None,
+        series: None,
+        tags: Vec::new(),
+        progress_percentage: None,
+        last_read_at: None,
+    });
+
+    assert_eq!(dto.author.as_deref(), Some("Ann VanderMeer"));
+    assert!(dto.authors.is_empty());
+    assert_eq!(dto.contributors[1].role, ROLE_EDITOR);
+}
+
+#[test]
+fn test_book_sort_deserializes_snake_case() {
+    let sorts: Vec<BookSort> =
+        serde_json::from_str(r#"["title", "author", "added_at", "last_read_at", "series"]"#)
+            .unwrap();
+
+    assert_eq!(
+        sorts,
+        vec![
+            BookSort::Title,
+            BookSort::Author,
+            BookSort::AddedAt,
+            BookSort::LastReadAt,
+            BookSort::Series
+        ]
+    );
+    assert_eq!(BookSort::default(), BookSort::Title);
+    assert!(serde_json::from_str::<BookSort>(r#""rating""#).is_err());
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::setup_db_with;
+use diesel::prelude::*;
+use stellaron_lib::application::book::get_book;
+use stellaron_lib::application::repository::author_repo::AuthorRepoImpl;
+use stellaron_lib::application::repository::book_repo::BookRepoImpl;
+use stellaron_lib::application::repository::publisher_repo::PublisherRepoImpl;
+use stellaron_lib::application::repository::tag_repo::TagRepoImpl;
+use stellaron_lib::domain::models::contributor::ROLE_AUTHOR;
+use stellaron_lib::domain::repository::{
+    AuthorCredit, AuthorRepository, BookPatch, BookRepository, NewBook, NewContributorLink,
+    PublisherRepository, TagRepository,
+};
+use stellaron_lib::infrastructure::database::models::schema::{authors, publishers};
+
+/// Author name the test database refuses to insert, to make a link fail.
+const REJECTED_AUTHOR: &str = "Rejected Author";
+
+/// Points the global pool at a fresh, migrated database in the temp
+/// directory. Authors named [`REJECTED_AUTHOR`] fail to insert.
+fn setup() -> String {
+    setup_db_with("book-repo", |conn| {
+        diesel::sql_query(format!(
+            "CREATE TRIGGER reject_author BEFORE INSERT ON authors \
+             WHEN NEW.name = '{}' BEGIN SELECT RAISE(ABORT, 'rejected'); END",
+            REJECTED_AUTHOR
+        ))
+        .execute(conn)
+        .unwrap();
+    })
+}
+
+/// Counts the authors and publishers with the given name.
+fn count_named(url: &str, name: &str) -> (i64, i64) {
+    let mut conn = SqliteConnection::establish(url).unwrap();
+    let authors = authors::table
+        .filter(authors::name.eq(name))
+        .count()
+        .get_result(&mut conn)
+        .unwrap();
+    let publishers = publishers::table
+        .filter(publishers::name.eq(name))
+        .count()
+        .get_result(&mut conn)
+        .unwrap();
+    (authors, publishers)
+}
+
+/// Imports a book credited to `author`, published by `publisher` and tagged
+/// `tag`.
+async fn seed_book(title: &str, author: &str, publisher: &str, tag: &str) -> i32 {
+    let author = AuthorRepoImpl::new()
+        .find_or_create(author, None)
+        .await
+        .unwrap();
+    let publisher = PublisherRepoImpl::new()
+        .find_or_create(publisher)
+        .await
+        .unwrap();
+    let tag = TagRepoImpl::new().find_or_create(tag).await.unwrap();
+
+    let book = NewBook {
+        title: title.to_string(),
+        published_date: None,
+        publisher_id: None,
+        isbn: None,
+        file_type: "epub".to_string(),
+        file_path: format!("/books/{}.epub", title),
+        cover_image_path: None,
+        checksum: Some(format!("checksum-{}", title)),
+        series_id: None,
+        series_index: None,
+        description: None,
+        language: None,
+        word_count: None,
+        page_count: None,
+    };
+    let links = [NewContributorLink {
+        author_id: author.id,
+        role: ROLE_AUTHOR.to_string(),
+    }];
+    BookRepoImpl::new()
+        .import_with_links(book, &links, &[tag.id], Some(publisher.id))
+        .await
+        .unwrap()
+        .id
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_update_with_links_replaces_links_and_removes_orphans() {
+    let url = setup();
+    let book_repo: Arc<dyn BookRepository> = Arc::new(BookRepoImpl::new());
+    let book_id = seed_book("Replaced", "Old Author", "Old House", "stale").await;
+
+    let patch = BookPatch {
+        authors: Some(vec![AuthorCredit {
+            name: "New Author".to_string(),
+            sort_name: None,
+        }]),
+        publisher: Some(Some("New House".to_string())),
+        tags: Some(vec!["fresh".to_string()]),
+        ..Default::default()
+    };
+    book_repo.update_with_links(book_id, patch).await.unwrap();
+
+    let book = get_book(book_id, &book_repo).await.unwrap().unwrap();
+    assert_eq!(book.authors, vec!["New Author"]);
+    assert_eq!(book.publisher.as_deref(), Some("New House"));
+    assert_eq!(book.tags, vec!["fresh"]);
+    assert_eq!(
+        count_named(&url, "Old Author"),
+        (0, 0),
+        "The unlinked author should be deleted"
+    );
+    assert_eq!(
+        count_named(&url, "Old House"),
+        (0, 0),
+        "The unlinked publisher should be deleted"
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_update_with_links_failure_leaves_book_unchanged() {
+    let url = setup();
+    let book_repo: Arc<dyn BookRepository> = Arc::new(BookRepoImpl::new());
+    let book_id = seed_book("Kept", "Kept Author", "Kept House", "kept").await;
+
+    // The title and publisher are written before the author link fails.
+    let patch = BookPatch {
+        title: Some("Changed".to_string()),
+        publisher: Some(Some("Other House".to_string())),
+        authors: Some(vec![AuthorCredit {
+            name: REJECTED_AUTHOR.to_string(),
+            sort_name: None,
+        }]),
+        tags: Some(vec!["other".to_string()]),
+        ..Default::default()
+    };
+    assert!(book_repo.update_with_links(book_id, patch).await.is_err());
+
+    let book = get_book(book_id, &book_repo).await.unwrap().unwrap();
+    assert_eq!(book.title, "Kept");
+    assert_eq!(book.authors, vec!["Kept Author"]);
+    assert_eq!(book.publisher.as_deref(), Some("Kept House"));
+    assert_eq!(book.tags, vec!["kept"]);
+    assert_eq!(count_named(&url, "Other House"), (0, 0));
+    assert_eq!(count_named(&url, "Kept Author"), (1, 0));
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use common::{TestRepos, scratch_dir};
+use stellaron_lib::application::bundle_import::{
+    ImportResult, import_archive, init_import_dir, is_archive,
+};
+use stellaron_lib::application::library_watch::is_ebook_file;
+use stellaron_lib::infrastructure::file_handlers::archive_handler::{
+    extract_entries, is_epub_container,
+};
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+/// Writes a ZIP file holding the given entries.
+fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+    let mut zip = ZipWriter::new(File::create(path).unwrap());
+    for (name, contents) in entries {
+        zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+        zip.write_all(contents).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+#[test]
+fn test_is_archive_uses_outer_extension() {
+    assert!(is_archive(Path::new("/downloads/bundle.zip")));
+    assert!(is_archive(Path::new("/downloads/Comics.CBZ")));
+    assert!(!is_archive(Path::new("/downloads/book.epub")));
+    assert!(!is_archive(Path::new("/downloads/zip")));
+}
+
+#[tokio::test]
+async fn test_is_epub_container() {
+    let dir = scratch_dir("bundle-epub-container");
+    let epub = dir.join("renamed.zip");
+    write_zip(
+        &epub,
+        &[
+            ("mimetype", &b"application/epub+zip"[..]),
+            ("META-INF/container.xml", &b"<container/>"[..]),
+        ],
+    );
+    let bundle = dir.join("bundle.zip");
+    write_zip(&bundle, &[("book.epub", &b"not really an epub"[..])]);
+    let not_zip = dir.join("broken.zip");
+    fs::write(&not_zip, b"plain text").unwrap();
+
+    assert!(is_epub_container(epub).await.unwrap());
+    assert!(!is_epub_container(bundle).await.unwrap());
+    assert!(is_epub_container(not_zip).await.is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_extract_entries_keeps_only_safe_ebook_entries() {
+    let dir = scratch_dir("bundle-extract");
+    let pdf = fs::read("tests/fixtures/test.pdf").unwrap();
+    let archive = dir.join("bundle.zip");
+    write_zip(
+        &archive,
+        &[
+            ("books/test.pdf", pdf.as_slice()),
+            ("books/readme.txt", &b"hello"[..]),
+            ("books/nested.zip", &b"another bundle"[..]),
+            ("__MACOSX/books/._test.pdf", &b"resource fork"[..]),
+            ("../escaped.pdf", &b"outside"[..]),
+        ],
+    );
+    let dest = dir.join("out");
+
+    let entries = extract_entries(archive.clone(), dest.clone(), is_ebook_file)
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, "books/test.pdf");
+    let extracted = entries[0].result.as_ref().unwrap();
+    assert_eq!(extracted, &dest.join("books/test.pdf"));
+    assert_eq!(fs::read(extracted).unwrap(), pdf);
+    assert!(!dir.join("escaped.pdf").exists());
+
+    // Files already there are never overwritten.
+    let again = extract_entries(archive, dest, is_ebook_file).await.unwrap();
+    assert_eq!(again.len(), 1);
+    assert!(again[0].result.is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_import_archive_never_extracts_next_to_bundle() {
+    let dir = scratch_dir("bundle-import-dir");
+    let imports = dir.join("imports");
+    init_import_dir(imports.clone());
+    let bundle = dir.join("downloads/bundle.zip");
+    fs::create_dir_all(bundle.parent().unwrap()).unwrap();
+    write_zip(
+        &bundle,
+        &[("books/broken.epub", &b"not really an epub"[..])],
+    );
+
+    let repos = TestRepos::new();
+    let results = import_archive(&bundle, &repos.library()).await;
+    assert_eq!(results.len(), 1);
+    let entry = bundle.join("books/broken.epub");
+    match &results[0] {
+        ImportResult::Failed { path, .. } => assert_eq!(path.as_str(), entry.to_str().unwrap()),
+        other => panic!("Expected a failure, got {:?}", other),
+    }
+    assert_eq!(
+        fs::read_dir(bundle.parent().unwrap()).unwrap().count(),
+        1,
+        "Nothing should be written next to the bundle"
+    );
+    // The entry that failed to import is deleted again.
+    assert!(!imports.join("bundle/books/broken.epub").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,100 @@
+//! Helpers shared by the integration tests.
+//!
+//! Each test binary compiles its own copy and uses only some of them.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Once};
+
+use diesel::prelude::*;
+use diesel_migrations::{EmbeddedMigrations, MigrationHarness, embed_migrations};
+use stellaron_lib::application::book::LibraryRepos;
+use stellaron_lib::application::repository::author_repo::AuthorRepoImpl;
+use stellaron_lib::application::repository::book_author_repo::BookAuthorRepoImpl;
+use stellaron_lib::application::repository::book_repo::BookRepoImpl;
+use stellaron_lib::application::repository::publisher_repo::PublisherRepoImpl;
+use stellaron_lib::application::repository::series_repo::SeriesRepoImpl;
+use stellaron_lib::application::repository::tag_repo::TagRepoImpl;
+use stellaron_lib::domain::repository::*;
+
+const MIGRATIONS: EmbeddedMigrations =
+    embed_migrations!("./src/infrastructure/database/migrations");
+
+static SETUP: Once = Once::new();
+
+/// Points the global pool at a fresh, migrated database named after `name`
+/// in the temp directory, and returns its URL.
+pub fn setup_db(name: &str) -> String {
+    setup_db_with(name, |_| {})
+}
+
+/// Like [`setup_db`], running `prepare` on the migrated database before the
+/// pool is pointed at it.
+///
+/// The database is only created by the first call of a test binary; later
+/// calls return the same URL.
+pub fn setup_db_with(name: &str, prepare: impl FnOnce(&mut SqliteConnection)) -> String {
+    let path = std::env::temp_dir().join(format!("stellaron-{}-{}.db", name, std::process::id()));
+    let url = path.to_string_lossy().to_string();
+    SETUP.call_once(|| {
+        let _ = fs::remove_file(&path);
+        let mut conn = SqliteConnection::establish(&url).unwrap();
+        conn.run_pending_migrations(MIGRATIONS).unwrap();
+        prepare(&mut conn);
+        // SAFETY: set once, before the pool is first used; the pool reads the
+        // variable when it is created.
+        unsafe { std::env::set_var("DATABASE_URL", &url) };
+    });
+    url
+}
+
+/// Creates an empty scratch directory for a test.
+pub fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("stellaron-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Owns the repositories a [`LibraryRepos`] borrows.
+pub struct TestRepos {
+    pub book_repo: Arc<dyn BookRepository>,
+    pub author_repo: Arc<dyn AuthorRepository>,
+    pub book_author_repo: Arc<dyn BookAuthorRepository>,
+    pub publisher_repo: Arc<dyn PublisherRepository>,
+    pub series_repo: Arc<dyn SeriesRepository>,
+    pub tag_repo: Arc<dyn TagRepository>,
+}
+
+impl Default for TestRepos {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestRepos {
+    /// Creates the Diesel-backed repositories.
+    pub fn new() -> Self {
+        Self {
+            book_repo: Arc::new(BookRepoImpl::new()),
+            author_repo: Arc::new(AuthorRepoImpl::new()),
+            book_author_repo: Arc::new(BookAuthorRepoImpl::new()),
+            publisher_repo: Arc::new(PublisherRepoImpl::new()),
+            series_repo: Arc::new(SeriesRepoImpl::new()),
+            tag_repo: Arc::new(TagRepoImpl::new()),
+        }
+    }
+
+    /// Borrows the repositories as [`LibraryRepos`].
+    pub fn library(&self) -> LibraryRepos<'_> {
+        LibraryRepos {
+            book_repo: &self.book_repo,
+            author_repo: &self.author_repo,
+            book_author_repo: &self.book_author_repo,
+            publisher_repo: &self.publisher_repo,
+            series_repo: &self.series_repo,
+            tag_repo: &self.tag_repo,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use stellaron_lib::domain::models::contributor::*;
+use stellaron_lib::infrastructure::file_handlers::{
+    ParsedContributor, authors_as_contributors, sort_name_for, split_author_list,
+};
+
+#[test]
+fn test_role_from_marc_relator() {
+    assert_eq!(role_from_marc_relator("aut"), Some(ROLE_AUTHOR));
+    assert_eq!(role_from_marc_relator("EDT"), Some(ROLE_EDITOR));
+    assert_eq!(role_from_marc_relator(" trl "), Some(ROLE_TRANSLATOR));
+    assert_eq!(role_from_marc_relator("ill"), Some(ROLE_ILLUSTRATOR));
+    assert_eq!(role_from_marc_relator("translator"), Some(ROLE_TRANSLATOR));
+    assert_eq!(role_from_marc_relator("bkp"), None);
+}
+
+#[test]
+fn test_sort_name_for() {
+    let cases = [
+        ("Neil Gaiman", Some("Gaiman, Neil")),
+        ("Ursula K. Le Guin", Some("Le Guin, Ursula K.")),
+        ("Ludwig van Beethoven", Some("van Beethoven, Ludwig")),
+        ("Martin Luther King Jr.", Some("King, Martin Luther, Jr.")),
+        ("Pratchett, Terry", Some("Pratchett, Terry")),
+        ("Homer", Some("Homer")),
+        ("  ", None),
+    ];
+
+    for (name, expected) in cases {
+        assert_eq!(sort_name_for(name).as_deref(), expected, "{}", name);
+    }
+}
+
+#[test]
+fn test_split_author_list() {
+    assert_eq!(
+        split_author_list("Terry Pratchett & Neil Gaiman"),
+        vec!["Terry Pratchett", "Neil Gaiman"]
+    );
+    assert_eq!(
+        split_author_list("Smith, Anna; Jones, Bob and Carla Diaz"),
+        vec!["Smith, Anna", "Jones, Bob", "Carla Diaz"]
+    );
+    assert!(split_author_list(" ; ").is_empty());
+}
+
+#[test]
+fn test_authors_as_contributors_dedupes_and_falls_back() {
+    let contributors = authors_as_contributors(&[
+        "Jane Doe".to_string(),
+        " Jane Doe ".to_string(),
+        "".to_string(),
+        "John Roe".to_string(),
+    ]);
+
+    assert_eq!(
+        contributors,
+        vec![
+            ParsedContributor::new("Jane Doe", ROLE_AUTHOR),
+            ParsedContributor::new("John Roe", ROLE_AUTHOR),
+        ]
+    );
+    assert_eq!(contributors[1].sort_name.as_deref(), Some("Roe, John"));
+
+    let unknown = authors_as_contributors(&[]);
+    assert_eq!(unknown.len(), 1);
+    assert_eq!(unknown[0].name, "Unknown Author");
+    assert!(unknown[0].sort_name.is_none());
+}
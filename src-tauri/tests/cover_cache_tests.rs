@@ -0,0 +1,128 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use image::{ImageFormat, RgbImage};
+use stellaron_lib::infrastructure::cover_cache::{
+    CoverSize, cover_dir, is_stale, remove_cover_dir, sized_cover_path, write_cover_thumbnails,
+};
+
+/// Creates an empty cache directory unique to one test.
+fn temp_cache_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "stellaron-cover-cache-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("Failed to create cache dir");
+    dir
+}
+
+/// Encodes a solid-colour PNG of the given size.
+fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    RgbImage::from_pixel(width, height, image::Rgb([200, 30, 60]))
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .expect("Failed to encode PNG");
+    bytes
+}
+
+fn dimensions(path: &Path) -> (u32, u32) {
+    image::image_dimensions(path).expect("Failed to read thumbnail")
+}
+
+#[test]
+fn test_write_cover_thumbnails_downscales_each_size() {
+    let cache = temp_cache_dir("sizes");
+
+    let detail = write_cover_thumbnails(&cache, "abc123", &png_bytes(1200, 1800))
+        .expect("Failed to write thumbnails");
+
+    assert_eq!(detail, cover_dir(&cache, "abc123").join("detail.jpg"));
+    for size in CoverSize::ALL {
+        let path = sized_cover_path(&detail, size);
+        assert_eq!(dimensions(&path), size.max_dimensions(), "{:?}", size);
+    }
+    assert!(
+        fs::read_dir(cover_dir(&cache, "abc123"))
+            .unwrap()
+            .all(|entry| entry.unwrap().path().extension().unwrap() == "jpg"),
+        "No partial files should be left behind"
+    );
+
+    fs::remove_dir_all(&cache).ok();
+}
+
+#[test]
+fn test_write_cover_thumbnails_keeps_aspect_ratio_without_upscaling() {
+    let cache = temp_cache_dir("small");
+
+    let detail = write_cover_thumbnails(&cache, "small", &png_bytes(200, 100))
+        .expect("Failed to write thumbnails");
+
+    assert_eq!(
+        dimensions(&sized_cover_path(&detail, CoverSize::Detail)),
+        (200, 100)
+    );
+    assert_eq!(
+        dimensions(&sized_cover_path(&detail, CoverSize::Grid)),
+        (200, 100)
+    );
+    assert_eq!(
+        dimensions(&sized_cover_path(&detail, CoverSize::List)),
+        (96, 48)
+    );
+
+    fs::remove_dir_all(&cache).ok();
+}
+
+#[test]
+fn test_write_cover_thumbnails_rejects_undecodable_bytes() {
+    let cache = temp_cache_dir("invalid");
+
+    assert!(write_cover_thumbnails(&cache, "bad", b"not an image").is_err());
+    assert!(!cover_dir(&cache, "bad").exists());
+
+    fs::remove_dir_all(&cache).ok();
+}
+
+#[test]
+fn test_is_stale_when_cover_missing_or_older_than_book() {
+    let cache = temp_cache_dir("stale");
+    let book = cache.join("book.epub");
+
+    assert!(is_stale(&cache.join("missing.jpg"), &book));
+
+    fs::write(&book, b"book").unwrap();
+    let detail = write_cover_thumbnails(&cache, "fresh", &png_bytes(10, 10)).unwrap();
+    assert!(!is_stale(&detail, &book));
+
+    let file = fs::File::options().write(true).open(&book).unwrap();
+    file.set_modified(
+        fs::metadata(&detail).unwrap().modified().unwrap() + std::time::Duration::from_secs(60),
+    )
+    .unwrap();
+    assert!(is_stale(&detail, &book));
+
+    fs::remove_dir_all(&cache).ok();
+}
+
+#[test]
+fn test_remove_cover_dir_is_idempotent() {
+    let cache = temp_cache_dir("remove");
+    write_cover_thumbnails(&cache, "gone", &png_bytes(10, 10)).unwrap();
+
+    remove_cover_dir(&cache, "gone").expect("First removal should succeed");
+    assert!(!cover_dir(&cache, "gone").exists());
+    remove_cover_dir(&cache, "gone").expect("Removing a missing dir should succeed");
+
+    fs::remove_dir_all(&cache).ok();
+}
+
+#[test]
+fn test_cover_size_deserializes_from_snake_case() {
+    let size: CoverSize = serde_json::from_str("\"grid\"").unwrap();
+    assert_eq!(size, CoverSize::Grid);
+    assert_eq!(CoverSize::default(), CoverSize::Detail);
+}
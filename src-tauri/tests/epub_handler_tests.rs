@@ -4,7 +4,7 @@ use stellaron_lib::utils::file::compute_checksum;
 #[tokio::test]
 async fn test_get_epub_content() {
     let path = "Fundamental-Accessibility-Tests-Basic-Functionality-v2.0.0.epub";
-    let result = get_epub_content(path).await;
+    let result = get_epub_content(path, None).await;
     assert!(
         result.is_ok(),
         "Failed to get epub content: {:?}",
@@ -60,3 +60,394 @@ async fn test_compute_checksum() {
         "SHA-256 checksum should be 64 hex characters"
     );
 }
+
+#[tokio::test]
+async fn test_get_epub_spine() {
+    let path = "Fundamental-Accessibility-Tests-Basic-Functionality-v2.0.0.epub";
+    let result = get_epub_spine(path).await;
+    assert!(result.is_ok(), "Failed to get epub spine: {:?}", result.err());
+    let spine = result.unwrap();
+    assert!(!spine.is_empty(), "Spine should not be empty");
+    assert!(
+        spine.iter().enumerate().all(|(i, item)| item.index == i),
+        "Spine items should be indexed in reading order"
+    );
+    assert!(
+        spine.iter().any(|item| item.length > 0),
+        "Spine items should report their resource length"
+    );
+}
+
+#[tokio::test]
+async fn test_get_epub_chapters_with_prefetch() {
+    let path = "pg1513-images-3.epub";
+    let spine = get_epub_spine(path).await.expect("Failed to get spine");
+    assert!(spine.len() > 2, "Fixture should have several chapters");
+
+    let chapters = get_epub_chapters(path, 1, 1, None)
+        .await
+        .expect("Failed to get chapters");
+    let indices: Vec<usize> = chapters.iter().map(|c| c.index).collect();
+    assert_eq!(indices, vec![0, 1, 2], "Should include one neighbour on each side");
+
+    let first = get_epub_chapters(path, 0, 2, None)
+        .await
+        .expect("Failed to get chapters");
+    assert_eq!(first.first().map(|c| c.index), Some(0), "Prefetch should clamp at 0");
+}
+
+#[tokio::test]
+async fn test_get_epub_chapter_is_sanitized_body() {
+    let path = "Fundamental-Accessibility-Tests-Basic-Functionality-v2.0.0.epub";
+    let spine = get_epub_spine(path).await.expect("Failed to get spine");
+    let readable = spine
+        .iter()
+        .find(|item| item.media_type == "application/xhtml+xml")
+        .expect("Should have an XHTML chapter");
+
+    let chapter = get_epub_chapter(path, readable.index, None)
+        .await
+        .expect("Failed to get chapter");
+    assert_eq!(chapter.idref, readable.idref);
+    assert!(!chapter.html.contains("<body"), "Should return body inner HTML");
+    assert!(!chapter.html.contains("<script"), "Scripts should be stripped");
+}
+
+#[tokio::test]
+async fn test_sanitize_keeps_prose_that_looks_like_attributes() {
+    use rbook::Epub;
+    use rbook::epub::EpubChapter;
+
+    let prose = "Set the variable once = true and the only = answer.";
+    let path = std::env::temp_dir().join(format!(
+        "stellaron-sanitize-prose-{}.epub",
+        std::process::id()
+    ));
+    Epub::builder()
+        .identifier("urn:uuid:9d3e6a2b-41c7-4f0e-8b6a-2c5d7e9f1a34")
+        .title("Prose")
+        .language("en")
+        .chapter(EpubChapter::new("One").xhtml_body(format!(
+            r#"<p>{prose}</p>
+<p onclick="steal()">Click</p>
+<a href=" javascript:steal()">Link</a>
+<a href="two.xhtml">Next</a>
+<script>steal()</script>"#
+        )))
+        .write()
+        .save(&path)
+        .expect("Failed to write prose EPUB");
+    let path = path.to_string_lossy().to_string();
+
+    let chapter = get_epub_chapter(&path, 0, None)
+        .await
+        .expect("Failed to get chapter");
+    assert!(
+        chapter.html.contains(prose),
+        "Prose should come through unchanged"
+    );
+    assert!(
+        !chapter.html.contains("<script"),
+        "Scripts should be stripped"
+    );
+    assert!(
+        !chapter.html.contains("onclick"),
+        "Event handlers should be stripped"
+    );
+    assert!(
+        !chapter.html.contains("javascript:"),
+        "Script URLs should be stripped"
+    );
+    assert!(
+        chapter.html.contains(r#"href="two.xhtml""#),
+        "Plain links are kept"
+    );
+
+    let content = get_epub_content(&path, None)
+        .await
+        .expect("Failed to get content");
+    assert!(
+        content.contains(prose),
+        "Prose should come through unchanged"
+    );
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_get_epub_chapter_out_of_range() {
+    let path = "Fundamental-Accessibility-Tests-Basic-Functionality-v2.0.0.epub";
+    let result = get_epub_chapter(path, 9999, None).await;
+    assert!(result.is_err(), "Should fail for out-of-range chapter");
+}
+
+#[tokio::test]
+async fn test_read_epub_resource() {
+    let path = "pg1513-images-3.epub";
+    let spine = get_epub_spine(path).await.expect("Failed to get spine");
+    let item = spine.first().expect("Spine should not be empty");
+
+    let (bytes, media_type) = read_epub_resource(path, &item.href)
+        .await
+        .expect("Failed to read resource")
+        .expect("Spine href should be in the manifest");
+    assert_eq!(media_type, item.media_type, "Should use the manifest MIME type");
+    assert_eq!(bytes.len() as u64, item.length);
+
+    let missing = read_epub_resource(path, "/does/not/exist.png")
+        .await
+        .expect("Missing resources should not be an error");
+    assert!(missing.is_none());
+}
+
+#[test]
+fn test_book_resource_url() {
+    let url = book_resource_url(7, "/EPUB/images/cover.jpg");
+    assert!(url.ends_with("/7/EPUB/images/cover.jpg"));
+    assert!(url.starts_with("book://") || url.starts_with("http://book.localhost/"));
+
+    // Each segment is percent-encoded once, and parsing the request path
+    // gives the href back.
+    let url = book_resource_url(7, "EPUB/Images/cover image.jpg");
+    assert!(url.ends_with("/7/EPUB/Images/cover%20image.jpg"));
+    assert_eq!(book_resource_url(7, "EPUB/Images/cover%20image.jpg"), url);
+    assert_eq!(
+        parse_book_resource_path(url_path(&url)),
+        Some((7, "EPUB/Images/cover image.jpg".to_string()))
+    );
+
+    assert_eq!(parse_book_resource_path("/7/"), None);
+    assert_eq!(parse_book_resource_path("/seven/cover.jpg"), None);
+    assert_eq!(parse_book_resource_path("/7/%FF.jpg"), None);
+}
+
+#[tokio::test]
+async fn test_chapter_links_encoded_resources_and_styles() {
+    let path = write_styled_epub("styled");
+    let chapters = get_epub_chapters(&path, 0, 0, Some(3))
+        .await
+        .expect("Failed to render chapter");
+    let chapter = &chapters[0];
+
+    let image_url = chapter
+        .html
+        .split('"')
+        .find(|part| part.contains("cover%20image.jpg"))
+        .expect("Image should be linked through the protocol");
+    let (book_id, href) = parse_book_resource_path(url_path(image_url)).unwrap();
+    assert_eq!(book_id, 3);
+    let (bytes, media_type) = read_epub_resource(&path, &href)
+        .await
+        .expect("Failed to read resource")
+        .expect("Decoded href should be in the manifest");
+    assert_eq!(bytes, b"not really a jpeg");
+    assert_eq!(media_type, "image/jpeg");
+
+    assert!(
+        chapter.styles.contains("color: teal"),
+        "Inline styles are kept"
+    );
+    let stylesheet_url = chapter
+        .styles
+        .split('"')
+        .find(|part| part.ends_with("main.css"))
+        .expect("Stylesheet should be linked through the protocol");
+    assert!(stylesheet_url.contains("/3/"));
+
+    // Without a book ID the stylesheet is inlined.
+    let chapters = get_epub_chapters(&path, 0, 0, None).await.unwrap();
+    assert!(chapters[0].styles.contains("font-family: serif"));
+    assert!(!chapters[0].styles.contains("<link"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// The path of a `book://` URL, below the host.
+fn url_path(url: &str) -> &str {
+    url.split_once("localhost")
+        .expect("book URLs name localhost")
+        .1
+}
+
+/// Writes an EPUB whose chapter links a stylesheet and an image with a space
+/// in its name.
+fn write_styled_epub(name: &str) -> String {
+    use rbook::Epub;
+    use rbook::epub::EpubChapter;
+
+    let path =
+        std::env::temp_dir().join(format!("stellaron-{}-{}.epub", name, std::process::id()));
+    Epub::builder()
+        .identifier("urn:uuid:0b7c2f62-3d2e-4d7a-9a61-7f4c1e5d2b90")
+        .title("Styled")
+        .language("en")
+        .resource([
+            ("styles/main.css", &b"body { font-family: serif; }"[..]),
+            ("Images/cover image.jpg", &b"not really a jpeg"[..]),
+        ])
+        .chapter(EpubChapter::new("One").href("one.xhtml").xhtml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml">
+  <head>
+    <title>One</title>
+    <link rel="stylesheet" type="text/css" href="styles/main.css"/>
+    <style>p { color: teal; }</style>
+  </head>
+  <body><p><img src="Images/cover%20image.jpg" alt="Cover"/></p></body>
+</html>"#,
+        ))
+        .write()
+        .save(&path)
+        .expect("Failed to write styled EPUB");
+    path.to_string_lossy().to_string()
+}
+
+#[tokio::test]
+async fn test_get_epub_toc() {
+    let path = "pg1513-images-3.epub";
+    let result = get_epub_toc(path).await;
+    assert!(result.is_ok(), "Failed to get epub toc: {:?}", result.err());
+    let toc = result.unwrap();
+    assert!(!toc.is_empty(), "Fixture should have a table of contents");
+    assert!(toc.iter().all(|e| e.depth == 0), "Top-level entries have depth 0");
+    assert!(toc.iter().all(|e| !e.label.is_empty()), "Entries should have labels");
+    assert!(
+        toc.iter().any(|e| e.spine_index.is_some()),
+        "Entries should map onto the spine"
+    );
+
+    assert!(
+        toc.iter().all(|e| e.position.is_none()),
+        "EPUB entries carry no scroll position"
+    );
+    for entry in toc.iter().filter(|e| e.spine_index.is_some()) {
+        assert!(entry.href.is_some(), "Mapped entries have an href");
+        assert!(entry.children.iter().all(|c| c.depth == 1));
+    }
+}
+
+/// Writes a minimal EPUB with placeholder metadata to a temp file.
+fn write_sample_epub(name: &str) -> String {
+    use rbook::Epub;
+    use rbook::epub::EpubChapter;
+
+    let path =
+        std::env::temp_dir().join(format!("stellaron-{}-{}.epub", name, std::process::id()));
+    Epub::builder()
+        .identifier("urn:uuid:5f0c3f1e-8f4e-4c2b-9d5a-1a2b3c4d5e6f")
+        .identifier("urn:isbn:0306406152")
+        .title("Old Title")
+        .author("Old Author")
+        .publisher("Old Publisher")
+        .tag(["Old Subject"])
+        .language("en")
+        .chapter(EpubChapter::new("One").xhtml_body("<p>Once upon a time.</p>"))
+        .write()
+        .save(&path)
+        .expect("Failed to write sample EPUB");
+    path.to_string_lossy().to_string()
+}
+
+#[tokio::test]
+async fn test_embed_epub_metadata_round_trips() {
+    use stellaron_lib::infrastructure::file_handlers::{BookMetadata, ParsedContributor};
+
+    let path = write_sample_epub("embed");
+    let before = compute_checksum(&path).await.unwrap();
+
+    let metadata = BookMetadata {
+        title: "The Dispossessed".to_string(),
+        authors: vec!["Ursula K. Le Guin".to_string()],
+        contributors: vec![
+            ParsedContributor::new("Ursula K. Le Guin", "author"),
+            ParsedContributor::new("Jane Doe", "translator"),
+        ],
+        publishers: vec!["Harper & Row".to_string()],
+        published_date: Some("1974-05-01".to_string()),
+        isbn: Some("979-10-343-0451-6".to_string()),
+        series: Some("Hainish Cycle".to_string()),
+        series_index: Some(5.0),
+        tags: vec!["Science fiction".to_string(), "Anarchism".to_string()],
+        description: Some("An ambiguous utopia.".to_string()),
+        language: Some("en-US".to_string()),
+        word_count: None,
+        page_count: None,
+        file_path: path.clone(),
+        cover_data: None,
+        checksum: before.clone(),
+    };
+    embed_epub_metadata(path.clone(), metadata)
+        .await
+        .expect("Failed to embed metadata");
+
+    let parsed = parse_epub_meta(path.clone()).await.unwrap();
+    assert_eq!(parsed.title, "The Dispossessed");
+    assert_eq!(parsed.authors, vec!["Ursula K. Le Guin"]);
+    assert!(
+        parsed
+            .contributors
+            .iter()
+            .any(|c| c.name == "Jane Doe" && c.role == "translator")
+    );
+    assert!(!parsed.contributors.iter().any(|c| c.name == "Old Author"));
+    assert_eq!(parsed.publishers, vec!["Harper & Row"]);
+    assert!(
+        parsed
+            .published_date
+            .as_deref()
+            .is_some_and(|date| date.starts_with("1974-05-01"))
+    );
+    assert_eq!(parsed.isbn.as_deref(), Some("9791034304516"));
+    assert_eq!(parsed.series.as_deref(), Some("Hainish Cycle"));
+    assert_eq!(parsed.series_index, Some(5.0));
+    assert_eq!(parsed.tags, vec!["Science fiction", "Anarchism"]);
+    assert_eq!(parsed.description.as_deref(), Some("An ambiguous utopia."));
+    assert_eq!(parsed.language.as_deref(), Some("en-US"));
+    assert_ne!(
+        parsed.checksum, before,
+        "Rewritten file should have a new checksum"
+    );
+    assert!(
+        !std::path::Path::new(&path)
+            .with_extension("epub.part")
+            .exists(),
+        "Temporary file should be gone"
+    );
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_embed_epub_metadata_keeps_fields_without_value() {
+    use stellaron_lib::infrastructure::file_handlers::BookMetadata;
+
+    let path = write_sample_epub("embed-partial");
+    let metadata = BookMetadata {
+        title: "New Title".to_string(),
+        authors: Vec::new(),
+        contributors: Vec::new(),
+        publishers: Vec::new(),
+        published_date: None,
+        isbn: None,
+        series: None,
+        series_index: None,
+        tags: Vec::new(),
+        description: None,
+        language: None,
+        word_count: None,
+        page_count: None,
+        file_path: path.clone(),
+        cover_data: None,
+        checksum: String::new(),
+    };
+    embed_epub_metadata(path.clone(), metadata).await.unwrap();
+
+    let parsed = parse_epub_meta(path.clone()).await.unwrap();
+    assert_eq!(parsed.title, "New Title");
+    assert_eq!(parsed.authors, vec!["Old Author"]);
+    assert_eq!(parsed.publishers, vec!["Old Publisher"]);
+    assert_eq!(parsed.isbn.as_deref(), Some("9780306406157"));
+    assert_eq!(parsed.tags, vec!["Old Subject"]);
+
+    let _ = std::fs::remove_file(&path);
+}
@@ -1,9 +1,9 @@
-use stellaron_lib::infrastructure::file_handlers::epub_handler::get_epub_content;
+use stellaron_lib::infrastructure::file_handlers::epub_handler::{book_resource_url, get_epub_content};
 
 #[tokio::test]
 async fn test_image_processing_basic() {
     let path = "Fundamental-Accessibility-Tests-Basic-Functionality-v2.0.0.epub";
-    let content = get_epub_content(path).await.expect("Failed to get content");
+    let content = get_epub_content(path, None).await.expect("Failed to get content");
 
     if content.contains("<img") {
         println!("Found img tags in Basic test");
@@ -20,7 +20,7 @@ async fn test_image_processing_basic() {
 #[tokio::test]
 async fn test_image_processing_pg1513() {
     let path = "pg1513-images-3.epub";
-    let content = get_epub_content(path).await.expect("Failed to get content");
+    let content = get_epub_content(path, None).await.expect("Failed to get content");
 
     // Check for <image> tags which are SVG images
     if content.contains("<image") {
@@ -42,3 +42,24 @@ async fn test_image_processing_pg1513() {
         panic!("Did not find <image> tags in PG1513, something is wrong with parsing or the file");
     }
 }
+
+#[tokio::test]
+async fn test_image_processing_book_scheme() {
+    let path = "pg1513-images-3.epub";
+    let content = get_epub_content(path, Some(42))
+        .await
+        .expect("Failed to get content");
+
+    assert!(
+        !content.contains("data:image/"),
+        "Images should be linked, not inlined, when a book ID is given"
+    );
+    assert!(
+        content.contains(&book_resource_url(42, "")),
+        "Images should point at the book:// protocol"
+    );
+    assert!(
+        content.contains("6874819369993830405_cover.jpg"),
+        "Linked URL should keep the manifest href"
+    );
+}
@@ -0,0 +1,68 @@
+use stellaron_lib::utils::isbn::{
+    is_valid_isbn10, is_valid_isbn13, isbn10_to_isbn13, normalize_isbn, strip_isbn,
+};
+
+#[test]
+fn test_strip_isbn_removes_prefixes_and_separators() {
+    let cases = [
+        ("urn:isbn:978-0-306-40615-7", "9780306406157"),
+        ("URN:ISBN:0306406152", "0306406152"),
+        ("ISBN-13: 978 0 306 40615 7", "9780306406157"),
+        ("isbn10 0-8044-2957-x", "080442957X"),
+        ("ISBN 1012345678", "1012345678"),
+        (" 0-306-40615-2 ", "0306406152"),
+    ];
+
+    for (raw, expected) in cases {
+        assert_eq!(strip_isbn(raw), expected, "input: {:?}", raw);
+    }
+}
+
+#[test]
+fn test_isbn10_check_digit() {
+    assert!(is_valid_isbn10("0306406152"));
+    assert!(is_valid_isbn10("080442957X"));
+    assert!(!is_valid_isbn10("0306406153"));
+    assert!(!is_valid_isbn10("X306406152"));
+    assert!(!is_valid_isbn10("030640615"));
+}
+
+#[test]
+fn test_isbn13_check_digit() {
+    assert!(is_valid_isbn13("9780306406157"));
+    assert!(is_valid_isbn13("9791034304516"));
+    assert!(!is_valid_isbn13("9780306406158"));
+    assert!(!is_valid_isbn13("1230306406157"));
+    assert!(!is_valid_isbn13("978030640615X"));
+}
+
+#[test]
+fn test_isbn10_to_isbn13() {
+    assert_eq!(
+        isbn10_to_isbn13("0306406152").as_deref(),
+        Some("9780306406157")
+    );
+    assert_eq!(
+        isbn10_to_isbn13("080442957X").as_deref(),
+        Some("9780804429573")
+    );
+    assert_eq!(isbn10_to_isbn13("0306406153"), None);
+}
+
+#[test]
+fn test_normalize_isbn() {
+    let cases = [
+        ("urn:isbn:978-0-306-40615-7", Some("9780306406157")),
+        ("9780306406157", Some("9780306406157")),
+        ("0-306-40615-2", Some("9780306406157")),
+        ("ISBN 0-8044-2957-X", Some("9780804429573")),
+        ("0-306-40615-3", None),
+        ("urn:uuid:8a9c1f6e-5d0c-4c3a-9a55-3b7f1f0c2d11", None),
+        ("B00ABCDEFG", None),
+        ("", None),
+    ];
+
+    for (raw, expected) in cases {
+        assert_eq!(normalize_isbn(raw).as_deref(), expected, "input: {:?}", raw);
+    }
+}
@@ -0,0 +1,69 @@
+use stellaron_lib::application::library_health::{
+    BookFileCheck, FileRepair, LibraryHealthReport, status_for_checksum,
+};
+use stellaron_lib::domain::models::book::{
+    FILE_STATUS_MISSING, FILE_STATUS_MODIFIED, FILE_STATUS_MOVED, FILE_STATUS_OK,
+};
+
+fn check(book_id: i32, status: &str) -> BookFileCheck {
+    BookFileCheck {
+        book_id,
+        title: format!("Book {}", book_id),
+        file_path: Some(format!("/books/{}.epub", book_id)),
+        status: status.to_string(),
+        found_path: None,
+    }
+}
+
+#[test]
+fn test_status_for_checksum_flags_changed_files() {
+    assert_eq!(status_for_checksum(Some("abc"), "abc"), FILE_STATUS_OK);
+    assert_eq!(
+        status_for_checksum(Some("abc"), "def"),
+        FILE_STATUS_MODIFIED
+    );
+    // Books imported before checksums were stored have nothing to compare.
+    assert_eq!(status_for_checksum(None, "def"), FILE_STATUS_OK);
+}
+
+#[test]
+fn test_health_report_counts_statuses_and_keeps_issues() {
+    let mut report = LibraryHealthReport::default();
+    report.record(check(1, FILE_STATUS_OK));
+    report.record(check(2, FILE_STATUS_MISSING));
+    report.record(check(3, FILE_STATUS_OK));
+    report.record(check(4, FILE_STATUS_MODIFIED));
+    report.record(BookFileCheck {
+        found_path: Some("/books/renamed.epub".to_string()),
+        ..check(5, FILE_STATUS_MOVED)
+    });
+
+    assert_eq!(
+        (report.ok, report.missing, report.moved, report.modified),
+        (2, 1, 1, 1)
+    );
+    let issue_ids: Vec<i32> = report.issues.iter().map(|c| c.book_id).collect();
+    assert_eq!(issue_ids, vec![2, 4, 5]);
+    assert_eq!(
+        report.issues[2].found_path.as_deref(),
+        Some("/books/renamed.epub")
+    );
+}
+
+#[test]
+fn test_file_repair_deserializes_from_action_tag() {
+    let relink: FileRepair =
+        serde_json::from_str(r#"{"action": "relink", "path": "/books/new.epub"}"#).unwrap();
+    assert_eq!(
+        relink,
+        FileRepair::Relink {
+            path: "/books/new.epub".to_string()
+        }
+    );
+    let rehash: FileRepair = serde_json::from_str(r#"{"action": "rehash"}"#).unwrap();
+    assert_eq!(rehash, FileRepair::Rehash);
+    let remove: FileRepair = serde_json::from_str(r#"{"action": "remove"}"#).unwrap();
+    assert_eq!(remove, FileRepair::Remove);
+
+    assert!(serde_json::from_str::<FileRepair>(r#"{"action": "relink"}"#).is_err());
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::Ordering;
+
+use common::{TestRepos, scratch_dir};
+use stellaron_lib::application::library_scan::{
+    ScanCounts, ScanEvent, ScanJobs, ScanSummary, discover_book_files, scan_directory,
+};
+
+/// Creates an empty scratch directory holding copies of the test fixtures
+/// and a file that is not an ebook.
+fn library_dir(name: &str) -> PathBuf {
+    let dir = scratch_dir(&format!("library-scan-{}", name));
+    fs::create_dir_all(dir.join("nested")).expect("Failed to create library dir");
+    fs::copy("tests/fixtures/test.pdf", dir.join("test.pdf")).unwrap();
+    fs::copy("tests/fixtures/test.mobi", dir.join("nested/test.mobi")).unwrap();
+    fs::write(dir.join("notes.txt"), "not a book").unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_discover_book_files_finds_ebooks_recursively() {
+    let dir = library_dir("discover");
+    fs::write(dir.join("nested/comics.cbz"), "bundle").unwrap();
+
+    let mut files = discover_book_files(&dir).await.unwrap();
+    files.sort();
+
+    assert_eq!(
+        files,
+        vec![
+            dir.join("nested/comics.cbz"),
+            dir.join("nested/test.mobi"),
+            dir.join("test.pdf")
+        ]
+    );
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn test_cancelled_scan_reports_discoveries_and_stops() {
+    let dir = library_dir("cancel");
+    let repos = TestRepos::new();
+
+    let jobs = ScanJobs::new();
+    let (job_id, cancelled) = jobs.start();
+    assert!(jobs.cancel(job_id));
+    assert!(cancelled.load(Ordering::Relaxed));
+
+    let events = Mutex::new(Vec::new());
+    let summary = scan_directory(
+        &dir,
+        2,
+        &cancelled,
+        &|event: ScanEvent| events.lock().unwrap().push(event),
+        &repos.library(),
+    )
+    .await
+    .unwrap();
+
+    let expected = ScanSummary {
+        counts: ScanCounts {
+            discovered: 2,
+            ..Default::default()
+        },
+        failures: Vec::new(),
+        cancelled: true,
+    };
+    assert_eq!(summary, expected);
+    assert_eq!(summary.counts.processed(), 0);
+
+    let events = events.into_inner().unwrap();
+    assert_eq!(events.len(), 3);
+    assert!(
+        events[..2]
+            .iter()
+            .all(|event| matches!(event, ScanEvent::Discovered { .. }))
+    );
+    assert_eq!(events[2], ScanEvent::Finished { summary: expected });
+
+    jobs.finish(job_id);
+    assert!(!jobs.cancel(job_id));
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_scan_jobs_hand_out_distinct_ids() {
+    let jobs = ScanJobs::new();
+    let (first, first_cancelled) = jobs.start();
+    let (second, second_cancelled) = jobs.start();
+
+    assert_ne!(first, second);
+    assert!(jobs.cancel(second));
+    assert!(!first_cancelled.load(Ordering::Relaxed));
+    assert!(second_cancelled.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_scan_event_serializes_with_kind_tag() {
+    let failed = serde_json::to_value(ScanEvent::Failed {
+        path: "/books/broken.epub".to_string(),
+        reason: "Parse error: bad zip".to_string(),
+    })
+    .unwrap();
+    assert_eq!(
+        failed,
+        serde_json::json!({
+            "kind": "failed",
+            "path": "/books/broken.epub",
+            "reason": "Parse error: bad zip"
+        })
+    );
+
+    let progress = serde_json::to_value(ScanEvent::Progress {
+        counts: ScanCounts {
+            discovered: 3,
+            imported: 1,
+            duplicates: 1,
+            failed: 0,
+        },
+    })
+    .unwrap();
+    assert_eq!(progress["kind"], "progress");
+    assert_eq!(progress["counts"]["duplicates"], 1);
+}
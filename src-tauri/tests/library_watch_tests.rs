@@ -0,0 +1,106 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use stellaron_lib::application::library_root::check_root_overlap;
+use stellaron_lib::application::library_watch::{
+    PendingChanges, WatchEvent, is_book_file, is_ebook_file,
+};
+use stellaron_lib::domain::error::DomainError;
+use stellaron_lib::domain::models::library_root::LibraryRoot;
+
+fn root(id: i32, path: &str) -> LibraryRoot {
+    LibraryRoot {
+        id,
+        path: path.to_string(),
+        added_at: None,
+    }
+}
+
+#[test]
+fn test_pending_changes_wait_for_paths_to_settle() {
+    let start = Instant::now();
+    let delay = Duration::from_secs(2);
+    let mut pending = PendingChanges::new();
+
+    pending.record(PathBuf::from("/books/b.epub"), start);
+    pending.record(PathBuf::from("/books/a.pdf"), start);
+    pending.record(PathBuf::from("/books/copying.epub"), start);
+    // A later event restarts the delay of a file still being written.
+    pending.record(
+        PathBuf::from("/books/copying.epub"),
+        start + Duration::from_secs(1),
+    );
+
+    assert!(
+        pending
+            .take_settled(start + Duration::from_secs(1), delay)
+            .is_empty()
+    );
+    assert_eq!(
+        pending.take_settled(start + delay, delay),
+        vec![
+            PathBuf::from("/books/a.pdf"),
+            PathBuf::from("/books/b.epub")
+        ]
+    );
+    assert!(!pending.is_empty());
+    assert_eq!(
+        pending.take_settled(start + Duration::from_secs(3), delay),
+        vec![PathBuf::from("/books/copying.epub")]
+    );
+    assert!(pending.is_empty());
+}
+
+#[test]
+fn test_is_book_file_checks_extension() {
+    assert!(is_book_file(Path::new("/books/Dune.epub")));
+    assert!(is_book_file(Path::new("/books/Manual.PDF")));
+    assert!(is_book_file(Path::new("/books/Kindle.mobi")));
+    assert!(!is_book_file(Path::new("/books/notes.txt")));
+    assert!(!is_book_file(Path::new("/books/.Dune.epub.part")));
+    assert!(!is_book_file(Path::new("/books/epub")));
+
+    // Bundles are imported too, but only ebooks are extracted from them.
+    assert!(is_book_file(Path::new("/books/Comics.cbz")));
+    assert!(is_book_file(Path::new("/books/bundle.zip")));
+    assert!(!is_ebook_file(Path::new("/books/bundle.zip")));
+    assert!(is_ebook_file(Path::new("/books/Dune.epub")));
+}
+
+#[test]
+fn test_check_root_overlap_rejects_nested_folders() {
+    let roots = vec![root(1, "/home/reader/Books"), root(2, "/mnt/library")];
+
+    assert!(check_root_overlap(Path::new("/home/reader/Comics"), &roots).is_ok());
+    assert!(check_root_overlap(Path::new("/mnt/library-old"), &roots).is_ok());
+    for overlapping in ["/home/reader/Books", "/home/reader/Books/SF", "/mnt"] {
+        assert!(matches!(
+            check_root_overlap(Path::new(overlapping), &roots),
+            Err(DomainError::InvalidInput(_))
+        ));
+    }
+}
+
+#[test]
+fn test_watch_event_serializes_with_kind_tag() {
+    let missing = serde_json::to_value(WatchEvent::Missing {
+        path: "/books/gone.epub".to_string(),
+        book_id: 4,
+    })
+    .unwrap();
+    assert_eq!(
+        missing,
+        serde_json::json!({
+            "kind": "missing",
+            "path": "/books/gone.epub",
+            "book_id": 4
+        })
+    );
+
+    let relinked = serde_json::to_value(WatchEvent::Relinked {
+        path: "/books/renamed.epub".to_string(),
+        book_id: 4,
+    })
+    .unwrap();
+    assert_eq!(relinked["kind"], "relinked");
+}
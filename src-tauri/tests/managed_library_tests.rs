@@ -0,0 +1,330 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use common::{scratch_dir, setup_db};
+use stellaron_lib::application::book::get_book;
+use stellaron_lib::application::managed_library::{
+    DEFAULT_NAMING_TEMPLATE, MAX_NAME_BYTES, ManagedLibrary, NamingFields, TransferMode,
+    library_settings, render_template, sanitize_component, sync_managed_path, unique_path,
+    update_library_settings,
+};
+use stellaron_lib::application::repository::author_repo::AuthorRepoImpl;
+use stellaron_lib::application::repository::book_repo::BookRepoImpl;
+use stellaron_lib::application::repository::library_settings_repo::LibrarySettingsRepoImpl;
+use stellaron_lib::domain::error::DomainError;
+use stellaron_lib::domain::models::contributor::ROLE_AUTHOR;
+use stellaron_lib::domain::models::library_settings::{IMPORT_MODE_MOVE, LibrarySettings};
+use stellaron_lib::domain::repository::{
+    AuthorRepository, BookRepository, LibrarySettingsRepository, NewBook, NewContributorLink,
+};
+
+/// A managed library in `dir` using the default naming template.
+fn library(dir: &Path, mode: TransferMode) -> ManagedLibrary {
+    ManagedLibrary {
+        dir: dir.to_path_buf(),
+        template: DEFAULT_NAMING_TEMPLATE.to_string(),
+        mode,
+    }
+}
+
+fn fields(title: &str, author: Option<&str>, series: Option<&str>) -> NamingFields {
+    NamingFields {
+        author: author.map(str::to_string),
+        title: title.to_string(),
+        series: series.map(str::to_string),
+        series_index: series.map(|_| 2.0),
+        year: Some("1965".to_string()),
+        ext: "epub".to_string(),
+    }
+}
+
+#[test]
+fn test_render_template_builds_folder_tree() {
+    let dune = fields("Dune Messiah", Some("Frank Herbert"), Some("Dune"));
+    assert_eq!(
+        render_template(DEFAULT_NAMING_TEMPLATE, &dune),
+        PathBuf::from("Frank Herbert/Dune/Dune Messiah.epub")
+    );
+    assert_eq!(
+        render_template(
+            "{author}/{series} {series_index} - {title} ({year}).{ext}",
+            &dune
+        ),
+        PathBuf::from("Frank Herbert/Dune 2 - Dune Messiah (1965).epub")
+    );
+
+    // A standalone book skips the series folder, and a missing author is
+    // filed under a placeholder name.
+    let standalone = fields("Solaris", None, None);
+    assert_eq!(
+        render_template(DEFAULT_NAMING_TEMPLATE, &standalone),
+        PathBuf::from("Unknown Author/Solaris.epub")
+    );
+
+    // Values never add directories of their own.
+    let slashed = fields("Either/Or", Some("Søren Kierkegaard"), None);
+    assert_eq!(
+        render_template(DEFAULT_NAMING_TEMPLATE, &slashed),
+        PathBuf::from("Søren Kierkegaard/Either_Or.epub")
+    );
+
+    // A template without a file name still yields one.
+    assert_eq!(
+        render_template("{author}", &standalone),
+        PathBuf::from("Unknown Author/Solaris.epub")
+    );
+}
+
+#[test]
+fn test_render_template_keeps_extension_of_long_titles() {
+    // Fifty CJK characters already fill MAX_NAME_BYTES.
+    let title = "長".repeat(60);
+    let long = fields(&title, Some("Frank Herbert"), None);
+    let path = render_template(DEFAULT_NAMING_TEMPLATE, &long);
+
+    assert_eq!(path.parent(), Some(Path::new("Frank Herbert")));
+    let name = path.file_name().unwrap().to_string_lossy();
+    assert!(name.len() <= MAX_NAME_BYTES);
+    let stem = name
+        .strip_suffix(".epub")
+        .expect("Extension should be kept");
+    assert!(stem.chars().all(|c| c == '長'));
+}
+
+#[test]
+fn test_sanitize_component() {
+    assert_eq!(sanitize_component("What? Why: <How>"), "What_ Why_ _How_");
+    assert_eq!(sanitize_component("  A \t\n Title.  "), "A Title");
+    assert_eq!(sanitize_component(".."), "");
+    assert_eq!(sanitize_component("con"), "con_");
+    assert_eq!(sanitize_component("COM1.epub"), "COM1.epub_");
+    assert_eq!(sanitize_component("Console"), "Console");
+
+    let long = "é".repeat(MAX_NAME_BYTES);
+    let cut = sanitize_component(&long);
+    assert!(cut.len() <= MAX_NAME_BYTES);
+    assert!(cut.chars().all(|c| c == 'é'));
+}
+
+#[test]
+fn test_unique_path_numbers_collisions() {
+    let dir = std::env::temp_dir().join(format!("stellaron-managed-unique-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let taken = dir.join("Book.epub");
+    fs::write(&taken, b"first").unwrap();
+
+    let free = dir.join("Other.epub");
+    assert_eq!(unique_path(&free, None), free);
+    assert_eq!(unique_path(&taken, None), dir.join("Book (2).epub"));
+    // A book's own file does not collide with itself.
+    assert_eq!(unique_path(&taken, Some(&taken)), taken);
+
+    fs::write(dir.join("Book (2).epub"), b"second").unwrap();
+    assert_eq!(unique_path(&taken, None), dir.join("Book (3).epub"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_adopt_copies_into_library() {
+    let dir = scratch_dir("managed-adopt-copy");
+    let source = dir.join("incoming.epub");
+    fs::write(&source, b"book").unwrap();
+    let library = library(&dir.join("library"), TransferMode::Copy);
+
+    let placed = library
+        .adopt(&source, &fields("Dune", Some("Frank Herbert"), None))
+        .await
+        .unwrap();
+    assert_eq!(placed, dir.join("library/Frank Herbert/Dune.epub"));
+    assert_eq!(fs::read(&placed).unwrap(), b"book");
+    assert!(source.exists(), "Copy mode should keep the original");
+
+    // A file already inside the library stays where it is.
+    assert_eq!(
+        library
+            .adopt(&placed, &fields("Other", None, None))
+            .await
+            .unwrap(),
+        placed
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_adopt_moves_into_library() {
+    let dir = scratch_dir("managed-adopt-move");
+    let source = dir.join("incoming.epub");
+    fs::write(&source, b"book").unwrap();
+    let library = library(&dir.join("library"), TransferMode::Move);
+    let occupied = dir.join("library/Frank Herbert/Dune.epub");
+    fs::create_dir_all(occupied.parent().unwrap()).unwrap();
+    fs::write(&occupied, b"other").unwrap();
+
+    let placed = library
+        .adopt(&source, &fields("Dune", Some("Frank Herbert"), None))
+        .await
+        .unwrap();
+    assert_eq!(placed, dir.join("library/Frank Herbert/Dune (2).epub"));
+    assert_eq!(fs::read(&placed).unwrap(), b"book");
+    assert_eq!(fs::read(&occupied).unwrap(), b"other");
+    assert!(!source.exists(), "Move mode should remove the original");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_release_undoes_adopt() {
+    let dir = scratch_dir("managed-release");
+    let source = dir.join("incoming.epub");
+    fs::write(&source, b"book").unwrap();
+    let book = fields("Dune", Some("Frank Herbert"), None);
+
+    // Copy mode deletes the copy and the folders created for it.
+    let copying = library(&dir.join("library"), TransferMode::Copy);
+    let placed = copying.adopt(&source, &book).await.unwrap();
+    copying.release(&source, &placed).await;
+    assert!(!placed.exists());
+    assert!(!dir.join("library/Frank Herbert").exists());
+    assert!(dir.join("library").exists(), "The library itself is kept");
+    assert_eq!(fs::read(&source).unwrap(), b"book");
+
+    // Move mode puts the file back.
+    let moving = library(&dir.join("library"), TransferMode::Move);
+    let placed = moving.adopt(&source, &book).await.unwrap();
+    assert!(!source.exists());
+    moving.release(&source, &placed).await;
+    assert!(!placed.exists());
+    assert_eq!(fs::read(&source).unwrap(), b"book");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_update_library_settings_validates_and_persists() {
+    setup_db("managed");
+    let settings_repo: Arc<dyn LibrarySettingsRepository> =
+        Arc::new(LibrarySettingsRepoImpl::new());
+    let dir = scratch_dir("managed-settings");
+
+    let rejected = update_library_settings(
+        LibrarySettings {
+            library_dir: Some(dir.to_string_lossy().to_string()),
+            naming_template: String::new(),
+            import_mode: "link".to_string(),
+        },
+        &settings_repo,
+    )
+    .await;
+    assert!(matches!(rejected, Err(DomainError::InvalidInput(_))));
+
+    let saved = update_library_settings(
+        LibrarySettings {
+            library_dir: Some(dir.join("library").to_string_lossy().to_string()),
+            naming_template: "  ".to_string(),
+            import_mode: "Move".to_string(),
+        },
+        &settings_repo,
+    )
+    .await
+    .unwrap();
+    assert!(dir.join("library").is_dir(), "The folder should be created");
+    assert_eq!(saved.naming_template, DEFAULT_NAMING_TEMPLATE);
+    assert_eq!(saved.import_mode, IMPORT_MODE_MOVE);
+    assert_eq!(settings_repo.find().await.unwrap(), Some(saved.clone()));
+    assert_eq!(library_settings(), saved);
+    assert_eq!(ManagedLibrary::current().unwrap().mode, TransferMode::Move);
+
+    // A blank folder turns managed mode off.
+    update_library_settings(
+        LibrarySettings {
+            library_dir: Some(String::new()),
+            ..saved
+        },
+        &settings_repo,
+    )
+    .await
+    .unwrap();
+    assert_eq!(ManagedLibrary::current(), None);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_sync_managed_path_numbers_collisions() {
+    setup_db("managed");
+    let settings_repo: Arc<dyn LibrarySettingsRepository> =
+        Arc::new(LibrarySettingsRepoImpl::new());
+    let book_repo: Arc<dyn BookRepository> = Arc::new(BookRepoImpl::new());
+    let dir = scratch_dir("managed-sync");
+    let settings = update_library_settings(
+        LibrarySettings {
+            library_dir: Some(dir.to_string_lossy().to_string()),
+            naming_template: String::new(),
+            import_mode: "copy".to_string(),
+        },
+        &settings_repo,
+    )
+    .await
+    .unwrap();
+    let dir = PathBuf::from(settings.library_dir.unwrap());
+
+    let current = dir.join("incoming.epub");
+    fs::write(&current, b"book").unwrap();
+    let occupied = dir.join("Frank Herbert/Dune.epub");
+    fs::create_dir_all(occupied.parent().unwrap()).unwrap();
+    fs::write(&occupied, b"other").unwrap();
+
+    let author = AuthorRepoImpl::new()
+        .find_or_create("Frank Herbert", None)
+        .await
+        .unwrap();
+    let book_id = book_repo
+        .import_with_links(
+            NewBook {
+                title: "Dune".to_string(),
+                published_date: None,
+                publisher_id: None,
+                isbn: None,
+                file_type: "epub".to_string(),
+                file_path: current.to_string_lossy().to_string(),
+                cover_image_path: None,
+                checksum: Some("checksum-sync".to_string()),
+                series_id: None,
+                series_index: None,
+                description: None,
+                language: None,
+                word_count: None,
+                page_count: None,
+            },
+            &[NewContributorLink {
+                author_id: author.id,
+                role: ROLE_AUTHOR.to_string(),
+            }],
+            &[],
+            None,
+        )
+        .await
+        .unwrap()
+        .id;
+
+    sync_managed_path(book_id, &book_repo).await.unwrap();
+    let renamed = dir.join("Frank Herbert/Dune (2).epub");
+    let book = get_book(book_id, &book_repo).await.unwrap().unwrap();
+    assert_eq!(book.file_path, Some(renamed.to_string_lossy().to_string()));
+    assert_eq!(fs::read(&renamed).unwrap(), b"book");
+    assert_eq!(fs::read(&occupied).unwrap(), b"other");
+    assert!(!current.exists());
+
+    // The numbered name is the book's own, so a second sync keeps it.
+    sync_managed_path(book_id, &book_repo).await.unwrap();
+    assert!(renamed.exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
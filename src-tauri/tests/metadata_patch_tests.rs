@@ -0,0 +1,124 @@
+use stellaron_lib::application::service::book_service::{MetadataPatch, validate_metadata_patch};
+use stellaron_lib::domain::error::DomainError;
+
+#[test]
+fn test_metadata_patch_deserializes_partial_objects() {
+    let patch: MetadataPatch =
+        serde_json::from_str(r#"{"title": "Kindred", "publisher": null, "tags": []}"#).unwrap();
+
+    assert_eq!(
+        patch,
+        MetadataPatch {
+            title: Some("Kindred".to_string()),
+            publisher: Some(None),
+            tags: Some(Some(Vec::new())),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_metadata_patch_null_clears_missing_keeps() {
+    let patch: MetadataPatch = serde_json::from_str(
+        r#"{"title": null, "isbn": null, "series_index": null, "tags": null}"#,
+    )
+    .unwrap();
+
+    let book_patch = validate_metadata_patch(&patch).unwrap();
+
+    assert!(book_patch.title.is_none(), "A null title is ignored");
+    assert_eq!(book_patch.isbn, Some(None));
+    assert_eq!(book_patch.series_index, Some(None));
+    assert_eq!(book_patch.tags, Some(Vec::new()));
+    assert!(book_patch.publisher.is_none() && book_patch.description.is_none());
+}
+
+#[test]
+fn test_validate_metadata_patch_normalizes_fields() {
+    let patch = MetadataPatch {
+        title: Some("  The Dispossessed ".to_string()),
+        authors: Some(vec![
+            "Ursula K. Le Guin".to_string(),
+            " ".to_string(),
+            "Ursula K. Le Guin".to_string(),
+        ]),
+        publisher: Some(Some("Harper & Row".to_string())),
+        isbn: Some(Some("0-306-40615-2".to_string())),
+        description: Some(Some("<p>An ambiguous utopia.</p>".to_string())),
+        series: Some(Some("Hainish Cycle".to_string())),
+        series_index: Some(Some(5.0)),
+        tags: Some(Some(vec!["Science Fiction; anarchism".to_string()])),
+        ..Default::default()
+    };
+
+    let book_patch = validate_metadata_patch(&patch).unwrap();
+
+    assert_eq!(book_patch.title.as_deref(), Some("The Dispossessed"));
+    let authors = book_patch.authors.unwrap();
+    assert_eq!(authors.len(), 1);
+    assert_eq!(authors[0].name, "Ursula K. Le Guin");
+    assert_eq!(authors[0].sort_name.as_deref(), Some("Le Guin, Ursula K."));
+    assert_eq!(book_patch.publisher, Some(Some("Harper & Row".to_string())));
+    assert_eq!(book_patch.isbn, Some(Some("9780306406157".to_string())));
+    assert_eq!(
+        book_patch.description,
+        Some(Some("An ambiguous utopia.".to_string()))
+    );
+    assert_eq!(book_patch.series, Some(Some("Hainish Cycle".to_string())));
+    assert_eq!(book_patch.series_index, Some(Some(5.0)));
+    assert_eq!(book_patch.tags.unwrap().len(), 2);
+    assert!(book_patch.published_date.is_none());
+}
+
+#[test]
+fn test_validate_metadata_patch_blank_text_clears_fields() {
+    let patch = MetadataPatch {
+        publisher: Some(Some("".to_string())),
+        published_date: Some(Some("  ".to_string())),
+        isbn: Some(Some("".to_string())),
+        description: Some(Some("".to_string())),
+        series: Some(Some(" ".to_string())),
+        tags: Some(Some(Vec::new())),
+        ..Default::default()
+    };
+
+    let book_patch = validate_metadata_patch(&patch).unwrap();
+
+    assert_eq!(book_patch.publisher, Some(None));
+    assert_eq!(book_patch.published_date, Some(None));
+    assert_eq!(book_patch.isbn, Some(None));
+    assert_eq!(book_patch.description, Some(None));
+    assert_eq!(book_patch.series, Some(None));
+    assert_eq!(book_patch.tags, Some(Vec::new()));
+    assert!(book_patch.title.is_none() && book_patch.authors.is_none());
+}
+
+#[test]
+fn test_validate_metadata_patch_rejects_invalid_values() {
+    let blank_title = MetadataPatch {
+        title: Some("   ".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_metadata_patch(&blank_title),
+        Err(DomainError::InvalidInput(_))
+    ));
+
+    let bad_isbn = MetadataPatch {
+        isbn: Some(Some("978-0-306-40615-8".to_string())),
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_metadata_patch(&bad_isbn),
+        Err(DomainError::InvalidIsbn(_))
+    ));
+
+    let negative_index = MetadataPatch {
+        series_index: Some(Some(-1.0)),
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_metadata_patch(&negative_index),
+        Err(DomainError::InvalidInput(_))
+    ));
+}
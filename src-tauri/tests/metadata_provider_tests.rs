@@ -0,0 +1,237 @@
+use stellaron_lib::application::provider::open_library::OpenLibraryProvider;
+use stellaron_lib::application::provider::{MetadataField, MetadataProvider, match_confidence};
+use stellaron_lib::domain::error::DomainError;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// Serves one canned HTTP response on a local port.
+///
+/// Returns the server's base URL and a handle resolving to the request line
+/// (e.g. `GET /search.json?title=... HTTP/1.1`) the provider sent.
+async fn serve_once(status: &str, body: &str) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("Failed to bind mock server");
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = socket.read(&mut buf).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        socket.write_all(response.as_bytes()).await.unwrap();
+        socket.shutdown().await.ok();
+
+        String::from_utf8_lossy(&request)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    });
+
+    (base_url, handle)
+}
+
+#[tokio::test]
+async fn test_lookup_isbn_maps_books_api_record() {
+    let body = r#"{
+        "ISBN:9780306406157": {
+            "key": "/books/OL123M",
+            "title": "The Left Hand of Darkness",
+            "subtitle": "50th Anniversary Edition",
+            "authors": [{"name": "Ursula K. Le Guin", "url": "https://openlibrary.org/authors/OL1A"}],
+            "publishers": [{"name": "Ace Books"}],
+            "publish_date": "1969",
+            "number_of_pages": 304,
+            "subjects": [{"name": "Science fiction"}, {"name": "Gender"}],
+            "identifiers": {"isbn_10": ["0306406152"]},
+            "notes": {"type": "/type/text", "value": "<p>First edition.</p>"},
+            "cover": {"medium": "https://covers.example/m.jpg", "large": "https://covers.example/l.jpg"}
+        }
+    }"#;
+    let (base_url, request) = serve_once("200 OK", body).await;
+
+    let provider = OpenLibraryProvider::with_base_url(format!("{}/", base_url));
+    let candidates = provider
+        .lookup_isbn("9780306406157")
+        .await
+        .expect("Lookup failed");
+
+    let request_line = request.await.unwrap();
+    assert!(
+        request_line.starts_with("GET /api/books?"),
+        "{}",
+        request_line
+    );
+    assert!(
+        request_line.contains("bibkeys=ISBN%3A9780306406157"),
+        "{}",
+        request_line
+    );
+    assert!(request_line.contains("jscmd=data"), "{}", request_line);
+
+    assert_eq!(candidates.len(), 1);
+    let candidate = &candidates[0];
+    assert_eq!(candidate.provider, "open_library");
+    assert_eq!(candidate.source_id.as_deref(), Some("/books/OL123M"));
+    assert_eq!(candidate.confidence, 1.0);
+    assert_eq!(
+        candidate.cover_url.as_deref(),
+        Some("https://covers.example/l.jpg")
+    );
+
+    let metadata = &candidate.metadata;
+    assert_eq!(
+        metadata.title,
+        "The Left Hand of Darkness: 50th Anniversary Edition"
+    );
+    assert_eq!(metadata.authors, vec!["Ursula K. Le Guin"]);
+    assert_eq!(
+        metadata.contributors[0].sort_name.as_deref(),
+        Some("Le Guin, Ursula K.")
+    );
+    assert_eq!(metadata.publishers, vec!["Ace Books"]);
+    assert_eq!(metadata.published_date.as_deref(), Some("1969"));
+    assert_eq!(metadata.isbn.as_deref(), Some("9780306406157"));
+    assert_eq!(metadata.tags, vec!["Science fiction", "Gender"]);
+    assert_eq!(metadata.description.as_deref(), Some("First edition."));
+    assert_eq!(metadata.page_count, Some(304));
+    assert!(metadata.file_path.is_empty() && metadata.checksum.is_empty());
+}
+
+#[tokio::test]
+async fn test_lookup_isbn_without_match_returns_no_candidates() {
+    let (base_url, _request) = serve_once("200 OK", "{}").await;
+
+    let provider = OpenLibraryProvider::with_base_url(base_url);
+    let candidates = provider.lookup_isbn("9780306406157").await.unwrap();
+
+    assert!(candidates.is_empty());
+}
+
+#[tokio::test]
+async fn test_search_ranks_candidates_by_confidence() {
+    let body = r#"{
+        "numFound": 2,
+        "docs": [
+            {
+                "key": "/works/OL2W",
+                "title": "Darkness Visible",
+                "author_name": ["William Styron"],
+                "first_publish_year": 1990
+            },
+            {
+                "key": "/works/OL1W",
+                "title": "The Left Hand of Darkness",
+                "author_name": ["Ursula K. Le Guin"],
+                "first_publish_year": 1969,
+                "publisher": ["Ace Books", "Walker"],
+                "isbn": ["not-an-isbn", "0306406152"],
+                "language": ["eng"],
+                "number_of_pages_median": 286,
+                "cover_i": 42
+            }
+        ]
+    }"#;
+    let (base_url, request) = serve_once("200 OK", body).await;
+
+    let provider = OpenLibraryProvider::with_base_url(base_url);
+    let candidates = provider
+        .search("The Left Hand of Darkness", Some("Le Guin, Ursula"))
+        .await
+        .expect("Search failed");
+
+    let request_line = request.await.unwrap();
+    assert!(
+        request_line.starts_with("GET /search.json?"),
+        "{}",
+        request_line
+    );
+    assert!(
+        request_line.contains("author=Le+Guin%2C+Ursula"),
+        "{}",
+        request_line
+    );
+
+    assert_eq!(candidates.len(), 2);
+    let best = &candidates[0];
+    assert_eq!(best.source_id.as_deref(), Some("/works/OL1W"));
+    assert_eq!(best.confidence, 1.0);
+    assert!(candidates[1].confidence < 0.5);
+    assert_eq!(
+        best.cover_url.as_deref(),
+        Some("https://covers.openlibrary.org/b/id/42-L.jpg")
+    );
+    assert_eq!(best.metadata.publishers, vec!["Ace Books"]);
+    assert_eq!(best.metadata.published_date.as_deref(), Some("1969"));
+    assert_eq!(best.metadata.isbn.as_deref(), Some("9780306406157"));
+    assert_eq!(best.metadata.language.as_deref(), Some("eng"));
+    assert_eq!(best.metadata.page_count, Some(286));
+}
+
+#[tokio::test]
+async fn test_provider_reports_http_errors() {
+    let (base_url, _request) = serve_once("503 Service Unavailable", "{}").await;
+
+    let provider = OpenLibraryProvider::with_base_url(base_url);
+    let result = provider.search("Anything", None).await;
+
+    assert!(matches!(result, Err(DomainError::Provider(_))));
+}
+
+#[test]
+fn test_match_confidence() {
+    let authors = vec!["Ursula K. Le Guin".to_string()];
+
+    assert_eq!(
+        match_confidence("The Dispossessed", None, "the dispossessed", &authors),
+        1.0
+    );
+    assert_eq!(
+        match_confidence(
+            "The Dispossessed",
+            Some("Ursula Le Guin"),
+            "The Dispossessed",
+            &authors
+        ),
+        1.0
+    );
+    let wrong_author = match_confidence(
+        "The Dispossessed",
+        Some("Jane Doe"),
+        "The Dispossessed",
+        &authors,
+    );
+    assert!((wrong_author - 0.7).abs() < 1e-6, "{}", wrong_author);
+    let partial = match_confidence("The Dispossessed", None, "Dispossessed", &authors);
+    assert!((partial - 0.5).abs() < 1e-6, "{}", partial);
+    assert_eq!(match_confidence("", None, "Anything", &authors), 0.0);
+}
+
+#[test]
+fn test_metadata_field_deserializes_from_snake_case() {
+    let fields: Vec<MetadataField> =
+        serde_json::from_str(r#"["title", "published_date", "page_count"]"#).unwrap();
+
+    assert_eq!(
+        fields,
+        vec![
+            MetadataField::Title,
+            MetadataField::PublishedDate,
+            MetadataField::PageCount
+        ]
+    );
+}
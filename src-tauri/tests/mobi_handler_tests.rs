@@ -0,0 +1,103 @@
+use stellaron_lib::infrastructure::file_handlers::mobi_handler::*;
+
+fn fixture_path(name: &str) -> String {
+    format!(
+        "{}/tests/fixtures/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+#[tokio::test]
+async fn test_parse_mobi_meta() {
+    let path = fixture_path("test.mobi");
+    let result = parse_mobi_meta(path).await;
+    assert!(
+        result.is_ok(),
+        "Failed to parse MOBI metadata: {:?}",
+        result.err()
+    );
+    let metadata = result.unwrap();
+    assert_eq!(metadata.title, "Test MOBI Book");
+    assert_eq!(metadata.authors, vec!["Jane Doe", "John Roe"]);
+    assert_eq!(metadata.publishers, vec!["Stellaron Press"]);
+    assert_eq!(metadata.isbn.as_deref(), Some("9780306406157"));
+    assert_eq!(metadata.published_date.as_deref(), Some("2020-01-02"));
+    assert_eq!(
+        metadata.checksum.len(),
+        64,
+        "SHA-256 checksum should be 64 hex characters"
+    );
+
+    let (cover, mime) = metadata.cover_data.expect("Should extract the EXTH cover");
+    assert!(!cover.is_empty(), "Cover image should not be empty");
+    assert_eq!(mime, "image/png");
+}
+
+#[tokio::test]
+async fn test_get_mobi_content() {
+    let path = fixture_path("test.mobi");
+    let result = get_mobi_content(&path).await;
+    assert!(
+        result.is_ok(),
+        "Failed to get MOBI content: {:?}",
+        result.err()
+    );
+    let content = result.unwrap();
+    assert!(content.contains("Chapter One"), "Should decompress PalmDOC text");
+    assert!(content.contains("Chapter Two"), "Should decode every text record");
+    assert!(
+        !content.contains("<body"),
+        "Should return the body's inner HTML only"
+    );
+    assert!(
+        content.contains("data:image/png;base64,"),
+        "recindex images should be inlined as data URIs"
+    );
+}
+
+#[tokio::test]
+async fn test_get_mobi_cover() {
+    let path = fixture_path("test.mobi");
+    let result = get_mobi_cover(&path).await;
+    assert!(
+        result.is_ok(),
+        "Failed to get MOBI cover: {:?}",
+        result.err()
+    );
+    assert!(result.unwrap().starts_with(b"\x89PNG"));
+}
+
+#[tokio::test]
+async fn test_scan_mobis() {
+    let result = scan_mobis(".").await;
+    assert!(result.is_ok(), "Failed to scan MOBI files: {:?}", result.err());
+    let paths = result.unwrap();
+    assert!(
+        paths
+            .iter()
+            .any(|p| p.extension().map(|e| e == "mobi").unwrap_or(false)),
+        "Should find at least one MOBI file"
+    );
+}
+
+#[test]
+fn test_is_mobi_extension() {
+    assert!(is_mobi_extension("mobi"));
+    assert!(is_mobi_extension("AZW3"));
+    assert!(is_mobi_extension("azw"));
+    assert!(!is_mobi_extension("epub"));
+}
+
+#[tokio::test]
+async fn test_parse_mobi_meta_not_mobi() {
+    let path = fixture_path("test.pdf");
+    let result = parse_mobi_meta(path).await;
+    assert!(result.is_err(), "Should reject a non-MOBI file");
+}
+
+#[tokio::test]
+async fn test_parse_mobi_meta_nonexistent() {
+    let result = parse_mobi_meta("nonexistent.mobi".to_string()).await;
+    assert!(result.is_err(), "Should fail for nonexistent file");
+}
@@ -28,6 +28,122 @@ async fn test_parse_pdf_meta() {
         64,
         "SHA-256 checksum should be 64 hex characters"
     );
+    assert_eq!(metadata.page_count, Some(1), "Should report the real page count");
+    assert!(metadata.word_count.is_none());
+    assert_eq!(metadata.title, "Test PDF");
+    assert_eq!(
+        metadata.publishers,
+        vec!["Unknown Publisher".to_string()],
+        "The creator tool must not be used as the publisher"
+    );
+}
+
+/// Writes a one-page PDF without XMP metadata whose Info dictionary holds the
+/// given entries, and whose page shows `page_text`.
+fn write_info_only_pdf(name: &str, info: &str, page_text: &str) -> String {
+    let content = format!("BT /F1 12 Tf 72 720 Td ({}) Tj ET", page_text);
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 4 0 R \
+         /Resources << /Font << /F1 5 0 R >> >> >>"
+            .to_string(),
+        format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            content.len(),
+            content
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        format!("<< {} >>", info),
+    ];
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", i + 1, object));
+    }
+    let xref_offset = pdf.len();
+    pdf.push_str(&format!(
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    ));
+    for offset in offsets {
+        pdf.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    pdf.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R /Info 6 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref_offset
+    ));
+
+    let path = std::env::temp_dir().join(format!("stellaron-{}-{}.pdf", name, std::process::id()));
+    std::fs::write(&path, pdf).expect("Failed to write PDF");
+    path.to_string_lossy().to_string()
+}
+
+#[tokio::test]
+async fn test_parse_pdf_meta_falls_back_to_info_dict() {
+    let path = write_info_only_pdf(
+        "info-dict",
+        "/Title (Info Title) /Author (Jane Doe; John Roe) /Subject (A short summary.) \
+         /Keywords (history, science) /CreationDate (D:20230115103000+01'00') \
+         /Creator (Microsoft Word) /Producer (Microsoft Word)",
+        "ISBN 978-0-306-40615-7",
+    );
+
+    let metadata = parse_pdf_meta(path.clone())
+        .await
+        .expect("Failed to parse PDF");
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(metadata.title, "Info Title");
+    assert_eq!(metadata.authors, vec!["Jane Doe", "John Roe"]);
+    assert_eq!(metadata.description.as_deref(), Some("A short summary."));
+    assert_eq!(metadata.tags, vec!["history", "science"]);
+    assert_eq!(metadata.published_date.as_deref(), Some("2023-01-15"));
+    assert_eq!(metadata.publishers, vec!["Unknown Publisher"]);
+    assert_eq!(metadata.isbn.as_deref(), Some("9780306406157"));
+}
+
+#[test]
+fn test_parse_pdf_date() {
+    let cases = [
+        ("D:20230115103000+01'00'", Some("2023-01-15")),
+        ("D:20230115", Some("2023-01-15")),
+        ("D:20230115103000-05'00'", Some("2023-01-15")),
+        ("D:199812", Some("1998-12")),
+        ("D:2001", Some("2001")),
+        ("20230115103000Z", Some("2023-01-15")),
+        ("2019-07-01T12:00:00Z", Some("2019-07-01")),
+        ("2019-07", Some("2019-07")),
+        ("D:20230231", None),
+        ("D:202313", None),
+        ("yesterday", None),
+        ("", None),
+    ];
+
+    for (raw, expected) in cases {
+        assert_eq!(parse_pdf_date(raw).as_deref(), expected, "input: {:?}", raw);
+    }
+}
+
+#[test]
+fn test_find_isbn() {
+    assert_eq!(
+        find_isbn("Copyright 2020\nISBN: 978-0-306-40615-7 (hardcover)").as_deref(),
+        Some("9780306406157")
+    );
+    assert_eq!(
+        find_isbn("isbn-10 0 306 40615 2").as_deref(),
+        Some("9780306406157")
+    );
+    assert_eq!(
+        find_isbn("ISBN 0306406153 (misprint), ISBN 080442957x").as_deref(),
+        Some("9780804429573")
+    );
+    assert_eq!(find_isbn("Call 978-0-306-40615-7 today"), None);
+    assert_eq!(find_isbn("ISBN pending"), None);
 }
 
 #[tokio::test]
@@ -131,3 +247,16 @@ async fn test_pdf_text_spans() {
         combined_text
     );
 }
+
+#[tokio::test]
+async fn test_get_pdf_outline() {
+    let path = fixture_path("test.pdf");
+    let result = get_pdf_outline(&path).await;
+    assert!(result.is_ok(), "Failed to get PDF outline: {:?}", result.err());
+    for entry in result.unwrap() {
+        assert_eq!(entry.depth, 0);
+        if let Some(page) = entry.page {
+            assert_eq!(entry.position, Some((page + 1).to_string()));
+        }
+    }
+}
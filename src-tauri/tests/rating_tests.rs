@@ -0,0 +1,20 @@
+use stellaron_lib::application::book::{MAX_RATING, validate_rating};
+use stellaron_lib::domain::error::DomainError;
+
+#[test]
+fn test_validate_rating_accepts_whole_and_half_stars() {
+    for rating in [0.0, 0.5, 1.0, 2.5, 4.5, MAX_RATING] {
+        assert!(validate_rating(rating).is_ok(), "{} should be valid", rating);
+    }
+}
+
+#[test]
+fn test_validate_rating_rejects_out_of_range_and_fractions() {
+    for rating in [-0.5, 5.5, 3.3, 4.25, f32::NAN] {
+        assert!(
+            matches!(validate_rating(rating), Err(DomainError::InvalidInput(_))),
+            "{} should be rejected",
+            rating
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use stellaron_lib::infrastructure::file_handlers::{epub_handler, mobi_handler, pdf_handler};
+use stellaron_lib::utils::search::*;
+
+fn fixture_path(name: &str) -> String {
+    format!(
+        "{}/tests/fixtures/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+#[test]
+fn test_build_search_pattern_options() {
+    let literal = build_search_pattern("a.c", &SearchOptions::default()).unwrap();
+    assert!(literal.is_match("xa.cx"));
+    assert!(!literal.is_match("abc"), "Literal queries should be escaped");
+
+    let regex = SearchOptions {
+        regex: true,
+        ..SearchOptions::default()
+    };
+    assert!(build_search_pattern("a.c", &regex).unwrap().is_match("abc"));
+    assert!(build_search_pattern("(", &regex).is_err());
+
+    let insensitive = SearchOptions {
+        case_insensitive: true,
+        ..SearchOptions::default()
+    };
+    assert!(build_search_pattern("romeo", &insensitive).unwrap().is_match("ROMEO"));
+    assert!(!build_search_pattern("romeo", &SearchOptions::default()).unwrap().is_match("ROMEO"));
+
+    let whole_word = SearchOptions {
+        whole_word: true,
+        ..SearchOptions::default()
+    };
+    let pattern = build_search_pattern("art", &whole_word).unwrap();
+    assert!(pattern.is_match("thou art"));
+    assert!(!pattern.is_match("wherefore"), "Should not match inside words");
+    assert!(!pattern.is_match("parting"));
+}
+
+#[test]
+fn test_find_matches_char_offsets_and_snippets() {
+    let text = "café au lait, café noir";
+    let pattern = build_search_pattern("café", &SearchOptions::default()).unwrap();
+    let matches = find_matches(text, &pattern, MAX_SEARCH_HITS);
+
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].offset, 0);
+    assert_eq!(matches[1].offset, 14, "Offsets should count characters, not bytes");
+    assert_eq!(matches[1].length, 4);
+    assert_eq!(matches[1].matched, "café");
+    assert!(matches[1].snippet.contains("café noir"));
+
+    let capped = find_matches(text, &pattern, 1);
+    assert_eq!(capped.len(), 1);
+    assert_eq!(capped[0].offset, 0);
+}
+
+#[test]
+fn test_html_to_text_separates_blocks() {
+    let text = html_to_text(
+        "<html><head><title>Hidden</title><style>p{}</style></head>\
+         <body><p>First</p><p>Second</p><script>var x;</script></body></html>",
+    );
+    assert!(text.contains("First\nSecond"), "Blocks should be separated: {:?}", text);
+    assert!(!text.contains("Hidden"));
+    assert!(!text.contains("var x"));
+}
+
+#[tokio::test]
+async fn test_search_epub() {
+    let options = SearchOptions {
+        case_insensitive: true,
+        whole_word: true,
+        ..SearchOptions::default()
+    };
+    let pattern = build_search_pattern("romeo", &options).unwrap();
+    let results =
+        epub_handler::search_epub("pg1513-images-3.epub", pattern.clone(), MAX_SEARCH_HITS)
+            .await
+            .expect("Failed to search EPUB");
+    assert!(!results.truncated);
+    let hits = results.hits;
+
+    assert!(!hits.is_empty(), "Should find Romeo in Romeo and Juliet");
+    assert!(hits.iter().all(|h| h.spine_index.is_some() && h.page.is_none()));
+    assert!(hits.iter().all(|h| h.matched.eq_ignore_ascii_case("romeo")));
+    assert!(
+        hits.windows(2).all(|w| w[0].spine_index <= w[1].spine_index),
+        "Hits should be in reading order"
+    );
+
+    // Capped searches keep the first hits and report the rest.
+    let capped = epub_handler::search_epub("pg1513-images-3.epub", pattern, 3)
+        .await
+        .expect("Failed to search EPUB");
+    assert!(capped.truncated);
+    assert_eq!(capped.hits.len(), 3);
+    assert_eq!(capped.hits[0].offset, hits[0].offset);
+    assert_eq!(capped.hits[2].spine_index, hits[2].spine_index);
+}
+
+#[tokio::test]
+async fn test_search_pdf_has_bounding_boxes() {
+    let pattern = build_search_pattern("Test", &SearchOptions::default()).unwrap();
+    let hits = pdf_handler::search_pdf(&fixture_path("test.pdf"), pattern, MAX_SEARCH_HITS)
+        .await
+        .expect("Failed to search PDF")
+        .hits;
+
+    assert!(!hits.is_empty(), "Should find 'Test' in the test PDF");
+    for hit in &hits {
+        assert_eq!(hit.page, Some(0));
+        let bbox = hit.bbox.expect("PDF hits should carry a bounding box");
+        assert!(bbox.width > 0.0 && bbox.height > 0.0);
+    }
+}
+
+#[tokio::test]
+async fn test_search_mobi() {
+    let pattern = build_search_pattern("chapter two", &SearchOptions {
+        case_insensitive: true,
+        ..SearchOptions::default()
+    })
+    .unwrap();
+    let hits = mobi_handler::search_mobi(&fixture_path("test.mobi"), pattern, MAX_SEARCH_HITS)
+        .await
+        .expect("Failed to search MOBI")
+        .hits;
+
+    assert_eq!(hits.len(), 1);
+    assert!(hits[0].snippet.contains("Chapter Two"));
+}
+
+#[tokio::test]
+async fn test_extract_epub_text_per_chapter() {
+    let segments = epub_handler::extract_epub_text("pg1513-images-3.epub")
+        .await
+        .expect("Failed to extract EPUB text");
+
+    assert!(segments.len() > 1, "Should extract one segment per chapter");
+    assert!(segments.iter().all(|s| s.spine_index.is_some() && s.page.is_none()));
+    assert!(segments.iter().any(|s| s.text.contains("Romeo")));
+}
+
+#[tokio::test]
+async fn test_extract_pdf_text_per_page() {
+    let segments = pdf_handler::extract_pdf_text(&fixture_path("test.pdf"))
+        .await
+        .expect("Failed to extract PDF text");
+
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].page, Some(0));
+    assert!(segments[0].text.contains("Test"));
+}
+
+#[test]
+fn test_build_match_query_quotes_terms() {
+    use stellaron_lib::application::search_index::build_match_query;
+
+    assert_eq!(build_match_query("  "), None);
+    assert_eq!(
+        build_match_query("romeo \"juliet OR"),
+        Some("\"romeo\" \"juliet\" \"OR\"".to_string())
+    );
+}
+
+#[test]
+fn test_highlight_snippet_escapes_html() {
+    use stellaron_lib::application::search_index::highlight_snippet;
+
+    assert_eq!(
+        highlight_snippet("a <b> \u{2}Romeo\u{3} & c"),
+        "a &lt;b&gt; <mark>Romeo</mark> &amp; c"
+    );
+}
@@ -0,0 +1,84 @@
+use stellaron_lib::application::series::next_unread_in_series;
+use stellaron_lib::domain::dto::book_dto::BookDto;
+use stellaron_lib::domain::models::book::Book;
+use stellaron_lib::infrastructure::file_handlers::parse_series_index;
+
+fn series_book(id: i32, series_index: f32, progress: Option<f32>) -> BookDto {
+    let book = Book {
+        id,
+        title: format!("Book {}", series_index),
+        published_date: None,
+        publisher_id: None,
+        isbn: None,
+        file_type: Some("epub".into()),
+        file_path: Some(format!("/books/{}.epub", id)),
+        cover_image_path: None,
+        checksum: None,
+        added_at: None,
+        is_favorite: false,
+        rating: None,
+        review: None,
+        series_id: Some(1),
+        series_index: Some(series_index),
+        description: None,
+        language: None,
+        word_count: None,
+        page_count: None,
+        file_status: "ok".into(),
+    };
+    let mut dto = BookDto::new(&book, None, None);
+    dto.progress_percentage = progress;
+    dto
+}
+
+fn next_id(books: &[BookDto]) -> Option<i32> {
+    next_unread_in_series(books).map(|book| book.id)
+}
+
+#[test]
+fn test_next_unread_starts_at_first_book() {
+    let books = vec![series_book(10, 1.0, None), series_book(11, 2.0, None)];
+
+    assert_eq!(next_id(&books), Some(10));
+    assert_eq!(next_id(&[]), None);
+}
+
+#[test]
+fn test_next_unread_follows_last_finished_book() {
+    let books = vec![
+        series_book(10, 1.0, Some(100.0)),
+        series_book(11, 2.0, Some(35.0)),
+        series_book(12, 3.0, None),
+    ];
+    assert_eq!(next_id(&books), Some(11), "The book in progress comes next");
+
+    let skipped = vec![
+        series_book(10, 1.0, None),
+        series_book(11, 2.0, Some(100.0)),
+        series_book(12, 2.5, None),
+    ];
+    assert_eq!(
+        next_id(&skipped),
+        Some(12),
+        "Skipped earlier books are not suggested"
+    );
+}
+
+#[test]
+fn test_next_unread_none_when_series_finished() {
+    let books = vec![
+        series_book(10, 1.0, Some(100.0)),
+        series_book(11, 2.0, Some(100.0)),
+    ];
+
+    assert_eq!(next_id(&books), None);
+}
+
+#[test]
+fn test_parse_series_index() {
+    assert_eq!(parse_series_index("2"), Some(2.0));
+    assert_eq!(parse_series_index(" 2.5 "), Some(2.5));
+    assert_eq!(parse_series_index("-1"), None);
+    assert_eq!(parse_series_index("NaN"), None);
+    assert_eq!(parse_series_index("two"), None);
+}
@@ -0,0 +1,186 @@
+use chrono::NaiveDate;
+use stellaron_lib::domain::error::DomainError;
+use stellaron_lib::domain::models::smart_collection::*;
+use stellaron_lib::infrastructure::database::smart_query::*;
+
+fn parse(json: &str) -> RuleGroup {
+    serde_json::from_str(json).expect("rule JSON should deserialize")
+}
+
+fn now() -> chrono::NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 3, 31)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+}
+
+#[test]
+fn test_compile_unread_pdfs_by_author_added_recently() {
+    let rules = parse(
+        r#"{
+            "match": "all",
+            "rules": [
+                {"field": "read_status", "operator": "equals", "value": "unread"},
+                {"field": "file_type", "operator": "equals", "value": "pdf"},
+                {"field": "author", "operator": "contains", "value": "Le Guin"},
+                {"field": "added_at", "operator": "in_last_days", "value": 30}
+            ]
+        }"#,
+    );
+
+    let compiled = compile_rules(&rules, now()).unwrap();
+
+    assert!(compiled.sql.starts_with('(') && compiled.sql.ends_with(')'));
+    assert_eq!(compiled.sql.matches(" AND ").count(), 4);
+    assert!(compiled.sql.contains("COALESCE(rp.progress_percentage, 0) <= 0"));
+    assert!(compiled.sql.contains("COALESCE(b.file_type, '') = ? COLLATE NOCASE"));
+    assert!(compiled.sql.contains("EXISTS (SELECT 1 FROM book_authors ba"));
+    assert!(compiled.sql.contains("b.added_at >= ?"));
+    assert_eq!(
+        compiled.binds,
+        vec![
+            RuleBind::Text("pdf".into()),
+            RuleBind::Text("%Le Guin%".into()),
+            RuleBind::Text("2025-03-01 12:00:00".into()),
+        ]
+    );
+}
+
+#[test]
+fn test_compile_nested_any_group_and_progress_between() {
+    let rules = parse(
+        r#"{
+            "match": "any",
+            "rules": [
+                {"field": "progress", "operator": "between", "value": [10, 90]},
+                {"match": "all", "rules": [
+                    {"field": "title", "operator": "starts_with", "value": "The"},
+                    {"field": "publisher", "operator": "is_empty"}
+                ]}
+            ]
+        }"#,
+    );
+
+    let compiled = compile_rules(&rules, now()).unwrap();
+
+    assert_eq!(
+        compiled.sql,
+        "(COALESCE(rp.progress_percentage, 0) BETWEEN ? AND ? OR \
+         (COALESCE(b.title, '') LIKE ? ESCAPE '\\' AND COALESCE(p.name, '') = ''))"
+    );
+    assert_eq!(
+        compiled.binds,
+        vec![
+            RuleBind::Double(10.0),
+            RuleBind::Double(90.0),
+            RuleBind::Text("The%".into()),
+        ]
+    );
+}
+
+#[test]
+fn test_compile_escapes_like_wildcards_and_negates() {
+    let rules = parse(
+        r#"{"match": "all", "rules": [
+            {"field": "title", "operator": "not_contains", "value": "100%_done"}
+        ]}"#,
+    );
+
+    let compiled = compile_rules(&rules, now()).unwrap();
+
+    assert_eq!(compiled.sql, "(NOT (COALESCE(b.title, '') LIKE ? ESCAPE '\\'))");
+    assert_eq!(compiled.binds, vec![RuleBind::Text("%100\\%\\_done%".into())]);
+}
+
+#[test]
+fn test_compile_empty_groups() {
+    let all = compile_rules(&parse(r#"{"match": "all"}"#), now()).unwrap();
+    let any = compile_rules(&parse(r#"{"match": "any", "rules": []}"#), now()).unwrap();
+
+    assert_eq!(all.sql, "1", "An empty all-group should match every book");
+    assert_eq!(any.sql, "0", "An empty any-group should match no book");
+    assert!(all.binds.is_empty() && any.binds.is_empty());
+}
+
+#[test]
+fn test_compile_rejects_invalid_rules() {
+    let cases = [
+        r#"{"field": "progress", "operator": "contains", "value": "x"}"#,
+        r#"{"field": "added_at", "operator": "between", "value": "2024-01-01"}"#,
+        r#"{"field": "read_status", "operator": "equals", "value": "abandoned"}"#,
+        r#"{"field": "progress", "operator": "greater_than", "value": "lots"}"#,
+        r#"{"field": "added_at", "operator": "in_last_days", "value": -1}"#,
+        r#"{"field": "added_at", "operator": "in_last_days", "value": 1e300}"#,
+        r#"{"field": "added_at", "operator": "in_last_days", "value": "NaN"}"#,
+        r#"{"field": "last_read_at", "operator": "in_last_days", "value": 1e12}"#,
+    ];
+
+    for case in cases {
+        let rules = parse(&format!(r#"{{"match": "all", "rules": [{}]}}"#, case));
+        assert!(
+            matches!(compile_rules(&rules, now()), Err(DomainError::InvalidInput(_))),
+            "Should reject {}",
+            case
+        );
+    }
+}
+
+#[test]
+fn test_compile_rejects_deep_nesting() {
+    let mut group = parse(r#"{"match": "all", "rules": []}"#);
+    for _ in 0..10 {
+        group = RuleGroup {
+            combinator: RuleMatch::Any,
+            rules: vec![Rule::Group(group)],
+        };
+    }
+
+    assert!(matches!(
+        compile_rules(&group, now()),
+        Err(DomainError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_rule_group_json_round_trip() {
+    let rules = parse(
+        r#"{"match": "any", "rules": [
+            {"field": "isbn", "operator": "is_not_empty"},
+            {"match": "all", "rules": [{"field": "last_read_at", "operator": "before", "value": "2024-06-01"}]}
+        ]}"#,
+    );
+
+    assert_eq!(rules.combinator, RuleMatch::Any);
+    assert!(matches!(&rules.rules[0], Rule::Condition(c) if c.field == RuleField::Isbn));
+    assert!(matches!(&rules.rules[1], Rule::Group(g) if g.combinator == RuleMatch::All));
+
+    let json = serde_json::to_string(&rules).unwrap();
+    assert_eq!(serde_json::from_str::<RuleGroup>(&json).unwrap(), rules);
+}
+
+#[test]
+fn test_compile_favorite_and_rating() {
+    let rules = parse(
+        r#"{"match": "all", "rules": [
+            {"field": "favorite", "operator": "equals", "value": true},
+            {"field": "rating", "operator": "greater_than", "value": 3.5},
+            {"match": "any", "rules": [{"field": "rating", "operator": "is_empty"}]}
+        ]}"#,
+    );
+
+    let compiled = compile_rules(&rules, now()).unwrap();
+
+    assert_eq!(
+        compiled.sql,
+        "(b.is_favorite = 1 AND b.rating > ? AND (b.rating IS NULL))"
+    );
+    assert_eq!(compiled.binds, vec![RuleBind::Double(3.5)]);
+
+    let bad = parse(
+        r#"{"match": "all", "rules": [{"field": "favorite", "operator": "equals", "value": "yes"}]}"#,
+    );
+    assert!(matches!(
+        compile_rules(&bad, now()),
+        Err(DomainError::InvalidInput(_))
+    ));
+}
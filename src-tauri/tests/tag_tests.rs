@@ -0,0 +1,60 @@
+use stellaron_lib::domain::dto::tag_dto::TagDto;
+use stellaron_lib::domain::models::tag::{MAX_TAG_LENGTH, Tag, normalize_tag_name};
+use stellaron_lib::infrastructure::file_handlers::split_tag_list;
+
+#[test]
+fn test_normalize_tag_name() {
+    assert_eq!(
+        normalize_tag_name("  Science   Fiction ").as_deref(),
+        Some("Science Fiction")
+    );
+    assert_eq!(normalize_tag_name("Fantasy").as_deref(), Some("Fantasy"));
+    assert_eq!(normalize_tag_name(" \t "), None);
+    assert_eq!(normalize_tag_name(""), None);
+}
+
+#[test]
+fn test_normalize_tag_name_rejects_long_names() {
+    let at_limit = "a".repeat(MAX_TAG_LENGTH);
+    let too_long = "a".repeat(MAX_TAG_LENGTH + 1);
+
+    assert_eq!(normalize_tag_name(&at_limit), Some(at_limit.clone()));
+    assert_eq!(normalize_tag_name(&too_long), None);
+}
+
+#[test]
+fn test_split_tag_list_splits_keyword_strings() {
+    let tags = split_tag_list(&["Fantasy; Dragons", "Coming of age, Magic"]);
+
+    assert_eq!(tags, vec!["Fantasy", "Dragons", "Coming of age", "Magic"]);
+}
+
+#[test]
+fn test_split_tag_list_dedupes_case_insensitively() {
+    let tags = split_tag_list(&["Fantasy", "fantasy", " FANTASY ", "Sci-Fi"]);
+
+    assert_eq!(tags, vec!["Fantasy", "Sci-Fi"]);
+}
+
+#[test]
+fn test_split_tag_list_skips_blank_entries() {
+    let tags = split_tag_list(&["", " ; ,", "History"]);
+
+    assert_eq!(tags, vec!["History"]);
+    assert!(split_tag_list::<&str>(&[]).is_empty());
+}
+
+#[test]
+fn test_tag_dto_new() {
+    let dto = TagDto::new(
+        Tag {
+            id: 7,
+            name: "Mystery".into(),
+        },
+        3,
+    );
+
+    assert_eq!(dto.id, 7);
+    assert_eq!(dto.name, "Mystery");
+    assert_eq!(dto.book_count, 3);
+}